@@ -0,0 +1,31 @@
+use oblivion_ui::components::{Window, Canvas, View};
+use oblivion_ui::rendering::SDLEngine;
+use oblivion_ui::themes::Theme;
+use oblivion_ui::error::UiError;
+
+const CHART_WIDTH: f32 = 400.0;
+const CHART_HEIGHT: f32 = 200.0;
+
+fn main() -> Result<(), UiError> {
+    let (mut engine, redraw_trigger) = SDLEngine::new("Chart App", 800, 600)?;
+    let values = [3.0, 7.0, 4.0, 9.0, 5.0, 6.0];
+
+    let mut window = Window::new("Chart App".to_string(), 800, 600);
+
+    let theme = Theme::default();
+    let bar_color = theme.primary_color;
+    let max = values.iter().cloned().fold(0.0, f32::max);
+
+    // Bars grow up from the bottom of the canvas
+    let chart = Canvas::new(CHART_WIDTH, CHART_HEIGHT).on_draw(move |renderer, x, y| {
+        let slot = CHART_WIDTH / values.len() as f32;
+        for (i, value) in values.iter().enumerate() {
+            let height = value / max * CHART_HEIGHT;
+            let bar_x = x + i as f32 * slot + slot * 0.1;
+            renderer.draw_rect(bar_x, y + CHART_HEIGHT - height, slot * 0.8, height, bar_color);
+        }
+    });
+    window.add_child(Box::new(chart));
+
+    engine.run(Box::new(window) as Box<dyn View>, &theme, redraw_trigger)
+}
@@ -0,0 +1,26 @@
+use oblivion_ui::components::{Window, VStack, Input, Text, View};
+use oblivion_ui::state::State;
+use oblivion_ui::rendering::SDLEngine;
+use oblivion_ui::themes::Theme;
+use oblivion_ui::error::UiError;
+
+fn main() -> Result<(), UiError> {
+    let (mut engine, redraw_trigger) = SDLEngine::new("Form App", 800, 600)?;
+    let name = State::new(String::new(), redraw_trigger.clone());
+    let email = State::new(String::new(), redraw_trigger.clone());
+
+    let mut window = Window::new("Form App".to_string(), 800, 600);
+
+    let mut vstack = VStack::new(10.0).padding(20.0);
+
+    // Click a field to focus it; typing only goes to the focused field
+    vstack.add_child(Box::new(Text::new(State::new("Name".to_string(), redraw_trigger.clone()).binding())));
+    vstack.add_child(Box::new(Input::new(name.binding(), "Your name".to_string())));
+    vstack.add_child(Box::new(Text::new(State::new("Email".to_string(), redraw_trigger.clone()).binding())));
+    vstack.add_child(Box::new(Input::new(email.binding(), "you@example.com".to_string())));
+
+    window.add_child(Box::new(vstack));
+
+    let theme = Theme::default();
+    engine.run(Box::new(window) as Box<dyn View>, &theme, redraw_trigger)
+}
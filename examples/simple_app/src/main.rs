@@ -3,8 +3,6 @@ use oblivion_ui::state::State;
 use oblivion_ui::rendering::SDLEngine;
 use oblivion_ui::themes::Theme;
 use oblivion_ui::error::UiError;
-use std::rc::Rc;
-use std::cell::RefCell;
 
 fn main() -> Result<(), UiError> {
     let (mut engine, redraw_trigger) = SDLEngine::new("Simple App", 800, 600)?;
@@ -26,6 +24,14 @@ fn main() -> Result<(), UiError> {
     let animated_button = AnimatedView::new(Box::new(button), -200.0, 0.0, 2.0);
     vstack.add_child(Box::new(animated_button));
 
+    let themes = engine.theme_handle();
+    let theme_button = Button::new("Toggle theme".to_string())
+        .on_click(move || {
+            let next = if themes.get().is_dark { Theme::default() } else { Theme::dark() };
+            themes.set_theme(next);
+        });
+    vstack.add_child(Box::new(theme_button));
+
     window.add_child(Box::new(vstack));
 
     let theme = Theme::default();
@@ -0,0 +1,88 @@
+// Semantic description of the view tree for screen readers and UI automation
+
+use crate::components::{Bounds, View};
+
+/// What kind of control a view is, as reported to assistive technology.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Role {
+    Button,
+    Text,
+    Image,
+    TextField,
+    Checkbox,
+    RadioGroup,
+    Slider,
+    /// A number with increment and decrement buttons.
+    SpinButton,
+    ProgressBar,
+    ComboBox,
+    List,
+    TabList,
+    Alert,
+    /// A labelled container with no behaviour of its own.
+    Group,
+}
+
+/// Role and label a view reports about itself.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccessibilityInfo {
+    pub role: Role,
+    pub label: Option<String>,
+}
+
+impl AccessibilityInfo {
+    pub fn new(role: Role) -> Self {
+        AccessibilityInfo { role, label: None }
+    }
+
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+}
+
+/// One element of an accessibility snapshot.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccessibilityNode {
+    pub role: Role,
+    pub label: Option<String>,
+    /// Where the view was last drawn, if it records that.
+    pub bounds: Option<Bounds>,
+    pub children: Vec<AccessibilityNode>,
+}
+
+impl AccessibilityNode {
+    /// Depth-first search of this node and its descendants by label.
+    pub fn find(&self, label: &str) -> Option<&AccessibilityNode> {
+        if self.label.as_deref() == Some(label) {
+            return Some(self);
+        }
+        self.children.iter().find_map(|child| child.find(label))
+    }
+}
+
+/// Describes the tree under `root`. Views without accessibility info are
+/// left out and their children take their place, so the result may have
+/// several top-level nodes. Bounds come from the last render.
+pub fn snapshot(root: &dyn View) -> Vec<AccessibilityNode> {
+    let mut nodes = Vec::new();
+    collect(root, &mut nodes);
+    nodes
+}
+
+fn collect(view: &dyn View, nodes: &mut Vec<AccessibilityNode>) {
+    match view.accessibility() {
+        Some(info) => {
+            let mut children = Vec::new();
+            for child in view.accessibility_children() {
+                collect(child, &mut children);
+            }
+            nodes.push(AccessibilityNode { role: info.role, label: info.label, bounds: view.bounds(), children });
+        }
+        None => {
+            for child in view.accessibility_children() {
+                collect(child, nodes);
+            }
+        }
+    }
+}
@@ -0,0 +1,421 @@
+// Window system abstraction, so views don't depend on a particular backend
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::color::Color;
+use crate::components::{Bounds, Event, Renderer, View, ViewId};
+use crate::damage::{Damage, DamageTracker};
+use crate::debug::{draw_layout_outlines, layout_snapshot};
+use crate::environment::Environment;
+use crate::error::UiError;
+use crate::focus::{find_view, FocusManager, HoverTracker};
+use crate::lifecycle::LifecycleTracker;
+use crate::rendering::FrameClock;
+use crate::themes::{Theme, ThemeHandle};
+use crate::timer::{TimerHandle, TimerQueue};
+
+/// Identifies one of a backend's windows. Backends with a single surface
+/// can ignore it.
+pub type WindowId = u32;
+
+/// Input reported by a backend, already converted to framework events.
+pub enum BackendEvent {
+    Input(Event),
+    /// The pointer left the window.
+    PointerLeft,
+    /// Move focus backwards (Shift+Tab on most platforms).
+    FocusPrevious,
+    /// The window's contents were lost and must be repainted in full.
+    Exposed,
+    /// The window was closed. Closing the main window quits.
+    Closed,
+    Quit,
+}
+
+/// Source of input for the view trees of a backend's windows.
+pub trait EventSource {
+    /// Waits up to `timeout` for input, then returns everything pending
+    /// with the window it happened in. Unknown windows mean the main one.
+    fn poll_events(&mut self, timeout: Duration) -> Vec<(WindowId, BackendEvent)>;
+}
+
+/// Surfaces views can be drawn into, plus the input that goes with them.
+pub trait Backend: EventSource {
+    /// Clears `window` to `background`, lets `draw` render the frame, then
+    /// presents it.
+    fn draw_frame(&mut self, window: WindowId, background: Color, draw: &mut dyn FnMut(&mut dyn Renderer));
+
+    /// Like `draw_frame`, but only `region` changed since the last frame.
+    /// Backends that can't keep the rest of the frame repaint everything.
+    fn draw_region(&mut self, window: WindowId, background: Color, _region: Bounds, draw: &mut dyn FnMut(&mut dyn Renderer)) {
+        self.draw_frame(window, background, draw);
+    }
+
+    /// Device pixels per logical pixel of `window`.
+    fn scale_factor(&self, _window: WindowId) -> f32 {
+        1.0
+    }
+
+    /// Size of `window` in logical pixels, if the backend knows it.
+    fn window_size(&self, _window: WindowId) -> Option<(f32, f32)> {
+        None
+    }
+
+    /// The window with keyboard focus, if the backend knows it.
+    fn focused_window(&self) -> Option<WindowId> {
+        None
+    }
+}
+
+/// Draws a whole view tree: the views, their overlays and the focus ring.
+pub fn draw_tree(renderer: &mut dyn Renderer, focus: &FocusManager, view: &dyn View, env: &Environment) {
+    view.render(renderer, env, 0.0, 0.0);
+    view.render_overlay(renderer, env);
+    if let Some(bounds) = focus.focused().and_then(|id| find_view(view, id)).and_then(|v| v.bounds()) {
+        renderer.draw_rect_outline(bounds.x - 2.0, bounds.y - 2.0, bounds.width + 4.0, bounds.height + 4.0, 2.0, env.primary_color);
+    }
+}
+
+/// A window's view tree with its focus and hover state, independent of any
+/// backend.
+pub struct ViewHost {
+    window: WindowId,
+    root: Box<dyn View>,
+    focus: FocusManager,
+    hover: HoverTracker,
+    lifecycle: LifecycleTracker,
+    damage: DamageTracker,
+    redraw_trigger: Rc<RefCell<bool>>,
+    debug_layout: bool,
+}
+
+impl ViewHost {
+    pub fn new(window: WindowId, root: Box<dyn View>, redraw_trigger: Rc<RefCell<bool>>) -> Self {
+        ViewHost {
+            window,
+            root,
+            focus: FocusManager::new(),
+            hover: HoverTracker::new(),
+            lifecycle: LifecycleTracker::new(),
+            damage: DamageTracker::new(),
+            redraw_trigger,
+            debug_layout: false,
+        }
+    }
+
+    pub fn window(&self) -> WindowId {
+        self.window
+    }
+
+    pub fn root(&self) -> &dyn View {
+        self.root.as_ref()
+    }
+
+    /// Replaces the view tree, which redraws with `redraw_trigger`.
+    pub fn set_root(&mut self, root: Box<dyn View>, redraw_trigger: Rc<RefCell<bool>>) {
+        self.root = root;
+        self.redraw_trigger = redraw_trigger;
+        self.damage.invalidate();
+        self.request_redraw();
+    }
+
+    pub fn focus(&self) -> &FocusManager {
+        &self.focus
+    }
+
+    pub fn focus_mut(&mut self) -> &mut FocusManager {
+        &mut self.focus
+    }
+
+    /// Routes `event` to the tree without hover tracking, e.g. for gestures.
+    pub fn route(&mut self, event: &Event) {
+        self.focus.route(self.root.as_mut(), event);
+    }
+
+    /// Makes the next frame repaint everything, e.g. when something other
+    /// than this host drew over the window.
+    pub fn invalidate(&mut self) {
+        self.damage.invalidate();
+    }
+
+    /// Outlines every view's bounds over the normal frame.
+    pub fn set_debug_layout(&mut self, enabled: bool) {
+        self.debug_layout = enabled;
+        self.damage.invalidate();
+        self.request_redraw();
+    }
+
+    pub fn needs_redraw(&self) -> bool {
+        *self.redraw_trigger.borrow()
+    }
+
+    pub fn request_redraw(&self) {
+        *self.redraw_trigger.borrow_mut() = true;
+    }
+
+    /// Advances animations by `dt` seconds.
+    pub fn update(&mut self, dt: f64) {
+        let was_animating = self.root.is_animating();
+        self.root.update(dt);
+        // The last step of an animation changes the frame too
+        if was_animating || self.root.is_animating() {
+            self.damage.invalidate();
+            self.request_redraw();
+        }
+    }
+
+    /// Routes one event into the tree. Returns false on `Quit` or `Closed`.
+    pub fn handle_event(&mut self, event: &BackendEvent) -> bool {
+        match event {
+            BackendEvent::Quit | BackendEvent::Closed => return false,
+            BackendEvent::FocusPrevious => {
+                self.focus.focus_previous(self.root.as_mut());
+                self.request_redraw();
+            }
+            BackendEvent::Exposed => {
+                self.damage.invalidate();
+                self.request_redraw();
+            }
+            BackendEvent::PointerLeft => {
+                let hovered = self.hover.hovered().to_vec();
+                if self.hover.clear(self.root.as_mut()) {
+                    self.damage_hover_change(&hovered);
+                    self.request_redraw();
+                }
+            }
+            BackendEvent::Input(event) => {
+                if let Event::MouseMove { x, y } = event {
+                    let hovered = self.hover.hovered().to_vec();
+                    if self.hover.update(self.root.as_mut(), *x, *y) {
+                        self.damage_hover_change(&hovered);
+                        self.request_redraw();
+                    }
+                } else {
+                    // Whatever the event changes is only known once it's drawn
+                    self.damage.invalidate();
+                }
+                let focused = self.focus.focused();
+                self.focus.route(self.root.as_mut(), event);
+                // Redraw so the focus ring follows
+                if matches!(event, Event::Resize { .. }) {
+                    self.damage.invalidate();
+                    self.request_redraw();
+                } else if self.focus.focused() != focused {
+                    self.request_redraw();
+                }
+            }
+        }
+        true
+    }
+
+    // Views that started or stopped being hovered may draw differently
+    fn damage_hover_change(&mut self, hovered: &[ViewId]) {
+        let now = self.hover.hovered();
+        let changed = hovered.iter().filter(|id| !now.contains(id)).chain(now.iter().filter(|id| !hovered.contains(id)));
+        for id in changed {
+            match find_view(self.root.as_ref(), *id).and_then(|view| view.bounds()) {
+                Some(bounds) => self.damage.add(bounds),
+                None => self.damage.invalidate(),
+            }
+        }
+    }
+
+    /// Draws the tree into `backend` and clears the redraw flag. Only the
+    /// area that changed since the last frame is repainted: what the views
+    /// report through `View::damage` and what input changed.
+    pub fn render(&mut self, backend: &mut dyn Backend, theme: &Theme) {
+        let env = self.environment(backend, theme);
+        let (window, root, focus, debug_layout) = (self.window, self.root.as_ref(), &self.focus, self.debug_layout);
+        let mut draw = |renderer: &mut dyn Renderer| {
+            draw_tree(renderer, focus, root, &env);
+            if debug_layout {
+                draw_layout_outlines(renderer, root);
+            }
+        };
+        let damage = match self.damage.take(root, &env) {
+            // Outlines are drawn over everything
+            Damage::Region(_) if debug_layout => Damage::Full,
+            damage => damage,
+        };
+        match damage {
+            Damage::None => {}
+            Damage::Region(region) => backend.draw_region(window, env.background_color, region, &mut draw),
+            Damage::Full => backend.draw_frame(window, env.background_color, &mut draw),
+        }
+        self.lifecycle.update(root);
+        self.focus.collect_tab_order(root);
+        *self.redraw_trigger.borrow_mut() = false;
+    }
+
+    // The environment the tree is laid out and drawn with; the root is
+    // offered the whole window
+    fn environment(&self, backend: &dyn Backend, theme: &Theme) -> Environment<'static> {
+        let mut env = Environment::new(theme.clone()).with_scale_factor(backend.scale_factor(self.window));
+        if let Some((width, height)) = backend.window_size(self.window) {
+            env.available_width = Some(width);
+            env.available_height = Some(height);
+        }
+        env
+    }
+}
+
+/// The engine loop shared by every backend: each step advances animations,
+/// routes the backend's input into the windows' view trees, runs
+/// dispatchers and timers, and redraws the windows that changed.
+pub struct EventLoop {
+    // The first window is the main one; closing it ends the loop
+    windows: Vec<ViewHost>,
+    theme: ThemeHandle,
+    // Theme of the last step; a new one redraws every window
+    drawn_theme: Option<Theme>,
+    timers: TimerQueue,
+    // Drain each dispatcher's queue; true if any messages were applied
+    dispatchers: Vec<Box<dyn FnMut() -> bool>>,
+    // Raised by `SharedState` writes from other threads
+    shared_redraw: Arc<AtomicBool>,
+    // Shared with the F12 shortcut; applied to the windows on the next step
+    debug_layout: Rc<Cell<bool>>,
+    shown_debug_layout: bool,
+    frames: usize,
+}
+
+impl EventLoop {
+    pub fn new(main_window: WindowId, root_view: Box<dyn View>, redraw_trigger: Rc<RefCell<bool>>) -> Self {
+        EventLoop {
+            theme: ThemeHandle::new(Theme::default(), redraw_trigger.clone()),
+            drawn_theme: None,
+            windows: vec![ViewHost::new(main_window, root_view, redraw_trigger)],
+            timers: TimerQueue::new(),
+            dispatchers: Vec::new(),
+            shared_redraw: Arc::new(AtomicBool::new(false)),
+            debug_layout: Rc::new(Cell::new(false)),
+            shown_debug_layout: false,
+            frames: 0,
+        }
+    }
+
+    pub fn main_window(&self) -> &ViewHost {
+        &self.windows[0]
+    }
+
+    pub fn main_window_mut(&mut self) -> &mut ViewHost {
+        &mut self.windows[0]
+    }
+
+    pub fn window_mut(&mut self, window: WindowId) -> Option<&mut ViewHost> {
+        self.windows.iter_mut().find(|host| host.window == window)
+    }
+
+    /// Adds a window showing `root_view`, drawn on the next step.
+    pub fn add_window(&mut self, window: WindowId, root_view: Box<dyn View>, redraw_trigger: Rc<RefCell<bool>>) {
+        let mut host = ViewHost::new(window, root_view, redraw_trigger);
+        host.set_debug_layout(self.shown_debug_layout);
+        self.windows.push(host);
+    }
+
+    pub fn theme_handle(&self) -> ThemeHandle {
+        self.theme.clone()
+    }
+
+    pub fn shared_redraw_flag(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.shared_redraw)
+    }
+
+    pub fn add_dispatcher(&mut self, drain: impl FnMut() -> bool + 'static) {
+        self.dispatchers.push(Box::new(drain));
+    }
+
+    pub fn schedule_at(&mut self, due: Instant, interval: Option<Duration>, callback: impl FnMut() + 'static) -> TimerHandle {
+        self.timers.schedule_at(due, interval, callback)
+    }
+
+    /// Flag that turns layout debugging on for every window when set; see
+    /// `SDLEngine::set_debug_layout`.
+    pub fn debug_layout_flag(&self) -> Rc<Cell<bool>> {
+        Rc::clone(&self.debug_layout)
+    }
+
+    /// How many frames have been drawn.
+    pub fn frame_count(&self) -> usize {
+        self.frames
+    }
+
+    /// Runs one iteration of the loop at `now`, `dt` seconds after the last
+    /// one. Returns false once the backend asked to quit.
+    pub fn step(&mut self, backend: &mut dyn Backend, now: Instant, dt: f64) -> bool {
+        for host in &mut self.windows {
+            host.update(dt);
+        }
+
+        // With nothing to draw, block on input instead of spinning, but wake
+        // up in time for the next timer
+        let timeout = if self.windows.iter().any(ViewHost::needs_redraw) {
+            Duration::ZERO
+        } else {
+            self.timers.next_due().map_or(IDLE_WAIT, |due| due.saturating_duration_since(now).min(IDLE_WAIT))
+        };
+        for (window, event) in backend.poll_events(timeout) {
+            let index = self.windows.iter().position(|host| host.window == window).unwrap_or(0);
+            if !self.windows[index].handle_event(&event) {
+                if index == 0 || matches!(event, BackendEvent::Quit) {
+                    return false;
+                }
+                self.windows.remove(index);
+            }
+        }
+
+        let mut messages = false;
+        for drain in &mut self.dispatchers {
+            messages |= drain();
+        }
+        let timers_fired = self.timers.fire_due(now);
+        if messages || timers_fired || self.shared_redraw.swap(false, Ordering::AcqRel) {
+            self.windows.iter().for_each(ViewHost::request_redraw);
+        }
+        let dump_layout = self.debug_layout.get() && !self.shown_debug_layout;
+        if self.debug_layout.get() != self.shown_debug_layout {
+            self.shown_debug_layout = self.debug_layout.get();
+            for host in &mut self.windows {
+                host.set_debug_layout(self.shown_debug_layout);
+            }
+        }
+
+        // Read the theme every step so runtime switches take effect. The
+        // handle only raises the main window's trigger, so the others are
+        // asked here.
+        let theme = self.theme.get();
+        if self.drawn_theme.as_ref() != Some(&theme) {
+            self.windows.iter().for_each(ViewHost::request_redraw);
+            self.drawn_theme = Some(theme.clone());
+        }
+        for host in &mut self.windows {
+            if host.needs_redraw() {
+                host.render(backend, &theme);
+                self.frames += 1;
+            }
+        }
+        // Printed once laid out, so the snapshot has positions
+        if dump_layout {
+            let focused = backend.focused_window().and_then(|id| self.windows.iter().find(|host| host.window() == id));
+            let host = focused.unwrap_or(&self.windows[0]);
+            print!("{}", layout_snapshot(host.root(), &host.environment(backend, &theme)));
+        }
+        true
+    }
+}
+
+/// Runs `root_view` on `backend` until it reports `Quit`.
+pub fn run(backend: &mut dyn Backend, root_view: Box<dyn View>, theme: &Theme, redraw_trigger: Rc<RefCell<bool>>) -> Result<(), UiError> {
+    let mut event_loop = EventLoop::new(0, root_view, redraw_trigger);
+    event_loop.theme_handle().set_default(theme.clone());
+    let mut clock = FrameClock::new();
+    while event_loop.step(backend, Instant::now(), clock.tick()) {}
+    Ok(())
+}
+
+// Longest the loop blocks waiting for input when nothing is animating
+const IDLE_WAIT: Duration = Duration::from_millis(100);
@@ -1,6 +1,14 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use oblivion_ui::components::View;
+use oblivion_ui::error::UiError;
+use oblivion_ui::rendering::SDLEngine;
+use oblivion_ui::rso::{load_rso, RsoRegistry, RsoReloader};
+use oblivion_ui::themes::Theme;
 use std::fs;
-use std::path::Path;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
 
 #[derive(Parser)]
 #[command(name = "oblivion-cli")]
@@ -16,47 +24,253 @@ enum Commands {
     New {
         /// Name of the project
         name: String,
+        /// Starter app to generate
+        #[arg(long, value_enum, default_value_t = Template::Hello)]
+        template: Template,
     },
-    /// Build the project
-    Build,
-    /// Run the project
-    Run,
+    /// Build the project; extra arguments are passed to cargo
+    Build {
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Run the project; extra arguments are passed to cargo
+    Run {
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Open a window showing a .rso UI definition
+    Preview {
+        /// Path to the .rso file
+        file: PathBuf,
+        /// Reload the view whenever the file changes
+        #[arg(long)]
+        watch: bool,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Template {
+    /// A window with a single label
+    Hello,
+    /// A label and a button that increments it
+    Counter,
+    /// A list built with ForEach
+    List,
+    /// Labelled text inputs
+    Form,
+}
+
+impl Template {
+    fn main_rs(self) -> &'static str {
+        match self {
+            Template::Hello => HELLO_MAIN_RS,
+            Template::Counter => COUNTER_MAIN_RS,
+            Template::List => LIST_MAIN_RS,
+            Template::Form => FORM_MAIN_RS,
+        }
+    }
 }
 
 fn main() {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::New { name } => {
-            create_project(&name);
-        }
-        Commands::Build => {
+        Commands::New { name, template } => match create_project(Path::new("."), &name, template) {
+            Ok(()) => {
+                println!("Project {} created successfully!", name);
+                println!("Run 'cd {} && cargo run' to start", name);
+            }
+            Err(e) => {
+                eprintln!("Failed to create project {}: {}", name, e);
+                std::process::exit(1);
+            }
+        },
+        Commands::Build { args } => {
             println!("Building project...");
-            // Run cargo build
-            std::process::Command::new("cargo")
-                .arg("build")
-                .status()
-                .expect("Failed to build");
+            run_cargo(cargo_args("build", &args));
         }
-        Commands::Run => {
+        Commands::Run { args } => {
             println!("Running project...");
-            std::process::Command::new("cargo")
-                .arg("run")
-                .status()
-                .expect("Failed to run");
+            run_cargo(cargo_args("run", &args));
+        }
+        Commands::Preview { file, watch } => {
+            if let Err(e) = preview(&file, watch) {
+                eprintln!("Failed to preview {}: {}", file.display(), e);
+                std::process::exit(1);
+            }
         }
     }
 }
 
-fn create_project(name: &str) {
-    let project_dir = Path::new(name);
+// Argument list for cargo: the subcommand followed by the user's extra args
+fn cargo_args(subcommand: &str, extra: &[String]) -> Vec<String> {
+    std::iter::once(subcommand.to_string()).chain(extra.iter().cloned()).collect()
+}
+
+// Runs cargo and exits with its status code
+fn run_cargo(args: Vec<String>) -> ! {
+    match std::process::Command::new("cargo").args(&args).status() {
+        Ok(status) => std::process::exit(status.code().unwrap_or(1)),
+        Err(e) => {
+            eprintln!("Failed to run cargo: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+// Reads and parses a .rso file
+fn load_preview(file: &Path) -> Result<Box<dyn View>, UiError> {
+    let content = fs::read_to_string(file)?;
+    load_rso(&content, &RsoRegistry::new())
+}
+
+fn preview(file: &Path, watch: bool) -> Result<(), UiError> {
+    let title = format!("Preview - {}", file.display());
+    if !watch {
+        let view = load_preview(file)?;
+        let (mut engine, redraw_trigger) = SDLEngine::new(&title, 800, 600)?;
+        engine.on_error(|e| eprintln!("{}", e));
+        return engine.run(view, &Theme::default(), redraw_trigger);
+    }
+
+    let reloader = RsoReloader::new(&fs::read_to_string(file)?, RsoRegistry::new())?;
+    let (mut engine, redraw_trigger) = SDLEngine::new(&title, 800, 600)?;
+    engine.on_error(|e| eprintln!("{}", e));
+    let (sender, updates) = mpsc::channel();
+    // Kept alive for the duration of `run`
+    let _watcher = watch_file(file, sender)?;
+    let reloader = reloader
+        .watch(updates, redraw_trigger.clone())
+        .on_error(|e| eprintln!("Keeping the previous view: {}", e));
+    engine.run(Box::new(reloader), &Theme::default(), redraw_trigger)
+}
+
+// Sends the file's new contents whenever it's written. Watches the parent
+// directory so editors that save by replacing the file are still seen.
+fn watch_file(file: &Path, sender: mpsc::Sender<String>) -> Result<RecommendedWatcher, UiError> {
+    let file = file.canonicalize()?;
+    let dir = file.parent().map(Path::to_path_buf).unwrap_or_else(|| PathBuf::from("."));
+    let target = file.clone();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let Ok(event) = event else {
+            return;
+        };
+        if !(event.kind.is_modify() || event.kind.is_create()) || !event.paths.contains(&target) {
+            return;
+        }
+        if let Ok(content) = fs::read_to_string(&target) {
+            let _ = sender.send(content);
+        }
+    })
+    .map_err(|e| UiError::IoError(io::Error::other(e)))?;
+    watcher
+        .watch(&dir, RecursiveMode::NonRecursive)
+        .map_err(|e| UiError::IoError(io::Error::other(e)))?;
+    Ok(watcher)
+}
+
+// Generated src/main.rs for each template; `{name}` is the project name
+const HELLO_MAIN_RS: &str = r#"use oblivion_ui::components::{Text, View, Window};
+use oblivion_ui::error::UiError;
+use oblivion_ui::rendering::SDLEngine;
+use oblivion_ui::state::State;
+use oblivion_ui::themes::Theme;
+
+fn main() -> Result<(), UiError> {
+    let (mut engine, redraw_trigger) = SDLEngine::new("{name}", 800, 600)?;
+    let greeting = State::new("Hello, Oblivion UI!".to_string(), redraw_trigger.clone());
+
+    let mut window = Window::new("{name}".to_string(), 800, 600);
+    window.add_child(Box::new(Text::new(greeting.binding())));
+
+    let theme = Theme::default();
+    engine.run(Box::new(window) as Box<dyn View>, &theme, redraw_trigger)
+}
+"#;
+
+const COUNTER_MAIN_RS: &str = r#"use oblivion_ui::components::{Button, Text, VStack, View, Window};
+use oblivion_ui::error::UiError;
+use oblivion_ui::rendering::SDLEngine;
+use oblivion_ui::state::State;
+use oblivion_ui::themes::Theme;
+
+fn main() -> Result<(), UiError> {
+    let (mut engine, redraw_trigger) = SDLEngine::new("{name}", 800, 600)?;
+    let counter = State::new("0".to_string(), redraw_trigger.clone());
+
+    let mut window = Window::new("{name}".to_string(), 800, 600);
+    let mut vstack = VStack::new(10.0);
+    vstack.add_child(Box::new(Text::new(counter.binding())));
+
+    let button = Button::new("Increment".to_string()).on_click(move || {
+        let current: i32 = counter.get().parse().unwrap_or(0);
+        counter.set((current + 1).to_string());
+    });
+    vstack.add_child(Box::new(button));
+    window.add_child(Box::new(vstack));
+
+    let theme = Theme::default();
+    engine.run(Box::new(window) as Box<dyn View>, &theme, redraw_trigger)
+}
+"#;
+
+const LIST_MAIN_RS: &str = r#"use oblivion_ui::components::{ForEach, Text, VStack, View, Window};
+use oblivion_ui::error::UiError;
+use oblivion_ui::rendering::SDLEngine;
+use oblivion_ui::state::State;
+use oblivion_ui::themes::Theme;
+
+fn main() -> Result<(), UiError> {
+    let (mut engine, redraw_trigger) = SDLEngine::new("{name}", 800, 600)?;
+    let items = vec!["Item 1".to_string(), "Item 2".to_string(), "Item 3".to_string()];
+
+    let mut window = Window::new("{name}".to_string(), 800, 600);
+    let mut vstack = VStack::new(10.0);
+    let list = ForEach::from_vec(items, |item| {
+        Box::new(Text::new(State::new(item, redraw_trigger.clone()).binding())) as Box<dyn View>
+    });
+    vstack.add_child(Box::new(list));
+    window.add_child(Box::new(vstack));
+
+    let theme = Theme::default();
+    engine.run(Box::new(window) as Box<dyn View>, &theme, redraw_trigger)
+}
+"#;
+
+const FORM_MAIN_RS: &str = r#"use oblivion_ui::components::{Input, Text, VStack, View, Window};
+use oblivion_ui::error::UiError;
+use oblivion_ui::rendering::SDLEngine;
+use oblivion_ui::state::State;
+use oblivion_ui::themes::Theme;
+
+fn main() -> Result<(), UiError> {
+    let (mut engine, redraw_trigger) = SDLEngine::new("{name}", 800, 600)?;
+    let name = State::new(String::new(), redraw_trigger.clone());
+    let email = State::new(String::new(), redraw_trigger.clone());
+
+    let mut window = Window::new("{name}".to_string(), 800, 600);
+    let mut vstack = VStack::new(10.0);
+    // Click a field to focus it; typing only goes to the focused field
+    vstack.add_child(Box::new(Text::new(State::new("Name".to_string(), redraw_trigger.clone()).binding())));
+    vstack.add_child(Box::new(Input::new(name.binding(), "Your name".to_string())));
+    vstack.add_child(Box::new(Text::new(State::new("Email".to_string(), redraw_trigger.clone()).binding())));
+    vstack.add_child(Box::new(Input::new(email.binding(), "you@example.com".to_string())));
+    window.add_child(Box::new(vstack));
+
+    let theme = Theme::default();
+    engine.run(Box::new(window) as Box<dyn View>, &theme, redraw_trigger)
+}
+"#;
+
+// Creates the project `name` inside `parent`
+fn create_project(parent: &Path, name: &str, template: Template) -> io::Result<()> {
+    let project_dir = parent.join(name);
 
     if project_dir.exists() {
-        eprintln!("Project {} already exists!", name);
-        return;
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("{} already exists", project_dir.display())));
     }
 
-    fs::create_dir(project_dir).expect("Failed to create project directory");
+    fs::create_dir_all(project_dir.join("src"))?;
 
     // Create Cargo.toml
     let cargo_toml = format!(r#"[package]
@@ -67,15 +281,102 @@ edition = "2021"
 [dependencies]
 oblivion_ui = {{ path = "../oblivion_ui" }}
 "#, name);
-
-    fs::write(project_dir.join("Cargo.toml"), cargo_toml).expect("Failed to write Cargo.toml");
+    fs::write(project_dir.join("Cargo.toml"), cargo_toml)?;
 
     // Create src/main.rs
-    let main_rs = "fn main() {\n    println!(\"Hello, Oblivion UI!\");\n}\n";
+    fs::write(project_dir.join("src/main.rs"), template.main_rs().replace("{name}", name))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    fs::create_dir(project_dir.join("src")).expect("Failed to create src directory");
-    fs::write(project_dir.join("src/main.rs"), main_rs).expect("Failed to write main.rs");
+    #[test]
+    fn test_create_project_writes_manifest_and_main() {
+        let parent = std::env::temp_dir().join(format!("oblivion_cli_{}", std::process::id()));
+        fs::create_dir_all(&parent).unwrap();
+        create_project(&parent, "demo_app", Template::Hello).unwrap();
 
-    println!("Project {} created successfully!", name);
-    println!("Run 'cd {} && cargo run' to start", name);
-}
\ No newline at end of file
+        let cargo_toml = fs::read_to_string(parent.join("demo_app/Cargo.toml")).unwrap();
+        let main_rs = fs::read_to_string(parent.join("demo_app/src/main.rs")).unwrap();
+        // A second attempt must not clobber the project
+        let again = create_project(&parent, "demo_app", Template::Hello);
+        fs::remove_dir_all(&parent).unwrap();
+
+        assert!(cargo_toml.contains("name = \"demo_app\""));
+        assert!(cargo_toml.contains("oblivion_ui = { path = \"../oblivion_ui\" }"));
+        assert!(main_rs.contains("SDLEngine::new(\"demo_app\", 800, 600)?"));
+        assert!(main_rs.contains("Text::new(greeting.binding())"));
+        assert_eq!(again.unwrap_err().kind(), io::ErrorKind::AlreadyExists);
+    }
+
+    #[test]
+    fn test_counter_template() {
+        let cli = Cli::try_parse_from(["oblivion-cli", "new", "counter_app", "--template", "counter"]).unwrap();
+        let Commands::New { template, .. } = cli.command else {
+            panic!("expected the new command");
+        };
+        assert_eq!(template, Template::Counter);
+        assert!(Cli::try_parse_from(["oblivion-cli", "new", "app", "--template", "todo"]).is_err());
+
+        let parent = std::env::temp_dir().join(format!("oblivion_cli_counter_{}", std::process::id()));
+        fs::create_dir_all(&parent).unwrap();
+        create_project(&parent, "counter_app", template).unwrap();
+        let main_rs = fs::read_to_string(parent.join("counter_app/src/main.rs")).unwrap();
+        fs::remove_dir_all(&parent).unwrap();
+
+        assert!(main_rs.contains("Button::new(\"Increment\".to_string()).on_click(move ||"));
+        assert!(main_rs.contains("SDLEngine::new(\"counter_app\", 800, 600)?"));
+    }
+
+    #[test]
+    fn test_preview_loads_rso_file() {
+        use oblivion_ui::color::Color;
+        use oblivion_ui::components::Renderer;
+        use oblivion_ui::environment::Environment;
+
+        // Collects drawn text, standing in for the SDL renderer
+        #[derive(Default)]
+        struct TextLog(Vec<String>);
+        impl Renderer for TextLog {
+            fn draw_text(&mut self, text: &str, _x: f32, _y: f32, _font_size: u32, _color: Color) {
+                self.0.push(text.to_string());
+            }
+            fn draw_rect(&mut self, _x: f32, _y: f32, _w: f32, _h: f32, _color: Color) {}
+            fn draw_round_rect(&mut self, _x: f32, _y: f32, _w: f32, _h: f32, _radius: f32, _color: Color) {}
+            fn draw_rect_outline(&mut self, _x: f32, _y: f32, _w: f32, _h: f32, _thickness: f32, _color: Color) {}
+        }
+
+        let path = std::env::temp_dir().join(format!("oblivion_preview_{}.rso", std::process::id()));
+        fs::write(&path, "VStack(4) { Text('Hello from .rso') HStack { Button('Click') Spacer } }").unwrap();
+        let view = load_preview(&path).unwrap();
+
+        let mut log = TextLog::default();
+        view.render(&mut log, &Environment::default(), 0.0, 0.0);
+        assert_eq!(log.0, vec!["Hello from .rso", "Click"]);
+
+        // Mistakes in the file are reported by the parser, not shown as a fallback view
+        fs::write(&path, "VStack { Text('unclosed) }").unwrap();
+        assert!(matches!(load_preview(&path), Err(UiError::ParseError(_))));
+        fs::write(&path, "VStack { Marquee('Hi') }").unwrap();
+        assert!(matches!(load_preview(&path), Err(UiError::ParseError(_))));
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(load_preview(&path), Err(UiError::IoError(_))));
+    }
+
+    #[test]
+    fn test_cargo_args_forward_extra_arguments() {
+        let cli = Cli::try_parse_from(["oblivion-cli", "run", "--release", "--features", "gpu", "--", "--fullscreen"]).unwrap();
+        let Commands::Run { args } = cli.command else {
+            panic!("expected the run command");
+        };
+        assert_eq!(
+            cargo_args("run", &args),
+            vec!["run", "--release", "--features", "gpu", "--", "--fullscreen"]
+        );
+        assert_eq!(cargo_args("build", &[]), vec!["build"]);
+    }
+}
@@ -0,0 +1,75 @@
+// RGBA color values shared by themes and renderers
+
+use crate::error::UiError;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+    pub const CLEAR: Color = Color::rgba(0, 0, 0, 0);
+
+    /// Opaque color.
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Color { r, g, b, a: 255 }
+    }
+
+    pub const fn rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Color { r, g, b, a }
+    }
+
+    /// Parses `#rgb`, `#rrggbb` or `#rrggbbaa`. The leading `#` is optional.
+    pub fn from_hex(s: &str) -> Result<Color, UiError> {
+        let invalid = || UiError::ThemeError(format!("invalid hex color '{}'", s));
+        let digits = s.strip_prefix('#').unwrap_or(s);
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+        let channel = |i: usize, len: usize| u8::from_str_radix(&digits[i..i + len], 16).map_err(|_| invalid());
+        match digits.len() {
+            // Short form repeats each digit: #f80 == #ff8800
+            3 => Ok(Color::rgb(channel(0, 1)? * 17, channel(1, 1)? * 17, channel(2, 1)? * 17)),
+            6 => Ok(Color::rgb(channel(0, 2)?, channel(2, 2)?, channel(4, 2)?)),
+            8 => Ok(Color::rgba(channel(0, 2)?, channel(2, 2)?, channel(4, 2)?, channel(6, 2)?)),
+            _ => Err(invalid()),
+        }
+    }
+
+    /// The color with its alpha scaled by `opacity`, from 0 to 1.
+    pub fn faded(self, opacity: f32) -> Color {
+        Color { a: (self.a as f32 * opacity.clamp(0.0, 1.0)).round() as u8, ..self }
+    }
+
+    /// `#rrggbb`, or `#rrggbbaa` when the color isn't opaque.
+    pub fn to_hex(&self) -> String {
+        if self.a == 255 {
+            format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+        } else {
+            format!("#{:02x}{:02x}{:02x}{:02x}", self.r, self.g, self.b, self.a)
+        }
+    }
+}
+
+impl From<(u8, u8, u8)> for Color {
+    fn from((r, g, b): (u8, u8, u8)) -> Self {
+        Color::rgb(r, g, b)
+    }
+}
+
+impl From<(u8, u8, u8, u8)> for Color {
+    fn from((r, g, b, a): (u8, u8, u8, u8)) -> Self {
+        Color::rgba(r, g, b, a)
+    }
+}
+
+impl From<Color> for (u8, u8, u8) {
+    fn from(color: Color) -> Self {
+        (color.r, color.g, color.b)
+    }
+}
@@ -1,79 +1,524 @@
-use crate::state::{Binding, State};
-use crate::themes::Theme;
+use crate::accessibility::{AccessibilityInfo, Role};
+use crate::state::{Binding, Dependency, State, Version};
+use crate::lifecycle::{LifecycleCallback, LifecycleHooks};
+use crate::color::Color;
+use crate::damage::{children_damage, Damage, DrawnVersion};
+use crate::date::{Date, DateField};
+use crate::environment::Environment;
+use crate::error::UiError;
+use crate::key::Key;
+use crate::rso::{RsoElement, RsoValue};
+use crate::scene::{reconcile, NodeId};
+use crate::texture::TextureId;
+use crate::themes::{TextRole, Theme};
+use std::hash::Hash;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::sync::atomic::{AtomicU64, Ordering};
 
 pub trait View {
-    fn render(&self, renderer: &mut dyn Renderer, theme: &Theme, x: f32, y: f32);
-    fn handle_event(&mut self, event: &Event);
-    fn update(&mut self, _dt: f64) {}
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32);
+    /// Second pass drawn after the whole tree, for popups that must sit above
+    /// siblings. Uses positions recorded during `render`.
+    fn render_overlay(&self, renderer: &mut dyn Renderer, env: &Environment) {
+        for child in self.children() {
+            child.render_overlay(renderer, env);
+        }
+    }
+    /// Area covered by an open overlay; clicks there go only to this view.
+    fn overlay_bounds(&self) -> Option<Bounds> {
+        None
+    }
+    /// Closes the open overlay after a click landed somewhere else, even if
+    /// another view consumed that click.
+    fn dismiss_overlay(&mut self) {}
+    /// While true, every event goes to this view and nothing else.
+    fn is_modal(&self) -> bool {
+        false
+    }
+    /// Returns true when the view consumed the event, which stops it from
+    /// reaching the views drawn beneath. Containers offer events to their
+    /// children topmost first.
+    fn handle_event(&mut self, event: &Event) -> bool;
+    /// Advances time-based state. The default forwards to the children.
+    fn update(&mut self, dt: f64) {
+        for child in self.children_mut() {
+            child.update(dt);
+        }
+    }
+    /// Whether the view (or a descendant) is mid-animation and needs redraws.
+    fn is_animating(&self) -> bool {
+        self.children().iter().any(|child| child.is_animating())
+    }
+    /// What changed since the view was last drawn, so a redraw only
+    /// repaints that. Input the host routed is accounted for already; this
+    /// covers changes made elsewhere, such as a state written by a timer.
+    /// The default asks for a full repaint.
+    fn damage(&self) -> Damage {
+        Damage::Full
+    }
+    /// Preferred `(width, height)` of the view, used by containers for layout.
+    fn size(&self, _env: &Environment) -> (f32, f32) {
+        (0.0, 0.0)
+    }
+    /// Rectangle covered by the last render, for views that hit-test.
+    fn bounds(&self) -> Option<Bounds> {
+        None
+    }
+    /// The view's Rust type, for debugging output.
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+    /// Where the view was last drawn, for inspecting layout. Containers
+    /// report it here without being hit-tested through `bounds`.
+    fn layout_bounds(&self) -> Option<Bounds> {
+        self.bounds()
+    }
+    fn id(&self) -> Option<ViewId> {
+        None
+    }
+    fn can_focus(&self) -> bool {
+        false
+    }
+    /// Whether the view and its descendants accept input. Focus skips
+    /// disabled subtrees.
+    fn is_enabled(&self) -> bool {
+        true
+    }
+    /// Whether a `Window` should place this view at its edges instead of
+    /// inside its safe area.
+    fn extends_to_edges(&self) -> bool {
+        false
+    }
+    /// Share of a stack's spare room this view takes along the stack's axis,
+    /// relative to its flexible siblings. 0 keeps the view at its own size.
+    fn flex(&self) -> f32 {
+        0.0
+    }
+    /// Whether stacks may stretch this view along their axis to fill spare room.
+    fn is_flexible(&self) -> bool {
+        self.flex() > 0.0
+    }
+    fn set_focused(&mut self, _focused: bool) {}
+    fn children(&self) -> Vec<&dyn View> {
+        Vec::new()
+    }
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        Vec::new()
+    }
+    fn lifecycle(&self) -> Option<LifecycleHooks> {
+        None
+    }
+    /// Role and label for assistive technology; `None` for purely structural views.
+    fn accessibility(&self) -> Option<AccessibilityInfo> {
+        None
+    }
+    /// Children to describe below this view in an accessibility snapshot.
+    fn accessibility_children(&self) -> Vec<&dyn View> {
+        self.children()
+    }
+    /// How this view is written in `.rso` source; `None` for views `to_rso`
+    /// can't express, which are left out in favour of their children.
+    fn rso_element(&self) -> Option<RsoElement> {
+        None
+    }
+}
+
+/// Identifies a view instance, e.g. for focus tracking.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ViewId(u64);
+
+impl ViewId {
+    pub fn next() -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+        ViewId(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Size {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Size {
+    pub fn new(width: f32, height: f32) -> Self {
+        Size { width, height }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Bounds {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Bounds {
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Bounds { x, y, width, height }
+    }
+
+    pub fn contains(&self, px: f32, py: f32) -> bool {
+        px >= self.x && px < self.x + self.width && py >= self.y && py < self.y + self.height
+    }
+
+    /// Overlapping area of two rectangles; empty (zero-sized) if disjoint.
+    pub fn intersect(&self, other: &Bounds) -> Bounds {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let right = (self.x + self.width).min(other.x + other.width);
+        let bottom = (self.y + self.height).min(other.y + other.height);
+        Bounds::new(x, y, (right - x).max(0.0), (bottom - y).max(0.0))
+    }
+
+    /// Smallest rectangle covering both.
+    pub fn union(&self, other: &Bounds) -> Bounds {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        let right = (self.x + self.width).max(other.x + other.width);
+        let bottom = (self.y + self.height).max(other.y + other.height);
+        Bounds::new(x, y, right - x, bottom - y)
+    }
+
+    /// Whether the two rectangles share any area.
+    pub fn intersects(&self, other: &Bounds) -> bool {
+        !self.intersect(other).is_empty()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width <= 0.0 || self.height <= 0.0
+    }
+}
+
+/// Space kept clear along each edge of a rectangle.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct EdgeInsets {
+    pub top: f32,
+    pub leading: f32,
+    pub bottom: f32,
+    pub trailing: f32,
+}
+
+impl EdgeInsets {
+    pub fn new(top: f32, leading: f32, bottom: f32, trailing: f32) -> Self {
+        EdgeInsets { top, leading, bottom, trailing }
+    }
+
+    /// The same inset on every edge.
+    pub fn all(inset: f32) -> Self {
+        Self::new(inset, inset, inset, inset)
+    }
+}
+
+// Placeholder text metrics, matching the block glyphs drawn by SDLRenderer
+pub fn measure_text(text: &str, font_size: u32) -> (f32, f32) {
+    let size = font_size as f32;
+    (text.chars().count() as f32 * size * 0.6, size * 1.4)
+}
+
+/// Breaks `text` into lines no wider than `max_width`, splitting at spaces.
+/// A single word that doesn't fit gets a line of its own.
+pub fn wrap_text(text: &str, font_size: u32, max_width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let candidate = if line.is_empty() { word.to_string() } else { format!("{} {}", line, word) };
+            if !line.is_empty() && measure_text(&candidate, font_size).0 > max_width {
+                lines.push(std::mem::replace(&mut line, word.to_string()));
+            } else {
+                line = candidate;
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Curve applied to an animation's progress.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Easing {
+    #[default]
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+
+impl Easing {
+    /// Maps linear progress `t` in `0..=1` onto the curve.
+    pub fn apply(self, t: f64) -> f64 {
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+                }
+            }
+        }
+    }
+}
+
+/// Animatable properties of an `AnimatedView`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AnimatedProperties {
+    pub x: f32,
+    pub y: f32,
+    pub opacity: f32,
+}
+
+impl Default for AnimatedProperties {
+    fn default() -> Self {
+        AnimatedProperties { x: 0.0, y: 0.0, opacity: 1.0 }
+    }
+}
+
+impl AnimatedProperties {
+    fn lerp(&self, to: &AnimatedProperties, t: f32) -> AnimatedProperties {
+        AnimatedProperties {
+            x: self.x + (to.x - self.x) * t,
+            y: self.y + (to.y - self.y) * t,
+            opacity: self.opacity + (to.opacity - self.opacity) * t,
+        }
+    }
 }
 
 pub struct AnimatedView {
     pub view: Box<dyn View>,
     pub offset_x: f32,
-    pub start_offset: f32,
-    pub end_offset: f32,
+    pub offset_y: f32,
+    pub opacity: f32,
+    pub from: AnimatedProperties,
+    pub to: AnimatedProperties,
     pub duration: f64,
     pub elapsed: f64,
+    pub easing: Easing,
 }
 
 impl AnimatedView {
+    /// Slides the view horizontally from `start_offset` to `end_offset`.
     pub fn new(view: Box<dyn View>, start_offset: f32, end_offset: f32, duration: f64) -> Self {
+        let from = AnimatedProperties { x: start_offset, ..Default::default() };
+        let to = AnimatedProperties { x: end_offset, ..Default::default() };
+        Self::new_full(view, from, to, duration)
+    }
+
+    /// Tweens position and opacity together.
+    pub fn new_full(view: Box<dyn View>, from: AnimatedProperties, to: AnimatedProperties, duration: f64) -> Self {
         AnimatedView {
             view,
-            offset_x: start_offset,
-            start_offset,
-            end_offset,
+            offset_x: from.x,
+            offset_y: from.y,
+            opacity: from.opacity,
+            from,
+            to,
             duration,
             elapsed: 0.0,
+            easing: Easing::Linear,
         }
     }
+
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
 }
 
 impl View for AnimatedView {
-    fn render(&self, renderer: &mut dyn Renderer, theme: &Theme, x: f32, y: f32) {
-        self.view.render(renderer, theme, x + self.offset_x, y);
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        render_faded(self.view.as_ref(), renderer, env, x + self.offset_x, y + self.offset_y, self.opacity);
     }
 
-    fn handle_event(&mut self, event: &Event) {
-        self.view.handle_event(event);
+    fn handle_event(&mut self, event: &Event) -> bool {
+        self.view.handle_event(event)
     }
 
     fn update(&mut self, dt: f64) {
+        self.view.update(dt);
         self.elapsed += dt;
-        let t = (self.elapsed / self.duration).min(1.0);
-        self.offset_x = self.start_offset + (self.end_offset - self.start_offset) * t as f32;
+        let t = self.easing.apply((self.elapsed / self.duration).min(1.0));
+        let current = self.from.lerp(&self.to, t as f32);
+        self.offset_x = current.x;
+        self.offset_y = current.y;
+        self.opacity = current.opacity;
+    }
+
+    fn is_animating(&self) -> bool {
+        self.elapsed < self.duration || self.view.is_animating()
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        vec![self.view.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        vec![self.view.as_mut()]
     }
+
+    fn size(&self, env: &Environment) -> (f32, f32) {
+        self.view.size(env)
+    }
+}
+
+type ViewBuilder = Box<dyn Fn() -> Vec<Box<dyn View>>>;
+// Takes the current rows with their ids and returns the reconciled ones
+type KeyedViewBuilder = Box<dyn Fn(Vec<(NodeId, Box<dyn View>)>) -> Vec<(NodeId, Box<dyn View>)>>;
+
+enum Rows {
+    // Every row is rebuilt when the collection changes
+    Rebuild(ViewBuilder),
+    // Rows whose key survives are kept, with their state and animations
+    Keyed(KeyedViewBuilder),
 }
 
 pub struct ForEach {
     pub views: Vec<Box<dyn View>>,
+    // Identity of each row in `views`; keyed rows keep theirs across rebuilds
+    ids: Vec<NodeId>,
+    // Bound collection: its version counter and the builder for its rows
+    source: Option<(Version, Rows)>,
+    built_version: u64,
 }
 
 impl ForEach {
-    pub fn new<I, F>(iter: I, mut f: F) -> Self
+    /// Builds one view per item of the bound collection, rebuilding the
+    /// rows whenever the collection is written.
+    pub fn new<T, F>(items: Binding<Vec<T>>, f: F) -> Self
+    where
+        T: Clone + 'static,
+        F: Fn(&T) -> Box<dyn View> + 'static,
+    {
+        let version = items.version_counter();
+        let build: ViewBuilder = Box::new(move || items.get().iter().map(&f).collect());
+        let views = build();
+        ForEach {
+            ids: positional_ids(views.len()),
+            views,
+            built_version: version.get(),
+            source: Some((version, Rows::Rebuild(build))),
+        }
+    }
+
+    /// Like `new`, but each row is identified by `key(item)`. On a change
+    /// only rows with new keys are built; the others are kept as they are,
+    /// mid-animation included, so anything a row shows that can change
+    /// should come from state rather than from the item.
+    pub fn keyed<T, K, KF, F>(items: Binding<Vec<T>>, key: KF, f: F) -> Self
+    where
+        T: Clone + 'static,
+        K: Hash,
+        KF: Fn(&T) -> K + 'static,
+        F: Fn(&T) -> Box<dyn View> + 'static,
+    {
+        let version = items.version_counter();
+        let build: KeyedViewBuilder = Box::new(move |previous| {
+            let items = items.get();
+            let ids: Vec<NodeId> = items.iter().map(|item| NodeId::from_key(&key(item))).collect();
+            reconcile(previous, &ids, |index| f(&items[index]))
+        });
+        let (ids, views) = build(Vec::new()).into_iter().unzip();
+        ForEach {
+            views,
+            ids,
+            built_version: version.get(),
+            source: Some((version, Rows::Keyed(build))),
+        }
+    }
+
+    /// Builds the views once from a static collection.
+    pub fn from_vec<I, F>(iter: I, f: F) -> Self
     where
         I: IntoIterator,
         F: FnMut(I::Item) -> Box<dyn View>,
     {
-        let views = iter.into_iter().map(f).collect();
-        ForEach { views }
+        let views: Vec<Box<dyn View>> = iter.into_iter().map(f).collect();
+        ForEach {
+            ids: positional_ids(views.len()),
+            views,
+            source: None,
+            built_version: 0,
+        }
+    }
+
+    /// Rebuilds the rows if the bound collection changed since the last build.
+    pub fn sync(&mut self) {
+        let Some((version, rows)) = &self.source else {
+            return;
+        };
+        if version.get() == self.built_version {
+            return;
+        }
+        match rows {
+            Rows::Rebuild(build) => {
+                self.views = build();
+                self.ids = positional_ids(self.views.len());
+            }
+            Rows::Keyed(build) => {
+                let previous = std::mem::take(&mut self.ids).into_iter().zip(std::mem::take(&mut self.views)).collect();
+                (self.ids, self.views) = build(previous).into_iter().unzip();
+            }
+        }
+        self.built_version = version.get();
+    }
+
+    /// Identity of each row, in display order.
+    pub fn node_ids(&self) -> &[NodeId] {
+        &self.ids
     }
 }
 
+// Rows without keys are identified by position
+fn positional_ids(count: usize) -> Vec<NodeId> {
+    (0..count).map(|index| NodeId::from_key(&index)).collect()
+}
+
 impl View for ForEach {
-    fn render(&self, renderer: &mut dyn Renderer, theme: &Theme, x: f32, y: f32) {
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
         let mut current_y = y;
         for view in &self.views {
-            view.render(renderer, theme, x, current_y);
+            view.render(renderer, env, x, current_y);
             current_y += 50.0; // Placeholder height
         }
     }
 
-    fn handle_event(&mut self, event: &Event) {
+    fn handle_event(&mut self, event: &Event) -> bool {
+        self.sync();
+        self.views.iter_mut().rev().any(|view| view.handle_event(event))
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        self.views.iter().map(|c| c.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        self.sync();
+        self.views.iter_mut().map(|c| c.as_mut() as &mut dyn View).collect()
+    }
+
+    fn size(&self, env: &Environment) -> (f32, f32) {
+        let width = self.views.iter().map(|v| v.size(env).0).fold(0.0, f32::max);
+        (width, self.views.len() as f32 * 50.0)
+    }
+
+    fn update(&mut self, dt: f64) {
+        self.sync();
         for view in &mut self.views {
-            view.handle_event(event);
+            view.update(dt);
+        }
+    }
+
+    // Rows are rebuilt on the next render, so where they go isn't known yet
+    fn damage(&self) -> Damage {
+        match &self.source {
+            Some((version, _)) if version.get() != self.built_version => Damage::Full,
+            _ => children_damage(self),
         }
     }
 }
@@ -82,7 +527,13 @@ pub struct Window {
     pub title: String,
     pub width: u32,
     pub height: u32,
+    /// Gap between consecutive children.
+    pub spacing: f32,
+    /// Margin kept between the window frame and its title and children,
+    /// which are clipped to the area inside it.
+    pub safe_area_insets: EdgeInsets,
     pub children: Vec<Box<dyn View>>,
+    frame: Cell<Bounds>,
 }
 
 impl Window {
@@ -91,30 +542,152 @@ impl Window {
             title,
             width,
             height,
+            spacing: 0.0,
+            safe_area_insets: EdgeInsets::default(),
             children: Vec::new(),
+            frame: Cell::new(Bounds::default()),
         }
     }
 
+    pub fn spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    pub fn safe_area_insets(mut self, insets: EdgeInsets) -> Self {
+        self.safe_area_insets = insets;
+        self
+    }
+
     pub fn add_child(&mut self, child: Box<dyn View>) {
         self.children.push(child);
     }
+
+    fn title_bar_height(&self, env: &Environment) -> f32 {
+        measure_text(&self.title, env.title_font_size).1
+    }
 }
 
 impl View for Window {
-    fn render(&self, renderer: &mut dyn Renderer, theme: &Theme, x: f32, y: f32) {
-        // Render window frame
-        let mut child_y = y;
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        // Background, then the title bar, then children stacked below it,
+        // all clipped to the safe area
+        self.frame.set(Bounds::new(x, y, self.width as f32, self.height as f32));
+        renderer.draw_rect(x, y, self.width as f32, self.height as f32, env.background_color);
+        let insets = self.safe_area_insets;
+        let (width, height) = (self.width as f32, self.height as f32);
+        let safe_area = Bounds::new(
+            x + insets.leading,
+            y + insets.top,
+            width - insets.leading - insets.trailing,
+            height - insets.top - insets.bottom,
+        );
+        renderer.push_clip(safe_area.x, safe_area.y, safe_area.width, safe_area.height);
+        renderer.draw_text(&self.title, safe_area.x, safe_area.y, env.title_font_size, env.text_color);
+        renderer.pop_clip();
+        let title_height = self.title_bar_height(env);
+        let full = env.with_available_size(Some(width), Some(height));
+        let safe = env.with_available_size(Some(safe_area.width), Some(safe_area.height - title_height));
+        let mut child_y = safe_area.y + title_height;
         for child in &self.children {
-            child.render(renderer, theme, x, child_y);
-            child_y += 50.0; // Placeholder height
+            // Edge-to-edge children sit at the frame's corner and take no room in the stack
+            if child.extends_to_edges() {
+                child.render(renderer, &full, x, y);
+                continue;
+            }
+            renderer.push_clip(safe_area.x, safe_area.y, safe_area.width, safe_area.height);
+            child.render(renderer, &safe, safe_area.x, child_y);
+            renderer.pop_clip();
+            child_y += child.size(&safe).1 + self.spacing;
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        if let Event::Resize { width, height } = event {
+            self.width = *width;
+            self.height = *height;
+        }
+        self.children.iter_mut().rev().any(|child| child.handle_event(event))
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        self.children.iter().map(|c| c.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        self.children.iter_mut().map(|c| c.as_mut() as &mut dyn View).collect()
+    }
+
+    fn size(&self, _env: &Environment) -> (f32, f32) {
+        (self.width as f32, self.height as f32)
+    }
+
+    fn layout_bounds(&self) -> Option<Bounds> {
+        Some(self.frame.get())
+    }
+
+    fn damage(&self) -> Damage {
+        children_damage(self)
+    }
+}
+
+/// Cross-axis placement of `VStack` children.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Alignment {
+    #[default]
+    Leading,
+    Center,
+    Trailing,
+}
+
+/// Cross-axis placement of `HStack` children.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum VerticalAlignment {
+    #[default]
+    Top,
+    Center,
+    Bottom,
+}
+
+impl Alignment {
+    /// Share of the spare cross-axis room placed before the child.
+    fn factor(self) -> f32 {
+        match self {
+            Alignment::Leading => 0.0,
+            Alignment::Center => 0.5,
+            Alignment::Trailing => 1.0,
+        }
+    }
+}
+
+impl VerticalAlignment {
+    fn factor(self) -> f32 {
+        match self {
+            VerticalAlignment::Top => 0.0,
+            VerticalAlignment::Center => 0.5,
+            VerticalAlignment::Bottom => 1.0,
         }
     }
+}
 
-    fn handle_event(&mut self, event: &Event) {
-        for child in &mut self.children {
-            child.handle_event(event);
+/// Main-axis length of each stack child. Flexible children split whatever is
+/// left of `available` after the others and the gaps in proportion to their
+/// `flex`, but keep their minimum.
+fn stack_lengths(children: &[Box<dyn View>], main_axis: impl Fn((f32, f32)) -> f32, env: &Environment, spacing: f32, available: Option<f32>) -> Vec<f32> {
+    let mut lengths: Vec<f32> = children.iter().map(|c| main_axis(c.size(env))).collect();
+    let weights: Vec<f32> = children.iter().map(|c| c.flex().max(0.0)).collect();
+    let total_weight: f32 = weights.iter().sum();
+    if let (Some(available), true) = (available, total_weight > 0.0) {
+        let gaps = spacing * children.len().saturating_sub(1) as f32;
+        let fixed: f32 = (0..children.len()).filter(|&i| weights[i] == 0.0).map(|i| lengths[i]).sum();
+        let spare = available - fixed - gaps;
+        for (length, weight) in lengths.iter_mut().zip(&weights) {
+            if *weight > 0.0 {
+                *length = length.max(spare * weight / total_weight);
+            }
         }
     }
+    lengths
 }
 
 pub struct VStack {
@@ -122,6 +695,10 @@ pub struct VStack {
     pub spacing: f32,
     pub padding: f32,
     pub border: f32,
+    /// Fixed outer height; spacers expand to fill it.
+    pub height: Option<f32>,
+    pub alignment: Alignment,
+    frame: Cell<Bounds>,
 }
 
 impl VStack {
@@ -131,9 +708,34 @@ impl VStack {
             spacing,
             padding: 0.0,
             border: 0.0,
+            height: None,
+            alignment: Alignment::Leading,
+            frame: Cell::new(Bounds::default()),
         }
     }
 
+    pub fn height(mut self, height: f32) -> Self {
+        self.height = Some(height);
+        self
+    }
+
+    pub fn alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    fn child_heights(&self, env: &Environment) -> Vec<f32> {
+        let inset = 2.0 * (self.padding + self.border);
+        stack_lengths(&self.children, |s| s.1, env, self.spacing, self.height.map(|h| h - inset))
+    }
+
+    // Room left for children inside the padding and border
+    fn child_env<'e>(&self, env: &'e Environment) -> Environment<'e> {
+        let inset = 2.0 * (self.padding + self.border);
+        let height = self.height.or(env.available_height);
+        env.with_available_size(env.available_width.map(|w| w - inset), height.map(|h| h - inset))
+    }
+
     pub fn padding(mut self, padding: f32) -> Self {
         self.padding = padding;
         self
@@ -150,22 +752,57 @@ impl VStack {
 }
 
 impl View for VStack {
-    fn render(&self, renderer: &mut dyn Renderer, theme: &Theme, x: f32, y: f32) {
-        let mut current_y = y + self.padding + self.border;
-        for child in &self.children {
-            child.render(renderer, theme, x + self.padding, current_y);
-            current_y += 30.0 + self.spacing; // Placeholder child height
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        let (width, height) = self.size(env);
+        self.frame.set(Bounds::new(x, y, width, height));
+        let inset = self.padding + self.border;
+        let content_width = width - 2.0 * inset;
+        let child_env = self.child_env(env);
+        let mut current_y = y + inset;
+        for (child, height) in self.children.iter().zip(self.child_heights(&child_env)) {
+            let offset = (content_width - child.size(&child_env).0) * self.alignment.factor();
+            child.render(renderer, &child_env, x + inset + offset, current_y);
+            current_y += height + self.spacing;
         }
         // Render border if >0
         if self.border > 0.0 {
-            renderer.draw_rect(x, y, 200.0, current_y - y); // Placeholder width
+            renderer.draw_rect_outline(x, y, width, height, self.border, env.secondary_color);
         }
     }
 
-    fn handle_event(&mut self, event: &Event) {
-        for child in &mut self.children {
-            child.handle_event(event);
-        }
+    fn handle_event(&mut self, event: &Event) -> bool {
+        self.children.iter_mut().rev().any(|child| child.handle_event(event))
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        self.children.iter().map(|c| c.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        self.children.iter_mut().map(|c| c.as_mut() as &mut dyn View).collect()
+    }
+
+    fn size(&self, env: &Environment) -> (f32, f32) {
+        let inset = 2.0 * (self.padding + self.border);
+        let env = &self.child_env(env);
+        // Spacers only take room along the stack's axis
+        let width = self.children.iter().filter(|c| !c.is_flexible()).map(|c| c.size(env).0).fold(0.0, f32::max);
+        let heights = self.child_heights(env);
+        let gaps = self.spacing * heights.len().saturating_sub(1) as f32;
+        let height = heights.iter().sum::<f32>() + gaps;
+        (width + inset, self.height.unwrap_or(height + inset))
+    }
+
+    fn layout_bounds(&self) -> Option<Bounds> {
+        Some(self.frame.get())
+    }
+
+    fn rso_element(&self) -> Option<RsoElement> {
+        Some(RsoElement::new("VStack").arg(RsoValue::Number(self.spacing)))
+    }
+
+    fn damage(&self) -> Damage {
+        children_damage(self)
     }
 }
 
@@ -174,6 +811,9 @@ pub struct Button {
     pub on_click: Option<Box<dyn FnMut()>>,
     pub padding: f32,
     pub border: f32,
+    id: ViewId,
+    bounds: Cell<Bounds>,
+    hovered: bool,
 }
 
 impl Button {
@@ -183,9 +823,16 @@ impl Button {
             on_click: None,
             padding: 5.0,
             border: 1.0,
+            id: ViewId::next(),
+            bounds: Cell::new(Bounds::default()),
+            hovered: false,
         }
     }
 
+    pub fn is_hovered(&self) -> bool {
+        self.hovered
+    }
+
     pub fn on_click<F: FnMut() + 'static>(mut self, f: F) -> Self {
         self.on_click = Some(Box::new(f));
         self
@@ -203,90 +850,364 @@ impl Button {
 }
 
 impl View for Button {
-    fn render(&self, renderer: &mut dyn Renderer, theme: &Theme, x: f32, y: f32) {
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        self.bounds.set(Bounds::new(x, y, 100.0, 30.0));
         // Render button rect with border, then text
-        renderer.draw_rect(x, y, 100.0, 30.0);
-        renderer.draw_text(&self.label, x + self.padding, y + self.padding);
+        renderer.draw_rect(x, y, 100.0, 30.0, env.primary_color);
+        if self.hovered {
+            renderer.draw_rect(x, y, 100.0, 30.0, Color::rgba(255, 255, 255, 48));
+        }
+        if self.border > 0.0 {
+            renderer.draw_rect_outline(x, y, 100.0, 30.0, self.border, env.secondary_color);
+        }
+        renderer.draw_text(&self.label, x + self.padding, y + self.padding, env.font_size, env.text_color);
     }
 
-    fn handle_event(&mut self, event: &Event) {
-        if let Event::Click { .. } = event {
-            if let Some(ref mut callback) = self.on_click {
-                callback();
+    fn handle_event(&mut self, event: &Event) -> bool {
+        match event {
+            Event::Click { x, y } => {
+                if !self.bounds.get().contains(*x, *y) {
+                    return false;
+                }
+                if let Some(ref mut callback) = self.on_click {
+                    callback();
+                }
+                true
             }
+            Event::MouseEnter => {
+                self.hovered = true;
+                false
+            }
+            Event::MouseLeave => {
+                self.hovered = false;
+                false
+            }
+            _ => false,
         }
     }
+
+    fn bounds(&self) -> Option<Bounds> {
+        Some(self.bounds.get())
+    }
+
+    fn id(&self) -> Option<ViewId> {
+        Some(self.id)
+    }
+
+    fn size(&self, _env: &Environment) -> (f32, f32) {
+        (100.0, 30.0)
+    }
+
+    fn accessibility(&self) -> Option<AccessibilityInfo> {
+        Some(AccessibilityInfo::new(Role::Button).label(self.label.clone()))
+    }
+
+    fn rso_element(&self) -> Option<RsoElement> {
+        Some(RsoElement::new("Button").arg(RsoValue::Text(self.label.clone())))
+    }
+
+    // Only input changes it, which the host already repaints for
+    fn damage(&self) -> Damage {
+        Damage::None
+    }
+}
+
+/// Horizontal placement of each line of a `Text` within its `max_width`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TextAlignment {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+/// Where a `Text` gets its string.
+pub enum TextSource {
+    /// The bound string, shown as is.
+    Bound(Binding<String>),
+    /// The string this key maps to in the environment's `Localization`, or
+    /// the key itself when there's no such string.
+    Localized(String),
 }
 
 pub struct Text {
-    pub text: Binding<String>,
+    pub source: TextSource,
+    pub role: TextRole,
+    /// Wrap onto multiple lines to stay within this width.
+    pub max_width: Option<f32>,
+    pub alignment: TextAlignment,
+    /// Overrides `theme.text_color`.
+    pub color: Option<Color>,
+    frame: Cell<Bounds>,
+    // The string localized text last resolved to, for accessibility
+    localized: RefCell<Option<String>>,
+    drawn: DrawnVersion,
+    // Font size of the last render, to re-measure changed text with
+    drawn_font_size: Cell<u32>,
 }
 
 impl Text {
     pub fn new(text: Binding<String>) -> Self {
-        Text { text }
+        Text::with_source(TextSource::Bound(text))
     }
-}
 
-impl View for Text {
-    fn render(&self, renderer: &mut dyn Renderer, theme: &Theme, x: f32, y: f32) {
-        renderer.draw_text(&self.text.get(), x, y);
+    fn with_source(source: TextSource) -> Self {
+        Text {
+            source,
+            role: TextRole::Body,
+            max_width: None,
+            alignment: TextAlignment::Left,
+            color: None,
+            frame: Cell::new(Bounds::default()),
+            localized: RefCell::new(None),
+            drawn: DrawnVersion::new(),
+            drawn_font_size: Cell::new(0),
+        }
     }
 
-    fn handle_event(&mut self, _event: &Event) {
-        // Text doesn't handle events
+    /// Text showing the string `key` maps to in the environment's
+    /// `Localization`, or the key itself when there's no such string.
+    pub fn localized(key: impl Into<String>) -> Self {
+        Text::with_source(TextSource::Localized(key.into()))
     }
-}
 
-// Alias for compatibility
-pub type Label = Text;
+    /// What the text shows under `env`.
+    pub fn content(&self, env: &Environment) -> String {
+        match &self.source {
+            TextSource::Bound(text) => text.get(),
+            TextSource::Localized(key) => env.localization().map_or(key.as_str(), |l| l.resolve(key)).to_string(),
+        }
+    }
 
-pub struct HStack {
-    pub children: Vec<Box<dyn View>>,
-    pub spacing: f32,
-    pub padding: f32,
-    pub border: f32,
-}
+    pub fn alignment(mut self, alignment: TextAlignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
 
-impl HStack {
-    pub fn new(spacing: f32) -> Self {
-        HStack {
-            children: Vec::new(),
-            spacing,
-            padding: 0.0,
-            border: 0.0,
-        }
+    pub fn color(mut self, color: impl Into<Color>) -> Self {
+        self.color = Some(color.into());
+        self
     }
 
-    pub fn padding(mut self, padding: f32) -> Self {
-        self.padding = padding;
+    pub fn role(mut self, role: TextRole) -> Self {
+        self.role = role;
         self
     }
 
-    pub fn border(mut self, border: f32) -> Self {
-        self.border = border;
+    pub fn max_width(mut self, width: f32) -> Self {
+        self.max_width = Some(width);
         self
     }
 
-    pub fn add_child(&mut self, child: Box<dyn View>) {
-        self.children.push(child);
+    fn lines(&self, env: &Environment, font_size: u32) -> Vec<String> {
+        self.wrap(self.content(env), font_size)
     }
-}
+
+    fn wrap(&self, content: String, font_size: u32) -> Vec<String> {
+        match self.max_width {
+            Some(width) => wrap_text(&content, font_size, width),
+            None => vec![content],
+        }
+    }
+
+    fn measure(lines: &[String], font_size: u32) -> (f32, f32) {
+        let width = lines.iter().map(|l| measure_text(l, font_size).0).fold(0.0, f32::max);
+        (width, measure_text("", font_size).1 * lines.len() as f32)
+    }
+}
+
+impl View for Text {
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        let font_size = env.font_size_for(self.role);
+        let line_height = measure_text("", font_size).1;
+        let color = self.color.unwrap_or(env.text_color);
+        let (width, height) = self.size(env);
+        self.frame.set(Bounds::new(x, y, width, height));
+        self.drawn_font_size.set(font_size);
+        match &self.source {
+            TextSource::Bound(text) => self.drawn.record(text),
+            TextSource::Localized(_) => *self.localized.borrow_mut() = Some(self.content(env)),
+        }
+        for (i, line) in self.lines(env, font_size).iter().enumerate() {
+            // Without a max_width there's no box to align within
+            let spare = self.max_width.map_or(0.0, |w| w - measure_text(line, font_size).0);
+            let offset = match self.alignment {
+                TextAlignment::Left => 0.0,
+                TextAlignment::Center => spare / 2.0,
+                TextAlignment::Right => spare,
+            };
+            renderer.draw_text(line, x + offset, y + i as f32 * line_height, font_size, color);
+        }
+    }
+
+    fn handle_event(&mut self, _event: &Event) -> bool {
+        // Text doesn't handle events
+        false
+    }
+
+    fn size(&self, env: &Environment) -> (f32, f32) {
+        let font_size = env.font_size_for(self.role);
+        Self::measure(&self.lines(env, font_size), font_size)
+    }
+
+    fn damage(&self) -> Damage {
+        let text = match &self.source {
+            TextSource::Bound(text) => text,
+            // Localized text only changes with the environment, which the host tracks
+            TextSource::Localized(_) if self.localized.borrow().is_some() => return Damage::None,
+            TextSource::Localized(_) => return Damage::Full,
+        };
+        let frame = self.frame.get();
+        // Aligned lines are placed within the whole max width
+        let area = Bounds::new(frame.x, frame.y, self.max_width.unwrap_or(0.0).max(frame.width), frame.height);
+        match self.drawn.damage(text, area) {
+            Damage::Region(area) => {
+                let font_size = self.drawn_font_size.get();
+                // Text that changed size moves the views around it
+                if Self::measure(&self.wrap(text.get(), font_size), font_size) == (frame.width, frame.height) {
+                    Damage::Region(area)
+                } else {
+                    Damage::Full
+                }
+            }
+            damage => damage,
+        }
+    }
+
+    fn accessibility(&self) -> Option<AccessibilityInfo> {
+        let label = match &self.source {
+            TextSource::Bound(text) => text.get(),
+            // Resolving needs the environment, so report what was last shown
+            TextSource::Localized(key) => self.localized.borrow().clone().unwrap_or_else(|| key.clone()),
+        };
+        Some(AccessibilityInfo::new(Role::Text).label(label))
+    }
+
+    fn layout_bounds(&self) -> Option<Bounds> {
+        Some(self.frame.get())
+    }
+
+    fn rso_element(&self) -> Option<RsoElement> {
+        Some(match &self.source {
+            TextSource::Bound(text) => RsoElement::new("Text").arg(RsoValue::Text(text.get())),
+            TextSource::Localized(key) => RsoElement::new("Text").labelled("localized", RsoValue::Text(key.clone())),
+        })
+    }
+}
+
+// Alias for compatibility
+pub type Label = Text;
+
+pub struct HStack {
+    pub children: Vec<Box<dyn View>>,
+    pub spacing: f32,
+    pub padding: f32,
+    pub border: f32,
+    /// Fixed outer width; spacers expand to fill it.
+    pub width: Option<f32>,
+    pub alignment: VerticalAlignment,
+    frame: Cell<Bounds>,
+}
+
+impl HStack {
+    pub fn new(spacing: f32) -> Self {
+        HStack {
+            children: Vec::new(),
+            spacing,
+            padding: 0.0,
+            border: 0.0,
+            width: None,
+            alignment: VerticalAlignment::Top,
+            frame: Cell::new(Bounds::default()),
+        }
+    }
+
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    pub fn alignment(mut self, alignment: VerticalAlignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    fn child_widths(&self, env: &Environment) -> Vec<f32> {
+        let inset = 2.0 * (self.padding + self.border);
+        stack_lengths(&self.children, |s| s.0, env, self.spacing, self.width.map(|w| w - inset))
+    }
+
+    // Room left for children inside the padding and border
+    fn child_env<'e>(&self, env: &'e Environment) -> Environment<'e> {
+        let inset = 2.0 * (self.padding + self.border);
+        let width = self.width.or(env.available_width);
+        env.with_available_size(width.map(|w| w - inset), env.available_height.map(|h| h - inset))
+    }
+
+    pub fn padding(mut self, padding: f32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    pub fn border(mut self, border: f32) -> Self {
+        self.border = border;
+        self
+    }
+
+    pub fn add_child(&mut self, child: Box<dyn View>) {
+        self.children.push(child);
+    }
+}
 
 impl View for HStack {
-    fn render(&self, renderer: &mut dyn Renderer, theme: &Theme, x: f32, y: f32) {
-        let mut current_x = x + self.padding + self.border;
-        for child in &self.children {
-            child.render(renderer, theme, current_x, y + self.padding);
-            current_x += 100.0 + self.spacing; // Placeholder width
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        let (width, height) = self.size(env);
+        self.frame.set(Bounds::new(x, y, width, height));
+        let inset = self.padding + self.border;
+        let content_height = height - 2.0 * inset;
+        let child_env = self.child_env(env);
+        let mut current_x = x + inset;
+        for (child, width) in self.children.iter().zip(self.child_widths(&child_env)) {
+            let offset = (content_height - child.size(&child_env).1) * self.alignment.factor();
+            child.render(renderer, &child_env, current_x, y + inset + offset);
+            current_x += width + self.spacing;
         }
         // Render border
     }
 
-    fn handle_event(&mut self, event: &Event) {
-        for child in &mut self.children {
-            child.handle_event(event);
-        }
+    fn handle_event(&mut self, event: &Event) -> bool {
+        self.children.iter_mut().rev().any(|child| child.handle_event(event))
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        self.children.iter().map(|c| c.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        self.children.iter_mut().map(|c| c.as_mut() as &mut dyn View).collect()
+    }
+
+    fn size(&self, env: &Environment) -> (f32, f32) {
+        let inset = 2.0 * (self.padding + self.border);
+        let env = &self.child_env(env);
+        let widths = self.child_widths(env);
+        let gaps = self.spacing * widths.len().saturating_sub(1) as f32;
+        let width = widths.iter().sum::<f32>() + gaps;
+        // Spacers only take room along the stack's axis
+        let height = self.children.iter().filter(|c| !c.is_flexible()).map(|c| c.size(env).1).fold(0.0, f32::max);
+        (self.width.unwrap_or(width + inset), height + inset)
+    }
+
+    fn layout_bounds(&self) -> Option<Bounds> {
+        Some(self.frame.get())
+    }
+
+    fn rso_element(&self) -> Option<RsoElement> {
+        Some(RsoElement::new("HStack").arg(RsoValue::Number(self.spacing)))
+    }
+
+    fn damage(&self) -> Damage {
+        children_damage(self)
     }
 }
 
@@ -295,6 +1216,18 @@ pub struct Grid {
     pub rows: usize,
     pub cols: usize,
     pub spacing: f32,
+    /// Fixed column widths; columns without an entry are measured.
+    pub column_widths: Vec<f32>,
+    /// Highlighted `(row, col)`, moved with the arrow keys.
+    pub selection: Option<Binding<(usize, usize)>>,
+    id: ViewId,
+    bounds: Cell<Bounds>,
+    drawn_selection: DrawnVersion,
+}
+
+// Moves `index` by `delta` within `0..len`, stopping at either end
+fn step_index(index: usize, delta: isize, len: usize) -> usize {
+    index.saturating_add_signed(delta).min(len.saturating_sub(1))
 }
 
 impl Grid {
@@ -312,7 +1245,51 @@ impl Grid {
             rows,
             cols,
             spacing,
+            column_widths: Vec::new(),
+            selection: None,
+            id: ViewId::next(),
+            bounds: Cell::new(Bounds::default()),
+            drawn_selection: DrawnVersion::new(),
+        }
+    }
+
+    pub fn column_widths(mut self, widths: Vec<f32>) -> Self {
+        self.column_widths = widths;
+        self
+    }
+
+    /// Lets the arrow keys move a highlighted cell, stored in `selection`.
+    pub fn selection(mut self, selection: Binding<(usize, usize)>) -> Self {
+        self.selection = Some(selection);
+        self
+    }
+
+    /// Moves the selected cell by `rows` and `cols`, clamped to the grid.
+    pub fn move_selection(&mut self, rows: isize, cols: isize) {
+        if let Some(selection) = &self.selection {
+            let (row, col) = selection.get();
+            selection.set_if_changed((step_index(row, rows, self.rows), step_index(col, cols, self.cols)));
+        }
+    }
+
+    /// Widths of each column and heights of each row: the largest child
+    /// in that column/row, unless the column width is fixed.
+    pub fn track_sizes(&self, env: &Environment) -> (Vec<f32>, Vec<f32>) {
+        let mut col_widths = vec![0.0f32; self.cols];
+        let mut row_heights = vec![0.0f32; self.rows];
+        for (row_idx, row) in self.children.iter().enumerate() {
+            for (col_idx, child_opt) in row.iter().enumerate() {
+                if let Some(child) = child_opt {
+                    let (w, h) = child.size(&self.cell_env(env, col_idx));
+                    col_widths[col_idx] = col_widths[col_idx].max(w);
+                    row_heights[row_idx] = row_heights[row_idx].max(h);
+                }
+            }
         }
+        for (col_idx, width) in self.column_widths.iter().enumerate().take(self.cols) {
+            col_widths[col_idx] = *width;
+        }
+        (col_widths, row_heights)
     }
 
     pub fn set_child(&mut self, row: usize, col: usize, child: Box<dyn View>) {
@@ -320,29 +1297,93 @@ impl Grid {
             self.children[row][col] = Some(child);
         }
     }
+
+    // A cell is offered its fixed column width, or an even share of the
+    // grid's room between the spacing
+    fn cell_env<'e>(&self, env: &'e Environment, col: usize) -> Environment<'e> {
+        let share = |available: f32, tracks: usize| {
+            (available - self.spacing * tracks.saturating_sub(1) as f32) / tracks.max(1) as f32
+        };
+        let width = self.column_widths.get(col).copied().or(env.available_width.map(|w| share(w, self.cols)));
+        env.with_available_size(width, env.available_height.map(|h| share(h, self.rows)))
+    }
 }
 
 impl View for Grid {
-    fn render(&self, renderer: &mut dyn Renderer, theme: &Theme, x: f32, y: f32) {
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        let (col_widths, row_heights) = self.track_sizes(env);
+        let (width, height) = self.size(env);
+        self.bounds.set(Bounds::new(x, y, width, height));
+        let selected = self.selection.as_ref().map(|selection| {
+            self.drawn_selection.record(selection);
+            selection.get()
+        });
+        let mut child_y = y;
         for (row_idx, row) in self.children.iter().enumerate() {
+            let mut child_x = x;
             for (col_idx, child_opt) in row.iter().enumerate() {
+                if selected == Some((row_idx, col_idx)) {
+                    renderer.draw_rect(child_x, child_y, col_widths[col_idx], row_heights[row_idx], env.secondary_color);
+                }
                 if let Some(child) = child_opt {
-                    let child_x = x + col_idx as f32 * (100.0 + self.spacing);
-                    let child_y = y + row_idx as f32 * (30.0 + self.spacing);
-                    child.render(renderer, theme, child_x, child_y);
+                    child.render(renderer, &self.cell_env(env, col_idx), child_x, child_y);
                 }
+                child_x += col_widths[col_idx] + self.spacing;
             }
+            child_y += row_heights[row_idx] + self.spacing;
         }
     }
 
-    fn handle_event(&mut self, event: &Event) {
-        for row in &mut self.children {
-            for child_opt in row {
-                if let Some(child) = child_opt {
-                    child.handle_event(event);
-                }
+    fn handle_event(&mut self, event: &Event) -> bool {
+        if self.selection.is_some() {
+            let (rows, cols) = match event {
+                Event::KeyDown(Key::Up) => (-1, 0),
+                Event::KeyDown(Key::Down) => (1, 0),
+                Event::KeyDown(Key::Left) => (0, -1),
+                Event::KeyDown(Key::Right) => (0, 1),
+                _ => (0, 0),
+            };
+            if (rows, cols) != (0, 0) {
+                self.move_selection(rows, cols);
+                return true;
             }
         }
+        self.children.iter_mut().flatten().flatten().rev().any(|child| child.handle_event(event))
+    }
+
+    fn id(&self) -> Option<ViewId> {
+        Some(self.id)
+    }
+
+    fn can_focus(&self) -> bool {
+        self.selection.is_some()
+    }
+
+    fn bounds(&self) -> Option<Bounds> {
+        Some(self.bounds.get())
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        self.children.iter().flatten().flatten().map(|c| c.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        self.children.iter_mut().flatten().flatten().map(|c| c.as_mut() as &mut dyn View).collect()
+    }
+
+    fn size(&self, env: &Environment) -> (f32, f32) {
+        let (col_widths, row_heights) = self.track_sizes(env);
+        let width = col_widths.iter().sum::<f32>() + self.spacing * self.cols.saturating_sub(1) as f32;
+        let height = row_heights.iter().sum::<f32>() + self.spacing * self.rows.saturating_sub(1) as f32;
+        (width, height)
+    }
+
+    fn damage(&self) -> Damage {
+        let selection = match &self.selection {
+            Some(selection) => self.drawn_selection.damage(selection, self.bounds.get()),
+            None => Damage::None,
+        };
+        selection.union(children_damage(self))
     }
 }
 
@@ -350,6 +1391,7 @@ pub struct Panel {
     pub child: Option<Box<dyn View>>,
     pub border_width: f32,
     pub padding: f32,
+    frame: Cell<Bounds>,
 }
 
 impl Panel {
@@ -358,6 +1400,7 @@ impl Panel {
             child: None,
             border_width,
             padding,
+            frame: Cell::new(Bounds::default()),
         }
     }
 
@@ -365,27 +1408,57 @@ impl Panel {
         self.child = Some(child);
         self
     }
+
+    // Room left for the child inside the padding
+    fn child_env<'e>(&self, env: &'e Environment) -> Environment<'e> {
+        let inset = 2.0 * self.padding;
+        env.with_available_size(env.available_width.map(|w| w - inset), env.available_height.map(|h| h - inset))
+    }
 }
 
 impl View for Panel {
-    fn render(&self, renderer: &mut dyn Renderer, theme: &Theme, x: f32, y: f32) {
-        // Render border
-        renderer.draw_rect(x, y, 200.0, 200.0);
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        let (width, height) = self.size(env);
+        self.frame.set(Bounds::new(x, y, width, height));
         if let Some(ref child) = self.child {
-            child.render(renderer, theme, x + self.padding, y + self.padding);
+            child.render(renderer, &self.child_env(env), x + self.padding, y + self.padding);
+        }
+        // Border hugs the padded child and is drawn over the padding, not the child
+        if self.border_width > 0.0 {
+            renderer.draw_rect_outline(x, y, width, height, self.border_width, env.secondary_color);
         }
     }
 
-    fn handle_event(&mut self, event: &Event) {
-        if let Some(ref mut child) = self.child {
-            child.handle_event(event);
-        }
+    fn handle_event(&mut self, event: &Event) -> bool {
+        self.child.as_mut().is_some_and(|child| child.handle_event(event))
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        self.child.iter().map(|c| c.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        self.child.iter_mut().map(|c| c.as_mut() as &mut dyn View).collect()
+    }
+
+    fn size(&self, env: &Environment) -> (f32, f32) {
+        let (width, height) = self.child.as_ref().map_or((0.0, 0.0), |child| child.size(&self.child_env(env)));
+        (width + 2.0 * self.padding, height + 2.0 * self.padding)
+    }
+
+    fn layout_bounds(&self) -> Option<Bounds> {
+        Some(self.frame.get())
+    }
+
+    fn damage(&self) -> Damage {
+        children_damage(self)
     }
 }
 
 pub struct Toggle {
     pub is_on: Binding<bool>,
     pub on_toggle: Option<Box<dyn FnMut(bool)>>,
+    bounds: Cell<Bounds>,
 }
 
 impl Toggle {
@@ -393,6 +1466,7 @@ impl Toggle {
         Toggle {
             is_on,
             on_toggle: None,
+            bounds: Cell::new(Bounds::default()),
         }
     }
 
@@ -403,556 +1477,3509 @@ impl Toggle {
 }
 
 impl View for Toggle {
-    fn render(&self, renderer: &mut dyn Renderer, theme: &Theme, x: f32, y: f32) {
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
         // Render toggle switch
         let state = if self.is_on.get() { "ON" } else { "OFF" };
-        renderer.draw_text(state, x, y);
+        let (w, h) = measure_text(state, env.font_size);
+        self.bounds.set(Bounds::new(x, y, w, h));
+        renderer.draw_text(state, x, y, env.font_size, env.text_color);
     }
 
-    fn handle_event(&mut self, event: &Event) {
-        if let Event::Click { .. } = event {
-            let current = self.is_on.get();
-            self.is_on.set(!current);
-            if let Some(ref mut callback) = self.on_toggle {
-                callback(!current);
-            }
+    fn handle_event(&mut self, event: &Event) -> bool {
+        let Event::Click { x, y } = event else {
+            return false;
+        };
+        if !self.bounds.get().contains(*x, *y) {
+            return false;
+        }
+        let current = self.is_on.get();
+        self.is_on.set(!current);
+        if let Some(ref mut callback) = self.on_toggle {
+            callback(!current);
         }
+        true
     }
-}
 
-pub struct Input {
-    pub text: Binding<String>,
-    pub placeholder: String,
-}
+    fn bounds(&self) -> Option<Bounds> {
+        Some(self.bounds.get())
+    }
 
-impl Input {
-    pub fn new(text: Binding<String>, placeholder: String) -> Self {
-        Input { text, placeholder }
+    fn size(&self, env: &Environment) -> (f32, f32) {
+        measure_text(if self.is_on.get() { "ON" } else { "OFF" }, env.font_size)
     }
-}
 
-impl View for Input {
-    fn render(&self, renderer: &mut dyn Renderer, theme: &Theme, x: f32, y: f32) {
-        // Render input field with text
-        let text = if self.text.get().is_empty() { &self.placeholder } else { &self.text.get() };
-        renderer.draw_text(text, x, y);
-        // Draw border
-        renderer.draw_rect(x, y, 200.0, 25.0);
+    fn accessibility(&self) -> Option<AccessibilityInfo> {
+        Some(AccessibilityInfo::new(Role::Checkbox))
     }
 
-    fn handle_event(&mut self, event: &Event) {
-        match event {
-            Event::KeyDown(key) => {
-                match key {
-                    sdl2::keyboard::Keycode::Backspace => {
-                        let mut current = self.text.get();
-                        current.pop();
-                        self.text.set(current);
-                    }
-                    _ => {
-                        if let Some(c) = key.to_string().chars().next() {
-                            if c.is_alphanumeric() || c.is_whitespace() {
-                                let mut current = self.text.get();
-                                current.push(c);
-                                self.text.set(current);
-                            }
-                        }
-                    }
-                }
-            }
-            _ => {}
-        }
+    fn rso_element(&self) -> Option<RsoElement> {
+        Some(RsoElement::new("Toggle"))
     }
 }
 
-pub struct Slider {
-    pub value: Binding<f32>,
-    pub min: f32,
-    pub max: f32,
-    pub on_change: Option<Box<dyn FnMut(f32)>>,
+// Height of the Picker header and of each option in its list
+const PICKER_ROW_HEIGHT: f32 = 24.0;
+
+/// Shows the selected option and expands into a list of choices on click.
+/// The list is drawn as an overlay so siblings can't cover it.
+pub struct Picker {
+    pub options: Vec<String>,
+    pub selected: Binding<usize>,
+    pub width: f32,
+    pub on_change: Option<Box<dyn FnMut(usize)>>,
+    id: ViewId,
+    expanded: bool,
+    bounds: Cell<Bounds>,
 }
 
-impl Slider {
-    pub fn new(value: Binding<f32>, min: f32, max: f32) -> Self {
-        Slider {
-            value,
-            min,
-            max,
+impl Picker {
+    pub fn new(options: Vec<String>, selected: Binding<usize>) -> Self {
+        Picker {
+            options,
+            selected,
+            width: 150.0,
             on_change: None,
+            id: ViewId::next(),
+            expanded: false,
+            bounds: Cell::new(Bounds::default()),
         }
     }
 
-    pub fn on_change<F: FnMut(f32) + 'static>(mut self, f: F) -> Self {
-        self.on_change = Some(Box::new(f));
+    pub fn width(mut self, w: f32) -> Self {
+        self.width = w;
         self
     }
-}
 
-impl View for Slider {
-    fn render(&self, renderer: &mut dyn Renderer, theme: &Theme, x: f32, y: f32) {
-        // Render slider bar and knob
-        renderer.draw_rect(x, y + 10.0, 100.0, 5.0); // Bar
-        let knob_x = x + (self.value.get() - self.min) / (self.max - self.min) * 100.0;
-        renderer.draw_rect(knob_x - 5.0, y, 10.0, 25.0); // Knob
+    pub fn on_change<F: FnMut(usize) + 'static>(mut self, f: F) -> Self {
+        self.on_change = Some(Box::new(f));
+        self
     }
 
-    fn handle_event(&mut self, event: &Event) {
-        if let Event::Click { x, .. } = event {
-            let new_value = self.min + (*x / 100.0) * (self.max - self.min);
-            let clamped = new_value.max(self.min).min(self.max);
-            self.value.set(clamped);
-            if let Some(ref mut callback) = self.on_change {
-                callback(clamped);
-            }
-        }
+    pub fn is_expanded(&self) -> bool {
+        self.expanded
     }
-}
 
-pub struct MenuBar {
-    pub items: Vec<String>,
-    pub on_select: Option<Box<dyn FnMut(usize)>>,
+    fn list_bounds(&self) -> Bounds {
+        let header = self.bounds.get();
+        Bounds::new(header.x, header.y + PICKER_ROW_HEIGHT, self.width, PICKER_ROW_HEIGHT * self.options.len() as f32)
+    }
 }
 
-impl MenuBar {
-    pub fn new(items: Vec<String>) -> Self {
-        MenuBar {
-            items,
-            on_select: None,
+impl View for Picker {
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        self.bounds.set(Bounds::new(x, y, self.width, PICKER_ROW_HEIGHT));
+        renderer.draw_rect_outline(x, y, self.width, PICKER_ROW_HEIGHT, 1.0, env.secondary_color);
+        if let Some(label) = self.options.get(self.selected.get()) {
+            renderer.draw_text(label, x + 4.0, y, env.font_size, env.text_color);
         }
     }
 
-    pub fn on_select<F: FnMut(usize) + 'static>(mut self, f: F) -> Self {
-        self.on_select = Some(Box::new(f));
-        self
+    fn render_overlay(&self, renderer: &mut dyn Renderer, env: &Environment) {
+        if !self.expanded {
+            return;
+        }
+        let list = self.list_bounds();
+        renderer.draw_rect(list.x, list.y, list.width, list.height, env.background_color);
+        renderer.draw_rect_outline(list.x, list.y, list.width, list.height, 1.0, env.secondary_color);
+        for (i, option) in self.options.iter().enumerate() {
+            let row_y = list.y + i as f32 * PICKER_ROW_HEIGHT;
+            if i == self.selected.get() {
+                renderer.draw_rect(list.x, row_y, list.width, PICKER_ROW_HEIGHT, env.secondary_color);
+            }
+            renderer.draw_text(option, list.x + 4.0, row_y, env.font_size, env.text_color);
+        }
     }
-}
 
-impl View for MenuBar {
-    fn render(&self, renderer: &mut dyn Renderer, theme: &Theme, x: f32, y: f32) {
-        let mut current_x = x;
-        for item in &self.items {
-            renderer.draw_text(item, current_x, y);
-            current_x += 50.0; // Placeholder width
+    fn overlay_bounds(&self) -> Option<Bounds> {
+        if self.expanded {
+            Some(self.list_bounds())
+        } else {
+            None
         }
     }
 
-    fn handle_event(&mut self, event: &Event) {
-        if let Event::Click { x, .. } = event {
-            let index = (*x / 50.0) as usize;
-            if index < self.items.len() {
-                if let Some(ref mut callback) = self.on_select {
+    fn dismiss_overlay(&mut self) {
+        self.expanded = false;
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        let Event::Click { x, y } = event else {
+            return false;
+        };
+        let list = self.list_bounds();
+        if self.expanded && list.contains(*x, *y) {
+            let index = ((*y - list.y) / PICKER_ROW_HEIGHT) as usize;
+            self.expanded = false;
+            if index < self.options.len() && index != self.selected.get() {
+                self.selected.set(index);
+                if let Some(ref mut callback) = self.on_change {
                     callback(index);
                 }
             }
+            true
+        } else if self.bounds.get().contains(*x, *y) {
+            self.expanded = !self.expanded;
+            true
+        } else {
+            // Clicking anywhere else dismisses the list, and still reaches what's there
+            self.expanded = false;
+            false
         }
     }
-}
-
-pub struct ZStack {
-    pub children: Vec<Box<dyn View>>,
-}
 
-impl ZStack {
-    pub fn new() -> Self {
-        ZStack { children: Vec::new() }
+    fn bounds(&self) -> Option<Bounds> {
+        Some(self.bounds.get())
     }
 
-    pub fn add_child(&mut self, child: Box<dyn View>) {
-        self.children.push(child);
+    fn id(&self) -> Option<ViewId> {
+        Some(self.id)
     }
-}
 
-impl View for ZStack {
-    fn render(&self, renderer: &mut dyn Renderer, theme: &Theme, x: f32, y: f32) {
-        for child in &self.children {
-            child.render(renderer, theme, x, y); // Overlay at same position
-        }
+    fn size(&self, _env: &Environment) -> (f32, f32) {
+        (self.width, PICKER_ROW_HEIGHT)
     }
 
-    fn handle_event(&mut self, event: &Event) {
-        for child in &mut self.children {
-            child.handle_event(event);
+    fn accessibility(&self) -> Option<AccessibilityInfo> {
+        let info = AccessibilityInfo::new(Role::ComboBox);
+        match self.options.get(self.selected.get()) {
+            Some(option) => Some(info.label(option.clone())),
+            None => Some(info),
         }
     }
 }
 
-pub struct List {
-    pub items: Vec<Box<dyn View>>,
+/// Button chosen to dismiss an `Alert`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AlertResult {
+    Ok,
+    Cancel,
 }
 
-impl List {
-    pub fn new(items: Vec<Box<dyn View>>) -> Self {
-        List { items }
-    }
+const ALERT_WIDTH: f32 = 300.0;
+const ALERT_HEIGHT: f32 = 150.0;
+const ALERT_BUTTON_WIDTH: f32 = 100.0;
+const ALERT_BUTTON_HEIGHT: f32 = 30.0;
+
+/// Modal message with OK and Cancel buttons, shown while `presented` is true.
+/// It draws over a dimmed backdrop and takes every event until dismissed.
+pub struct Alert {
+    pub title: String,
+    pub message: String,
+    pub presented: Binding<bool>,
+    pub on_result: Option<Box<dyn FnMut(AlertResult)>>,
+    /// Size of the area dimmed behind the panel; `None` covers the window.
+    pub area: Option<(f32, f32)>,
+    // Last window size seen, from the environment or a resize
+    window_area: Cell<(f32, f32)>,
+    id: ViewId,
 }
 
-impl View for List {
-    fn render(&self, renderer: &mut dyn Renderer, theme: &Theme, x: f32, y: f32) {
-        let mut current_y = y;
-        for item in &self.items {
-            item.render(renderer, theme, x, current_y);
-            current_y += 30.0; // Item height
+impl Alert {
+    pub fn new(title: String, message: String, presented: Binding<bool>) -> Self {
+        Alert {
+            title,
+            message,
+            presented,
+            on_result: None,
+            area: None,
+            window_area: Cell::new((0.0, 0.0)),
+            id: ViewId::next(),
         }
     }
 
-    fn handle_event(&mut self, event: &Event) {
-        for item in &mut self.items {
-            item.handle_event(event);
-        }
+    pub fn area(mut self, width: f32, height: f32) -> Self {
+        self.area = Some((width, height));
+        self
     }
-}
 
-pub struct ProgressBar {
-    pub progress: Binding<f32>, // 0.0 to 1.0
-}
+    fn covered_area(&self) -> (f32, f32) {
+        self.area.unwrap_or(self.window_area.get())
+    }
 
-impl ProgressBar {
-    pub fn new(progress: Binding<f32>) -> Self {
-        ProgressBar { progress }
+    pub fn on_result<F: FnMut(AlertResult) + 'static>(mut self, f: F) -> Self {
+        self.on_result = Some(Box::new(f));
+        self
     }
-}
 
-impl View for ProgressBar {
-    fn render(&self, renderer: &mut dyn Renderer, theme: &Theme, x: f32, y: f32) {
-        // Render background
-        renderer.draw_rect(x, y, 200.0, 20.0);
-        // Render progress
-        let progress_width = self.progress.get() * 200.0;
-        renderer.draw_rect(x, y, progress_width, 20.0);
+    fn panel(&self) -> Bounds {
+        let (width, height) = self.covered_area();
+        Bounds::new((width - ALERT_WIDTH) / 2.0, (height - ALERT_HEIGHT) / 2.0, ALERT_WIDTH, ALERT_HEIGHT)
     }
 
-    fn handle_event(&mut self, _event: &Event) {
-        // Progress bars don't handle events
+    // OK on the right, Cancel to its left
+    fn button_bounds(&self, result: AlertResult) -> Bounds {
+        let panel = self.panel();
+        let y = panel.y + panel.height - ALERT_BUTTON_HEIGHT - 10.0;
+        let right = panel.x + panel.width - ALERT_BUTTON_WIDTH - 10.0;
+        let x = match result {
+            AlertResult::Ok => right,
+            AlertResult::Cancel => right - ALERT_BUTTON_WIDTH - 10.0,
+        };
+        Bounds::new(x, y, ALERT_BUTTON_WIDTH, ALERT_BUTTON_HEIGHT)
     }
-}
 
-pub struct TabView {
-    pub tabs: Vec<String>,
-    pub selected: Binding<usize>,
-    pub content: Vec<Box<dyn View>>,
+    fn dismiss(&mut self, result: AlertResult) {
+        self.presented.set(false);
+        if let Some(ref mut callback) = self.on_result {
+            callback(result);
+        }
+    }
 }
 
-impl TabView {
-    pub fn new(tabs: Vec<String>, selected: Binding<usize>, content: Vec<Box<dyn View>>) -> Self {
-        TabView { tabs, selected, content }
+impl View for Alert {
+    fn render(&self, _renderer: &mut dyn Renderer, _env: &Environment, _x: f32, _y: f32) {
+        // Everything is drawn in the overlay pass
     }
-}
 
-impl View for TabView {
-    fn render(&self, renderer: &mut dyn Renderer, theme: &Theme, x: f32, y: f32) {
-        // Render tabs
-        let mut tab_x = x;
-        for (i, tab) in self.tabs.iter().enumerate() {
-            renderer.draw_text(tab, tab_x, y);
-            tab_x += 100.0;
+    fn render_overlay(&self, renderer: &mut dyn Renderer, env: &Environment) {
+        // The root environment is offered the whole window
+        if let (Some(width), Some(height)) = (env.available_width, env.available_height) {
+            self.window_area.set((width, height));
         }
-        // Render selected content
-        let selected = self.selected.get();
-        if selected < self.content.len() {
-            self.content[selected].render(renderer, theme, x, y + 30.0);
+        if !self.presented.get() {
+            return;
+        }
+        let (width, height) = self.covered_area();
+        renderer.draw_rect(0.0, 0.0, width, height, Color::rgba(0, 0, 0, 128));
+        let panel = self.panel();
+        renderer.draw_rect(panel.x, panel.y, panel.width, panel.height, env.background_color);
+        renderer.draw_rect_outline(panel.x, panel.y, panel.width, panel.height, 1.0, env.secondary_color);
+        renderer.draw_text(&self.title, panel.x + 10.0, panel.y + 10.0, env.title_font_size, env.text_color);
+        let title_height = measure_text(&self.title, env.title_font_size).1;
+        renderer.draw_text(&self.message, panel.x + 10.0, panel.y + 20.0 + title_height, env.font_size, env.text_color);
+        for (result, label, color) in [
+            (AlertResult::Cancel, "Cancel", env.secondary_color),
+            (AlertResult::Ok, "OK", env.primary_color),
+        ] {
+            let button = self.button_bounds(result);
+            renderer.draw_rect(button.x, button.y, button.width, button.height, color);
+            renderer.draw_text(label, button.x + 5.0, button.y + 5.0, env.font_size, env.text_color);
         }
     }
 
-    fn handle_event(&mut self, event: &Event) {
-        if let Event::Click { x: click_x, .. } = event {
-            let tab_index = (*click_x / 100.0) as usize;
-            if tab_index < self.tabs.len() {
-                self.selected.set(tab_index);
+    fn is_modal(&self) -> bool {
+        self.presented.get()
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        if let Event::Resize { width, height } = event {
+            // Left unconsumed so the rest of the tree resizes too
+            self.window_area.set((*width as f32, *height as f32));
+            return false;
+        }
+        if !self.presented.get() {
+            return false;
+        }
+        match event {
+            // A presented alert is modal, so it takes every click
+            Event::Click { x, y } => {
+                for result in [AlertResult::Ok, AlertResult::Cancel] {
+                    if self.button_bounds(result).contains(*x, *y) {
+                        self.dismiss(result);
+                        break;
+                    }
+                }
+                true
+            }
+            Event::KeyDown(Key::Enter) => {
+                self.dismiss(AlertResult::Ok);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn id(&self) -> Option<ViewId> {
+        Some(self.id)
+    }
+
+    fn accessibility(&self) -> Option<AccessibilityInfo> {
+        Some(AccessibilityInfo::new(Role::Alert).label(self.title.clone()))
+    }
+}
+
+// Height of one RadioGroup option row
+const RADIO_ROW_HEIGHT: f32 = 24.0;
+const RADIO_INDICATOR_SIZE: f32 = 12.0;
+
+/// Mutually exclusive options stacked vertically; `selected` holds the index
+/// of the chosen one.
+pub struct RadioGroup {
+    pub options: Vec<String>,
+    pub selected: Binding<usize>,
+    pub on_change: Option<Box<dyn FnMut(usize)>>,
+    bounds: Cell<Bounds>,
+}
+
+impl RadioGroup {
+    pub fn new(options: Vec<String>, selected: Binding<usize>) -> Self {
+        RadioGroup {
+            options,
+            selected,
+            on_change: None,
+            bounds: Cell::new(Bounds::default()),
+        }
+    }
+
+    pub fn on_change<F: FnMut(usize) + 'static>(mut self, f: F) -> Self {
+        self.on_change = Some(Box::new(f));
+        self
+    }
+}
+
+impl View for RadioGroup {
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        let (width, height) = self.size(env);
+        self.bounds.set(Bounds::new(x, y, width, height));
+        let selected = self.selected.get();
+        let inset = (RADIO_ROW_HEIGHT - RADIO_INDICATOR_SIZE) / 2.0;
+        for (i, option) in self.options.iter().enumerate() {
+            let row_y = y + i as f32 * RADIO_ROW_HEIGHT;
+            // Filled indicator for the selected option, hollow for the rest
+            if i == selected {
+                renderer.draw_rect(x, row_y + inset, RADIO_INDICATOR_SIZE, RADIO_INDICATOR_SIZE, env.primary_color);
+            } else {
+                renderer.draw_rect_outline(x, row_y + inset, RADIO_INDICATOR_SIZE, RADIO_INDICATOR_SIZE, 1.0, env.secondary_color);
+            }
+            renderer.draw_text(option, x + RADIO_ROW_HEIGHT, row_y, env.font_size, env.text_color);
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        let Event::Click { x, y } = event else {
+            return false;
+        };
+        let bounds = self.bounds.get();
+        if !bounds.contains(*x, *y) {
+            return false;
+        }
+        let index = ((*y - bounds.y) / RADIO_ROW_HEIGHT) as usize;
+        if index < self.options.len() && index != self.selected.get() {
+            self.selected.set(index);
+            if let Some(ref mut callback) = self.on_change {
+                callback(index);
+            }
+        }
+        true
+    }
+
+    fn bounds(&self) -> Option<Bounds> {
+        Some(self.bounds.get())
+    }
+
+    fn size(&self, env: &Environment) -> (f32, f32) {
+        let label_width = self
+            .options
+            .iter()
+            .map(|o| measure_text(o, env.font_size).0)
+            .fold(0.0, f32::max);
+        (RADIO_ROW_HEIGHT + label_width, RADIO_ROW_HEIGHT * self.options.len() as f32)
+    }
+
+    fn accessibility(&self) -> Option<AccessibilityInfo> {
+        Some(AccessibilityInfo::new(Role::RadioGroup))
+    }
+}
+
+pub struct Input {
+    pub text: Binding<String>,
+    pub placeholder: String,
+    id: ViewId,
+    focused: bool,
+    bounds: Cell<Bounds>,
+    // Byte index into the text
+    cursor: usize,
+    // (anchor, cursor) while text is selected
+    selection: Option<(usize, usize)>,
+}
+
+impl Input {
+    pub fn new(text: Binding<String>, placeholder: String) -> Self {
+        let cursor = text.get().len();
+        Input {
+            text,
+            placeholder,
+            id: ViewId::next(),
+            focused: false,
+            bounds: Cell::new(Bounds::default()),
+            cursor,
+            selection: None,
+        }
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Selected byte range, start first.
+    pub fn selection(&self) -> Option<(usize, usize)> {
+        self.selection.map(|(a, b)| (a.min(b), a.max(b)))
+    }
+
+    // Moves the cursor, extending the selection or dropping it
+    fn move_cursor(&mut self, to: usize, extend: bool) {
+        let anchor = self.selection.map_or(self.cursor, |(anchor, _)| anchor);
+        self.cursor = to;
+        self.selection = if extend && anchor != to { Some((anchor, to)) } else { None };
+    }
+
+    // Removes the selected text, leaving the cursor where it started
+    fn delete_selection(&mut self, text: &mut String) -> bool {
+        let Some((start, end)) = self.selection() else {
+            return false;
+        };
+        text.replace_range(start..end, "");
+        self.cursor = start;
+        self.selection = None;
+        true
+    }
+}
+
+fn prev_char_boundary(text: &str, index: usize) -> usize {
+    text[..index].char_indices().next_back().map_or(0, |(i, _)| i)
+}
+
+fn next_char_boundary(text: &str, index: usize) -> usize {
+    text[index..].chars().next().map_or(index, |c| index + c.len_utf8())
+}
+
+impl View for Input {
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        self.bounds.set(Bounds::new(x, y, 200.0, 25.0));
+        let current = self.text.get();
+        let offset = |i: usize| x + measure_text(current.get(..i).unwrap_or(&current), env.font_size).0;
+        if let Some((start, end)) = self.selection() {
+            let highlight = Color { a: 96, ..env.primary_color };
+            renderer.draw_rect(offset(start), y, offset(end) - offset(start), 25.0, highlight);
+        }
+        // Render input field with text
+        let text = if current.is_empty() { &self.placeholder } else { &current };
+        renderer.draw_text(text, x, y, env.font_size, env.text_color);
+        if self.focused {
+            renderer.draw_rect(offset(self.cursor), y, 1.0, 25.0, env.text_color);
+        }
+        // Draw border
+        renderer.draw_rect_outline(x, y, 200.0, 25.0, 1.0, env.secondary_color);
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        if !self.focused {
+            return false;
+        }
+        let mut current = self.text.get();
+        // The binding may have been changed from elsewhere
+        if self.cursor > current.len() || !current.is_char_boundary(self.cursor) {
+            self.cursor = current.len();
+            self.selection = None;
+        }
+        let (key, extend) = match event {
+            Event::KeyDown(key) => (Some(*key), false),
+            Event::ShiftKeyDown(key) => (Some(*key), true),
+            _ => (None, false),
+        };
+        match (key, event) {
+            (Some(Key::Left), _) => match self.selection() {
+                Some((start, _)) if !extend => self.move_cursor(start, false),
+                _ => self.move_cursor(prev_char_boundary(&current, self.cursor), extend),
+            },
+            (Some(Key::Right), _) => match self.selection() {
+                Some((_, end)) if !extend => self.move_cursor(end, false),
+                _ => self.move_cursor(next_char_boundary(&current, self.cursor), extend),
+            },
+            (Some(Key::Home), _) => self.move_cursor(0, extend),
+            (Some(Key::End), _) => self.move_cursor(current.len(), extend),
+            (Some(Key::Backspace), _) => {
+                if !self.delete_selection(&mut current) && self.cursor > 0 {
+                    let start = prev_char_boundary(&current, self.cursor);
+                    current.replace_range(start..self.cursor, "");
+                    self.cursor = start;
+                }
+                self.text.set(current);
+            }
+            (Some(Key::Delete), _) => {
+                if !self.delete_selection(&mut current) && self.cursor < current.len() {
+                    let end = next_char_boundary(&current, self.cursor);
+                    current.replace_range(self.cursor..end, "");
+                }
+                self.text.set(current);
+            }
+            // Text arrives already composed (shift, layouts, IME)
+            (_, Event::TextInput(input) | Event::Paste(input)) => {
+                self.delete_selection(&mut current);
+                current.insert_str(self.cursor, input);
+                self.cursor += input.len();
+                self.text.set(current);
+            }
+            (_, Event::Copy(clipboard)) => {
+                if let Some((start, end)) = self.selection() {
+                    clipboard.set_text(&current[start..end]);
+                }
+            }
+            (_, Event::Cut(clipboard)) => {
+                if let Some((start, end)) = self.selection() {
+                    clipboard.set_text(&current[start..end]);
+                    self.delete_selection(&mut current);
+                    self.text.set(current);
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn bounds(&self) -> Option<Bounds> {
+        Some(self.bounds.get())
+    }
+
+    fn id(&self) -> Option<ViewId> {
+        Some(self.id)
+    }
+
+    fn can_focus(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn size(&self, _env: &Environment) -> (f32, f32) {
+        (200.0, 25.0)
+    }
+
+    fn accessibility(&self) -> Option<AccessibilityInfo> {
+        Some(AccessibilityInfo::new(Role::TextField).label(self.placeholder.clone()))
+    }
+}
+
+/// Multi-line text input. Edits at the end of the text; there is no caret movement.
+pub struct TextArea {
+    pub text: Binding<String>,
+    pub width: f32,
+    pub height: f32,
+    pub scroll_offset: f32,
+    id: ViewId,
+    focused: bool,
+    bounds: Cell<Bounds>,
+    line_height: Cell<f32>,
+}
+
+impl TextArea {
+    pub fn new(text: Binding<String>) -> Self {
+        TextArea {
+            text,
+            width: 300.0,
+            height: 120.0,
+            scroll_offset: 0.0,
+            id: ViewId::next(),
+            focused: false,
+            bounds: Cell::new(Bounds::default()),
+            line_height: Cell::new(0.0),
+        }
+    }
+
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn height(mut self, height: f32) -> Self {
+        self.height = height;
+        self
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Furthest the text can scroll, from the last rendered line height.
+    pub fn max_offset(&self) -> f32 {
+        let lines = self.text.get().split('\n').count() as f32;
+        (lines * self.line_height.get() - self.height).max(0.0)
+    }
+
+    pub fn scroll_by(&mut self, delta: f32) {
+        self.scroll_offset = (self.scroll_offset + delta).clamp(0.0, self.max_offset());
+    }
+
+    fn edit(&mut self, f: impl FnOnce(&mut String)) {
+        let mut current = self.text.get();
+        f(&mut current);
+        self.text.set(current);
+        // Keep the cursor line in view
+        self.scroll_offset = self.max_offset();
+    }
+}
+
+impl View for TextArea {
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        self.bounds.set(Bounds::new(x, y, self.width, self.height));
+        let line_height = measure_text("", env.font_size).1;
+        self.line_height.set(line_height);
+
+        let text = self.text.get();
+        renderer.push_clip(x, y, self.width, self.height);
+        let mut line_y = y - self.scroll_offset;
+        let mut cursor = (x, line_y);
+        for line in text.split('\n') {
+            renderer.draw_text(line, x, line_y, env.font_size, env.text_color);
+            cursor = (x + measure_text(line, env.font_size).0, line_y);
+            line_y += line_height;
+        }
+        if self.focused {
+            renderer.draw_rect(cursor.0, cursor.1, 1.0, line_height, env.text_color);
+        }
+        renderer.pop_clip();
+        renderer.draw_rect_outline(x, y, self.width, self.height, 1.0, env.secondary_color);
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        if let Event::MouseWheel { x, y, dy, .. } = event {
+            if !self.bounds.get().contains(*x, *y) {
+                return false;
+            }
+            self.scroll_by(-dy * SCROLL_STEP);
+            return true;
+        }
+        if !self.focused {
+            return false;
+        }
+        match event {
+            Event::KeyDown(Key::Enter) => self.edit(|text| text.push('\n')),
+            // Removing a trailing newline joins the last two lines
+            Event::KeyDown(Key::Backspace) => self.edit(|text| {
+                text.pop();
+            }),
+            Event::TextInput(input) => self.edit(|text| text.push_str(input)),
+            _ => return false,
+        }
+        true
+    }
+
+    fn bounds(&self) -> Option<Bounds> {
+        Some(self.bounds.get())
+    }
+
+    fn id(&self) -> Option<ViewId> {
+        Some(self.id)
+    }
+
+    fn can_focus(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn size(&self, _env: &Environment) -> (f32, f32) {
+        (self.width, self.height)
+    }
+
+    fn accessibility(&self) -> Option<AccessibilityInfo> {
+        Some(AccessibilityInfo::new(Role::TextField))
+    }
+}
+
+pub struct Slider {
+    pub value: Binding<f32>,
+    pub min: f32,
+    pub max: f32,
+    pub width: f32,
+    pub on_change: Option<Box<dyn FnMut(f32)>>,
+    bounds: Cell<Bounds>,
+    // Pointer x while a drag that started on the slider is in progress
+    drag_x: Option<f32>,
+    drawn: DrawnVersion,
+}
+
+impl Slider {
+    pub fn new(value: Binding<f32>, min: f32, max: f32) -> Self {
+        Slider {
+            value,
+            min,
+            max,
+            width: 100.0,
+            on_change: None,
+            bounds: Cell::new(Bounds::default()),
+            drag_x: None,
+            drawn: DrawnVersion::new(),
+        }
+    }
+
+    fn set_from_x(&mut self, x: f32) {
+        let local_x = x - self.bounds.get().x;
+        let new_value = self.min + (local_x / self.width) * (self.max - self.min);
+        let clamped = new_value.max(self.min).min(self.max);
+        self.value.set(clamped);
+        if let Some(ref mut callback) = self.on_change {
+            callback(clamped);
+        }
+    }
+
+    pub fn width(mut self, w: f32) -> Self {
+        self.width = w;
+        self
+    }
+
+    pub fn on_change<F: FnMut(f32) + 'static>(mut self, f: F) -> Self {
+        self.on_change = Some(Box::new(f));
+        self
+    }
+}
+
+const SLIDER_KNOB_OVERHANG: f32 = 5.0;
+
+impl View for Slider {
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        // Remember where we were drawn so clicks can be mapped to local coordinates
+        self.bounds.set(Bounds::new(x, y, self.width, 25.0));
+        self.drawn.record(&self.value);
+        // Render slider bar and knob
+        renderer.draw_rect(x, y + 10.0, self.width, 5.0, env.secondary_color); // Bar
+        let knob_x = x + (self.value.get() - self.min) / (self.max - self.min) * self.width;
+        renderer.draw_rect(knob_x - 5.0, y, 10.0, 25.0, env.primary_color); // Knob
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        match event {
+            Event::Click { x, y } => {
+                if !self.bounds.get().contains(*x, *y) {
+                    return false;
+                }
+                self.drag_x = Some(*x);
+                self.set_from_x(*x);
+                true
+            }
+            Event::Drag { dx, .. } => {
+                let Some(drag_x) = self.drag_x else {
+                    return false;
+                };
+                self.drag_x = Some(drag_x + dx);
+                self.set_from_x(drag_x + dx);
+                true
+            }
+            // Other views may be tracking the same release
+            Event::MouseUp { .. } => {
+                self.drag_x = None;
+                false
+            }
+            _ => false,
+        }
+    }
+
+    fn bounds(&self) -> Option<Bounds> {
+        Some(self.bounds.get())
+    }
+
+    fn size(&self, _env: &Environment) -> (f32, f32) {
+        (self.width, 25.0)
+    }
+
+    fn accessibility(&self) -> Option<AccessibilityInfo> {
+        Some(AccessibilityInfo::new(Role::Slider))
+    }
+
+    fn rso_element(&self) -> Option<RsoElement> {
+        Some(RsoElement::new("Slider").arg(RsoValue::Number(self.min)).arg(RsoValue::Number(self.max)))
+    }
+
+    fn damage(&self) -> Damage {
+        let b = self.bounds.get();
+        // The knob overhangs both ends of the bar
+        self.drawn.damage(&self.value, Bounds::new(b.x - SLIDER_KNOB_OVERHANG, b.y, b.width + 2.0 * SLIDER_KNOB_OVERHANG, b.height))
+    }
+}
+
+// Room for the value between a Stepper's buttons
+const STEPPER_VALUE_WIDTH: f32 = 50.0;
+
+/// A number between decrement and increment buttons. Each click moves it
+/// by `step`, clamped to `min..=max`.
+pub struct Stepper {
+    pub value: Binding<i32>,
+    pub min: i32,
+    pub max: i32,
+    pub step: i32,
+    pub on_change: Option<Box<dyn FnMut(i32)>>,
+    decrement: Button,
+    increment: Button,
+    bounds: Cell<Bounds>,
+}
+
+impl Stepper {
+    /// Stepper over `min..=max`; bounds given the wrong way round are swapped.
+    pub fn new(value: Binding<i32>, min: i32, max: i32) -> Self {
+        Stepper {
+            value,
+            min: min.min(max),
+            max: max.max(min),
+            step: 1,
+            on_change: None,
+            decrement: Button::new("-".to_string()),
+            increment: Button::new("+".to_string()),
+            bounds: Cell::new(Bounds::default()),
+        }
+    }
+
+    pub fn step(mut self, step: i32) -> Self {
+        self.step = step;
+        self
+    }
+
+    pub fn on_change<F: FnMut(i32) + 'static>(mut self, f: F) -> Self {
+        self.on_change = Some(Box::new(f));
+        self
+    }
+
+    /// Adds `delta` to the value, clamped to the range. The callback only
+    /// runs when the value actually changes.
+    pub fn step_by(&mut self, delta: i32) {
+        let current = self.value.get();
+        let new_value = current.saturating_add(delta).clamp(self.min, self.max);
+        if new_value == current {
+            return;
+        }
+        self.value.set(new_value);
+        if let Some(ref mut callback) = self.on_change {
+            callback(new_value);
+        }
+    }
+}
+
+impl View for Stepper {
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        let (button_width, height) = self.decrement.size(env);
+        self.bounds.set(Bounds::new(x, y, 2.0 * button_width + STEPPER_VALUE_WIDTH, height));
+        self.decrement.render(renderer, env, x, y);
+        let value = self.value.get().to_string();
+        let (text_width, text_height) = measure_text(&value, env.font_size);
+        let text_x = x + button_width + (STEPPER_VALUE_WIDTH - text_width) / 2.0;
+        renderer.draw_text(&value, text_x, y + (height - text_height) / 2.0, env.font_size, env.text_color);
+        self.increment.render(renderer, env, x + button_width + STEPPER_VALUE_WIDTH, y);
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        if !matches!(event, Event::Click { .. }) {
+            return false;
+        }
+        // The buttons consume clicks inside their own bounds
+        if self.decrement.handle_event(event) {
+            self.step_by(-self.step);
+            true
+        } else if self.increment.handle_event(event) {
+            self.step_by(self.step);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn bounds(&self) -> Option<Bounds> {
+        Some(self.bounds.get())
+    }
+
+    fn size(&self, env: &Environment) -> (f32, f32) {
+        let (button_width, height) = self.decrement.size(env);
+        (2.0 * button_width + STEPPER_VALUE_WIDTH, height)
+    }
+
+    fn accessibility(&self) -> Option<AccessibilityInfo> {
+        Some(AccessibilityInfo::new(Role::SpinButton).label(self.value.get().to_string()))
+    }
+}
+
+/// The date formatted on one line, then a row of decrement and increment
+/// buttons for each of year, month and day.
+pub struct DatePicker {
+    pub value: Binding<Date>,
+    pub on_change: Option<Box<dyn FnMut(Date)>>,
+    // Decrement and increment buttons, one pair per `DateField::ALL` entry
+    buttons: Vec<(Button, Button)>,
+    bounds: Cell<Bounds>,
+}
+
+impl DatePicker {
+    pub fn new(value: Binding<Date>) -> Self {
+        DatePicker {
+            value,
+            on_change: None,
+            buttons: DateField::ALL.iter().map(|_| (Button::new("-".to_string()), Button::new("+".to_string()))).collect(),
+            bounds: Cell::new(Bounds::default()),
+        }
+    }
+
+    pub fn on_change<F: FnMut(Date) + 'static>(mut self, f: F) -> Self {
+        self.on_change = Some(Box::new(f));
+        self
+    }
+
+    /// Moves one field of the date by `delta`; see `Date::step`.
+    pub fn step(&mut self, field: DateField, delta: i32) {
+        let current = self.value.get();
+        let new_value = current.step(field, delta);
+        if new_value == current {
+            return;
+        }
+        self.value.set(new_value);
+        if let Some(ref mut callback) = self.on_change {
+            callback(new_value);
+        }
+    }
+}
+
+impl View for DatePicker {
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        let (width, height) = self.size(env);
+        self.bounds.set(Bounds::new(x, y, width, height));
+        let date = self.value.get();
+        let (_, text_height) = measure_text("0", env.font_size);
+        renderer.draw_text(&date.to_string(), x, y, env.font_size, env.text_color);
+        let mut row_y = y + text_height;
+        for (field, (decrement, increment)) in DateField::ALL.iter().zip(&self.buttons) {
+            let (button_width, button_height) = decrement.size(env);
+            decrement.render(renderer, env, x, row_y);
+            let value = match field {
+                DateField::Year => date.year.to_string(),
+                DateField::Month => date.month.to_string(),
+                DateField::Day => date.day.to_string(),
+            };
+            let (text_width, _) = measure_text(&value, env.font_size);
+            let text_x = x + button_width + (STEPPER_VALUE_WIDTH - text_width) / 2.0;
+            renderer.draw_text(&value, text_x, row_y + (button_height - text_height) / 2.0, env.font_size, env.text_color);
+            increment.render(renderer, env, x + button_width + STEPPER_VALUE_WIDTH, row_y);
+            row_y += button_height;
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        if !matches!(event, Event::Click { .. }) {
+            return false;
+        }
+        // The buttons consume clicks inside their own bounds
+        let hit = DateField::ALL.iter().zip(&mut self.buttons).find_map(|(&field, (decrement, increment))| {
+            if decrement.handle_event(event) {
+                Some((field, -1))
+            } else if increment.handle_event(event) {
+                Some((field, 1))
+            } else {
+                None
+            }
+        });
+        match hit {
+            Some((field, delta)) => {
+                self.step(field, delta);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn bounds(&self) -> Option<Bounds> {
+        Some(self.bounds.get())
+    }
+
+    fn size(&self, env: &Environment) -> (f32, f32) {
+        let (button_width, button_height) = self.buttons[0].0.size(env);
+        let (_, text_height) = measure_text("0", env.font_size);
+        (2.0 * button_width + STEPPER_VALUE_WIDTH, text_height + DateField::ALL.len() as f32 * button_height)
+    }
+
+    fn accessibility(&self) -> Option<AccessibilityInfo> {
+        Some(AccessibilityInfo::new(Role::Group).label(self.value.get().to_string()))
+    }
+}
+
+// Gap between a ColorPicker's channel labels, sliders and swatch
+const COLOR_PICKER_GAP: f32 = 5.0;
+
+/// Red, green and blue sliders next to a swatch of the color they make.
+/// The color's alpha is kept as it is.
+pub struct ColorPicker {
+    pub value: Binding<Color>,
+    pub on_change: Option<Box<dyn FnMut(Color)>>,
+    // Red, green and blue sliders, each bound to its channel of `value`
+    channels: Vec<Slider>,
+    bounds: Cell<Bounds>,
+}
+
+// Red, green or blue component of `color`, by index
+fn color_channel(color: &mut Color, index: usize) -> &mut u8 {
+    match index {
+        0 => &mut color.r,
+        1 => &mut color.g,
+        _ => &mut color.b,
+    }
+}
+
+impl ColorPicker {
+    pub fn new(value: Binding<Color>) -> Self {
+        let channels = (0..3)
+            .map(|index| {
+                let channel = value.map(
+                    move |color| [color.r, color.g, color.b][index] as f32,
+                    move |color, level: f32| *color_channel(color, index) = level.round().clamp(0.0, 255.0) as u8,
+                );
+                Slider::new(channel, 0.0, 255.0)
+            })
+            .collect();
+        ColorPicker { value, on_change: None, channels, bounds: Cell::new(Bounds::default()) }
+    }
+
+    pub fn on_change<F: FnMut(Color) + 'static>(mut self, f: F) -> Self {
+        self.on_change = Some(Box::new(f));
+        self
+    }
+}
+
+impl View for ColorPicker {
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        let (width, height) = self.size(env);
+        self.bounds.set(Bounds::new(x, y, width, height));
+        let color = self.value.get();
+        let (label_width, _) = measure_text("G", env.font_size);
+        let mut row_y = y;
+        let mut slider_width = 0.0;
+        for (slider, label) in self.channels.iter().zip(["R", "G", "B"]) {
+            renderer.draw_text(label, x, row_y, env.font_size, env.text_color);
+            slider.render(renderer, env, x + label_width + COLOR_PICKER_GAP, row_y);
+            let (w, h) = slider.size(env);
+            slider_width = w;
+            row_y += h + COLOR_PICKER_GAP;
+        }
+        let swatch_x = x + label_width + slider_width + 2.0 * COLOR_PICKER_GAP;
+        renderer.draw_rect(swatch_x, y, height, height, color);
+        renderer.draw_rect_outline(swatch_x, y, height, height, 1.0, env.secondary_color);
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        let before = self.value.get();
+        // Every slider sees the release, so none is left dragging
+        let mut consumed = false;
+        for slider in &mut self.channels {
+            consumed |= slider.handle_event(event);
+        }
+        let color = self.value.get();
+        if color != before {
+            if let Some(ref mut callback) = self.on_change {
+                callback(color);
+            }
+        }
+        consumed
+    }
+
+    fn bounds(&self) -> Option<Bounds> {
+        Some(self.bounds.get())
+    }
+
+    fn size(&self, env: &Environment) -> (f32, f32) {
+        let (label_width, _) = measure_text("G", env.font_size);
+        let (slider_width, slider_height) = self.channels[0].size(env);
+        let height = 3.0 * slider_height + 2.0 * COLOR_PICKER_GAP;
+        // The swatch is square, as tall as the three sliders
+        (label_width + slider_width + 2.0 * COLOR_PICKER_GAP + height, height)
+    }
+
+    fn accessibility(&self) -> Option<AccessibilityInfo> {
+        Some(AccessibilityInfo::new(Role::Group).label(self.value.get().to_hex()))
+    }
+}
+
+/// Entry in a `MenuBar`: a command, a submenu, or a separator line.
+pub struct MenuItem {
+    pub title: String,
+    pub action: Option<Box<dyn FnMut()>>,
+    pub children: Vec<MenuItem>,
+    pub is_separator: bool,
+}
+
+impl MenuItem {
+    pub fn new(title: impl Into<String>) -> Self {
+        MenuItem {
+            title: title.into(),
+            action: None,
+            children: Vec::new(),
+            is_separator: false,
+        }
+    }
+
+    pub fn submenu(title: impl Into<String>, children: Vec<MenuItem>) -> Self {
+        MenuItem { children, ..MenuItem::new(title) }
+    }
+
+    pub fn separator() -> Self {
+        MenuItem { is_separator: true, ..MenuItem::new("") }
+    }
+
+    pub fn action<F: FnMut() + 'static>(mut self, f: F) -> Self {
+        self.action = Some(Box::new(f));
+        self
+    }
+
+    pub fn child(mut self, item: MenuItem) -> Self {
+        self.children.push(item);
+        self
+    }
+
+    pub fn has_submenu(&self) -> bool {
+        !self.children.is_empty()
+    }
+}
+
+impl From<String> for MenuItem {
+    fn from(title: String) -> Self {
+        MenuItem::new(title)
+    }
+}
+
+impl From<&str> for MenuItem {
+    fn from(title: &str) -> Self {
+        MenuItem::new(title)
+    }
+}
+
+/// Follows `path` through nested submenus.
+fn menu_item_mut<'a>(items: &'a mut [MenuItem], path: &[usize]) -> Option<&'a mut MenuItem> {
+    let (&first, rest) = path.split_first()?;
+    let item = items.get_mut(first)?;
+    if rest.is_empty() {
+        Some(item)
+    } else {
+        menu_item_mut(&mut item.children, rest)
+    }
+}
+
+/// Horizontal menu bar whose items run an action or open a dropdown.
+pub struct MenuBar {
+    pub items: Vec<MenuItem>,
+    pub on_select: Option<Box<dyn FnMut(usize)>>,
+    id: ViewId,
+    // Path of open submenus: the top-level index, then one index per level
+    open_path: Vec<usize>,
+    bounds: Cell<Bounds>,
+    // Horizontal extent (start, end) of each item from the last render
+    item_ranges: RefCell<Vec<(f32, f32)>>,
+    font_size: Cell<u32>,
+}
+
+// Space on either side of a menu bar item's title
+const MENU_ITEM_PADDING: f32 = 8.0;
+const MENU_MIN_WIDTH: f32 = 120.0;
+// Room for the arrow marking an item with a submenu
+const MENU_ARROW_WIDTH: f32 = 16.0;
+const MENU_SEPARATOR_HEIGHT: f32 = 9.0;
+
+/// One open dropdown: its frame, its items and each row's (y, height).
+struct MenuPanel<'a> {
+    bounds: Bounds,
+    items: &'a [MenuItem],
+    rows: Vec<(f32, f32)>,
+}
+
+impl<'a> MenuPanel<'a> {
+    /// Lays `items` out in a column with its top-left corner at `(x, y)`.
+    fn new(items: &'a [MenuItem], x: f32, y: f32, font_size: u32) -> Self {
+        let row_height = measure_text("", font_size).1;
+        let text_width = items.iter().map(|item| measure_text(&item.title, font_size).0).fold(0.0, f32::max);
+        let width = (text_width + 2.0 * MENU_ITEM_PADDING + MENU_ARROW_WIDTH).max(MENU_MIN_WIDTH);
+        let mut rows = Vec::with_capacity(items.len());
+        let mut row_y = y;
+        for item in items {
+            let height = if item.is_separator { MENU_SEPARATOR_HEIGHT } else { row_height };
+            rows.push((row_y, height));
+            row_y += height;
+        }
+        MenuPanel { bounds: Bounds::new(x, y, width, row_y - y), items, rows }
+    }
+
+    fn row_at(&self, y: f32) -> Option<usize> {
+        self.rows.iter().position(|&(top, height)| y >= top && y < top + height)
+    }
+
+    /// Draws the panel, highlighting `open` (the row whose submenu is showing).
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, open: Option<usize>) {
+        let b = self.bounds;
+        renderer.draw_rect(b.x, b.y, b.width, b.height, env.background_color);
+        renderer.draw_rect_outline(b.x, b.y, b.width, b.height, 1.0, env.secondary_color);
+        for (i, (item, &(row_y, row_height))) in self.items.iter().zip(&self.rows).enumerate() {
+            if item.is_separator {
+                let line_y = row_y + (row_height / 2.0).floor();
+                renderer.draw_rect(b.x + 4.0, line_y, b.width - 8.0, 1.0, env.secondary_color);
+                continue;
+            }
+            if open == Some(i) {
+                renderer.draw_rect(b.x, row_y, b.width, row_height, env.secondary_color);
+            }
+            renderer.draw_text(&item.title, b.x + MENU_ITEM_PADDING, row_y, env.font_size, env.text_color);
+            if item.has_submenu() {
+                renderer.draw_text(">", b.x + b.width - MENU_ARROW_WIDTH, row_y, env.font_size, env.text_color);
+            }
+        }
+    }
+}
+
+impl MenuBar {
+    pub fn new<I: Into<MenuItem>>(items: Vec<I>) -> Self {
+        MenuBar {
+            items: items.into_iter().map(Into::into).collect(),
+            on_select: None,
+            id: ViewId::next(),
+            open_path: Vec::new(),
+            bounds: Cell::new(Bounds::default()),
+            item_ranges: RefCell::new(Vec::new()),
+            font_size: Cell::new(Environment::default().font_size),
+        }
+    }
+
+    /// Widths of each item: its title plus padding on both sides.
+    fn item_widths(&self, env: &Environment) -> Vec<f32> {
+        self.items.iter().map(|item| measure_text(&item.title, env.font_size).0 + 2.0 * MENU_ITEM_PADDING).collect()
+    }
+
+    /// Index of the item under `x`, using the ranges from the last render.
+    pub fn item_at(&self, x: f32) -> Option<usize> {
+        self.item_ranges.borrow().iter().position(|&(start, end)| x >= start && x < end)
+    }
+
+    pub fn on_select<F: FnMut(usize) + 'static>(mut self, f: F) -> Self {
+        self.on_select = Some(Box::new(f));
+        self
+    }
+
+    /// The item reached by following `path` through submenus.
+    pub fn item(&self, path: &[usize]) -> Option<&MenuItem> {
+        let (&first, rest) = path.split_first()?;
+        rest.iter().try_fold(self.items.get(first)?, |item, &index| item.children.get(index))
+    }
+
+    /// Path of the submenus currently open, outermost first.
+    pub fn open_path(&self) -> &[usize] {
+        &self.open_path
+    }
+
+    /// Acts as if the item at `path` was clicked: a submenu toggles open,
+    /// anything else runs its action and closes every menu.
+    pub fn activate(&mut self, path: &[usize]) {
+        let Some(item) = menu_item_mut(&mut self.items, path) else {
+            return;
+        };
+        if item.is_separator {
+            return;
+        }
+        if item.has_submenu() {
+            if self.open_path.starts_with(path) {
+                self.open_path.truncate(path.len() - 1);
+            } else {
+                self.open_path = path.to_vec();
+            }
+            return;
+        }
+        if let Some(ref mut action) = item.action {
+            action();
+        }
+        self.open_path.clear();
+        if let [index] = path {
+            if let Some(ref mut callback) = self.on_select {
+                callback(*index);
+            }
+        }
+    }
+
+    /// Dropdowns for the open path, each placed beside the row that opened it.
+    fn panels(&self) -> Vec<MenuPanel<'_>> {
+        let mut panels = Vec::new();
+        let Some((&first, _)) = self.open_path.split_first() else {
+            return panels;
+        };
+        let (Some(item), Some(&(start, _))) = (self.items.get(first), self.item_ranges.borrow().get(first)) else {
+            return panels;
+        };
+        let bar = self.bounds.get();
+        let (mut x, mut y) = (start, bar.y + bar.height);
+        let mut items = &item.children[..];
+        for depth in 1..=self.open_path.len() {
+            if items.is_empty() {
+                break;
+            }
+            let panel = MenuPanel::new(items, x, y, self.font_size.get());
+            let next = self.open_path.get(depth).and_then(|&index| Some((index, items.get(index)?)));
+            let Some((index, parent)) = next else {
+                panels.push(panel);
+                break;
+            };
+            x = panel.bounds.x + panel.bounds.width;
+            y = panel.rows[index].0;
+            items = &parent.children;
+            panels.push(panel);
+        }
+        panels
+    }
+
+    /// Path of the item under a point, checking open dropdowns before the bar.
+    fn path_at(&self, x: f32, y: f32) -> Option<Vec<usize>> {
+        for (depth, panel) in self.panels().iter().enumerate().rev() {
+            if panel.bounds.contains(x, y) {
+                let row = panel.row_at(y)?;
+                let mut path = self.open_path[..=depth].to_vec();
+                path.push(row);
+                return Some(path);
+            }
+        }
+        if self.bounds.get().contains(x, y) {
+            return self.item_at(x).map(|index| vec![index]);
+        }
+        None
+    }
+}
+
+impl View for MenuBar {
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        let (width, height) = self.size(env);
+        self.bounds.set(Bounds::new(x, y, width, height));
+        self.font_size.set(env.font_size);
+        let mut ranges = self.item_ranges.borrow_mut();
+        ranges.clear();
+        let mut current_x = x;
+        for (i, (item, item_width)) in self.items.iter().zip(self.item_widths(env)).enumerate() {
+            if self.open_path.first() == Some(&i) {
+                renderer.draw_rect(current_x, y, item_width, height, env.secondary_color);
+            }
+            renderer.draw_text(&item.title, current_x + MENU_ITEM_PADDING, y, env.font_size, env.text_color);
+            ranges.push((current_x, current_x + item_width));
+            current_x += item_width;
+        }
+    }
+
+    fn render_overlay(&self, renderer: &mut dyn Renderer, env: &Environment) {
+        for (depth, panel) in self.panels().iter().enumerate() {
+            panel.render(renderer, env, self.open_path.get(depth + 1).copied());
+        }
+    }
+
+    fn overlay_bounds(&self) -> Option<Bounds> {
+        // One frame covering every open dropdown
+        self.panels().iter().map(|panel| panel.bounds).reduce(|a, b| a.union(&b))
+    }
+
+    fn dismiss_overlay(&mut self) {
+        self.open_path.clear();
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        let Event::Click { x, y } = event else {
+            return false;
+        };
+        match self.path_at(*x, *y) {
+            Some(path) => {
+                self.activate(&path);
+                true
+            }
+            // Clicking anywhere else dismisses the menus
+            None => {
+                self.open_path.clear();
+                false
+            }
+        }
+    }
+
+    fn bounds(&self) -> Option<Bounds> {
+        Some(self.bounds.get())
+    }
+
+    fn id(&self) -> Option<ViewId> {
+        Some(self.id)
+    }
+
+    fn size(&self, env: &Environment) -> (f32, f32) {
+        (self.item_widths(env).iter().sum(), measure_text("", env.font_size).1)
+    }
+}
+
+pub struct ZStack {
+    pub children: Vec<Box<dyn View>>,
+    frame: Cell<Bounds>,
+}
+
+impl ZStack {
+    pub fn new() -> Self {
+        ZStack { children: Vec::new(), frame: Cell::new(Bounds::default()) }
+    }
+
+    pub fn add_child(&mut self, child: Box<dyn View>) {
+        self.children.push(child);
+    }
+}
+
+impl Default for ZStack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl View for ZStack {
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        let (width, height) = self.size(env);
+        self.frame.set(Bounds::new(x, y, width, height));
+        // Overlay at the same origin; later children draw on top. They share
+        // the room the ZStack is offered, so the environment passes through.
+        for child in &self.children {
+            child.render(renderer, env, x, y);
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        self.children.iter_mut().rev().any(|child| child.handle_event(event))
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        self.children.iter().map(|c| c.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        self.children.iter_mut().map(|c| c.as_mut() as &mut dyn View).collect()
+    }
+
+    fn size(&self, env: &Environment) -> (f32, f32) {
+        self.children
+            .iter()
+            .map(|c| c.size(env))
+            .fold((0.0, 0.0), |acc, s| (acc.0.max(s.0), acc.1.max(s.1)))
+    }
+
+    fn layout_bounds(&self) -> Option<Bounds> {
+        Some(self.frame.get())
+    }
+
+    fn rso_element(&self) -> Option<RsoElement> {
+        Some(RsoElement::new("ZStack"))
+    }
+
+    fn damage(&self) -> Damage {
+        children_damage(self)
+    }
+}
+
+pub struct List {
+    pub items: Vec<Box<dyn View>>,
+    pub separators: bool,
+    /// Index of the highlighted row, moved with Up/Down.
+    pub selection: Option<Binding<usize>>,
+    id: ViewId,
+    bounds: Cell<Bounds>,
+    // Writes a finished drag back to the bound collection
+    on_reorder: Option<Box<dyn FnMut(usize, usize)>>,
+    // Row being dragged and the pointer's y while the button is held
+    drag: Option<(usize, f32)>,
+}
+
+const LIST_ROW_HEIGHT: f32 = 30.0;
+
+/// Moves the element at `from` so it ends up at index `to`, shifting the
+/// ones in between. Out-of-range indices leave `items` unchanged.
+pub fn reorder<T>(items: &mut [T], from: usize, to: usize) {
+    if from >= items.len() || to >= items.len() {
+        return;
+    }
+    if from < to {
+        items[from..=to].rotate_left(1);
+    } else {
+        items[to..=from].rotate_right(1);
+    }
+}
+
+/// Where the element at `index` ends up after `reorder(items, from, to)`.
+pub fn reordered_index(index: usize, from: usize, to: usize) -> usize {
+    if index == from {
+        to
+    } else if from < index && index <= to {
+        index - 1
+    } else if to <= index && index < from {
+        index + 1
+    } else {
+        index
+    }
+}
+
+impl List {
+    pub fn new(items: Vec<Box<dyn View>>) -> Self {
+        List {
+            items,
+            separators: false,
+            selection: None,
+            id: ViewId::next(),
+            bounds: Cell::new(Bounds::default()),
+            on_reorder: None,
+            drag: None,
+        }
+    }
+
+    pub fn separators(mut self, separators: bool) -> Self {
+        self.separators = separators;
+        self
+    }
+
+    /// Lets Up/Down move a highlighted row, stored in `selection`.
+    pub fn selection(mut self, selection: Binding<usize>) -> Self {
+        self.selection = Some(selection);
+        self
+    }
+
+    /// Moves the selected row by `delta`, stopping at the first and last.
+    pub fn move_selection(&mut self, delta: isize) {
+        if let Some(selection) = &self.selection {
+            selection.set_if_changed(step_index(selection.get(), delta, self.items.len()));
+        }
+    }
+
+    /// Lets rows be dragged to a new position. A drop moves the row and the
+    /// matching element of `items`, which should hold one element per row.
+    pub fn reorderable<T: Clone + 'static>(mut self, items: Binding<Vec<T>>) -> Self {
+        self.on_reorder = Some(Box::new(move |from, to| {
+            let mut values = items.get();
+            reorder(&mut values, from, to);
+            items.set(values);
+        }));
+        self
+    }
+
+    /// Index the dragged row would land at if dropped now.
+    pub fn drop_index(&self) -> Option<usize> {
+        let (from, y) = self.drag?;
+        let gap = self.drop_gap(y);
+        // Gaps after the row shift down by one once it's taken out
+        Some(if gap > from { gap - 1 } else { gap })
+    }
+
+    // Gap between rows nearest to `y`, from 0 (above the first) to the row count
+    fn drop_gap(&self, y: f32) -> usize {
+        let offset = (y - self.bounds.get().y) / LIST_ROW_HEIGHT;
+        (offset.round().max(0.0) as usize).min(self.items.len())
+    }
+
+    fn finish_drag(&mut self) {
+        let (Some((from, _)), Some(to)) = (self.drag, self.drop_index()) else {
+            return;
+        };
+        self.drag = None;
+        if from == to {
+            return;
+        }
+        reorder(&mut self.items, from, to);
+        if let Some(on_reorder) = &mut self.on_reorder {
+            on_reorder(from, to);
+        }
+        // The selection follows its row, whether it moved or was shifted
+        if let Some(selection) = &self.selection {
+            selection.set_if_changed(reordered_index(selection.get(), from, to));
+        }
+    }
+
+    pub fn add_child(&mut self, child: Box<dyn View>) {
+        self.items.push(child);
+    }
+
+    // Each row is offered the list's width and one row's height
+    fn row_env<'e>(&self, env: &'e Environment) -> Environment<'e> {
+        env.with_available_size(None, Some(LIST_ROW_HEIGHT))
+    }
+}
+
+impl View for List {
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        let (width, height) = self.size(env);
+        self.bounds.set(Bounds::new(x, y, width, height));
+        let selected = self.selection.as_ref().map(|selection| selection.get());
+        let row_env = self.row_env(env);
+        let mut current_y = y;
+        for (i, item) in self.items.iter().enumerate() {
+            if selected == Some(i) {
+                renderer.draw_rect(x, current_y, width, LIST_ROW_HEIGHT, env.secondary_color);
+            }
+            item.render(renderer, &row_env, x, current_y);
+            current_y += LIST_ROW_HEIGHT;
+            // Separator between rows, not after the last one
+            if self.separators && i + 1 < self.items.len() {
+                renderer.draw_rect(x, current_y - 1.0, width, 1.0, env.secondary_color);
+            }
+        }
+        // Insertion line in the gap the dragged row would drop into
+        if let Some((_, pointer_y)) = self.drag {
+            let gap_y = y + self.drop_gap(pointer_y) as f32 * LIST_ROW_HEIGHT;
+            renderer.draw_rect(x, gap_y - 1.0, width, 2.0, env.primary_color);
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        if self.selection.is_some() {
+            match event {
+                Event::KeyDown(Key::Up) => {
+                    self.move_selection(-1);
+                    return true;
+                }
+                Event::KeyDown(Key::Down) => {
+                    self.move_selection(1);
+                    return true;
+                }
+                _ => {}
+            }
+        }
+        // Controls inside a row get the press before it starts a drag
+        if self.items.iter_mut().rev().any(|item| item.handle_event(event)) {
+            return true;
+        }
+        if self.on_reorder.is_none() {
+            return false;
+        }
+        match event {
+            Event::Click { x, y } if self.bounds.get().contains(*x, *y) => {
+                let row = ((y - self.bounds.get().y) / LIST_ROW_HEIGHT) as usize;
+                if row < self.items.len() {
+                    self.drag = Some((row, *y));
+                }
+                true
+            }
+            Event::Drag { dy, .. } => match &mut self.drag {
+                Some((_, y)) => {
+                    *y += dy;
+                    true
+                }
+                None => false,
+            },
+            Event::MouseUp { .. } => {
+                self.finish_drag();
+                false
+            }
+            _ => false,
+        }
+    }
+
+    fn id(&self) -> Option<ViewId> {
+        Some(self.id)
+    }
+
+    fn can_focus(&self) -> bool {
+        self.selection.is_some()
+    }
+
+    fn bounds(&self) -> Option<Bounds> {
+        Some(self.bounds.get())
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        self.items.iter().map(|c| c.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        self.items.iter_mut().map(|c| c.as_mut() as &mut dyn View).collect()
+    }
+
+    fn size(&self, env: &Environment) -> (f32, f32) {
+        let row_env = self.row_env(env);
+        let width = self.items.iter().map(|c| c.size(&row_env).0).fold(0.0, f32::max);
+        (width, self.items.len() as f32 * LIST_ROW_HEIGHT)
+    }
+
+    fn accessibility(&self) -> Option<AccessibilityInfo> {
+        Some(AccessibilityInfo::new(Role::List))
+    }
+}
+
+pub struct ProgressBar {
+    pub progress: Binding<f32>, // 0.0 to 1.0, clamped when drawn
+    pub width: f32,
+    frame: Cell<Bounds>,
+    drawn: DrawnVersion,
+}
+
+impl ProgressBar {
+    pub fn new(progress: Binding<f32>) -> Self {
+        ProgressBar { progress, width: 200.0, frame: Cell::new(Bounds::default()), drawn: DrawnVersion::new() }
+    }
+
+    pub fn width(mut self, w: f32) -> Self {
+        self.width = w;
+        self
+    }
+}
+
+impl View for ProgressBar {
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        self.frame.set(Bounds::new(x, y, self.width, 20.0));
+        self.drawn.record(&self.progress);
+        // Render track
+        renderer.draw_rect(x, y, self.width, 20.0, env.secondary_color);
+        // Render progress
+        let progress_width = self.progress.get().clamp(0.0, 1.0) * self.width;
+        renderer.draw_rect(x, y, progress_width, 20.0, env.primary_color);
+    }
+
+    fn handle_event(&mut self, _event: &Event) -> bool {
+        // Progress bars don't handle events
+        false
+    }
+
+    fn size(&self, _env: &Environment) -> (f32, f32) {
+        (self.width, 20.0)
+    }
+
+    fn accessibility(&self) -> Option<AccessibilityInfo> {
+        Some(AccessibilityInfo::new(Role::ProgressBar))
+    }
+
+    fn layout_bounds(&self) -> Option<Bounds> {
+        Some(self.frame.get())
+    }
+
+    fn damage(&self) -> Damage {
+        self.drawn.damage(&self.progress, self.frame.get())
+    }
+}
+
+pub struct TabView {
+    pub tabs: Vec<String>,
+    pub selected: Binding<usize>,
+    pub content: Vec<Box<dyn View>>,
+    bounds: Cell<Bounds>,
+    // Horizontal extent (start, end) of each header from the last render
+    header_ranges: RefCell<Vec<(f32, f32)>>,
+}
+
+const TAB_HEADER_HEIGHT: f32 = 30.0;
+// Space on either side of a tab's title
+const TAB_PADDING: f32 = 12.0;
+
+impl TabView {
+    pub fn new(tabs: Vec<String>, selected: Binding<usize>, content: Vec<Box<dyn View>>) -> Self {
+        TabView {
+            tabs,
+            selected,
+            content,
+            bounds: Cell::new(Bounds::default()),
+            header_ranges: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Builds the tabs from `(title, content)` pairs.
+    pub fn with_tabs(tabs: Vec<(String, Box<dyn View>)>, selected: Binding<usize>) -> Self {
+        let (titles, content) = tabs.into_iter().unzip();
+        Self::new(titles, selected, content)
+    }
+
+    fn header_widths(&self, env: &Environment) -> Vec<f32> {
+        self.tabs.iter().map(|tab| measure_text(tab, env.font_size).0 + 2.0 * TAB_PADDING).collect()
+    }
+
+    /// Index of the tab header at a point, using the last render's layout.
+    pub fn header_at(&self, x: f32, y: f32) -> Option<usize> {
+        let bounds = self.bounds.get();
+        if y < bounds.y || y >= bounds.y + TAB_HEADER_HEIGHT {
+            return None;
+        }
+        self.header_ranges.borrow().iter().position(|&(start, end)| x >= start && x < end)
+    }
+}
+
+impl View for TabView {
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        let (width, height) = self.size(env);
+        self.bounds.set(Bounds::new(x, y, width, height));
+        let selected = self.selected.get();
+        let mut ranges = self.header_ranges.borrow_mut();
+        ranges.clear();
+        let mut tab_x = x;
+        for (i, (tab, tab_width)) in self.tabs.iter().zip(self.header_widths(env)).enumerate() {
+            if i == selected {
+                renderer.draw_rect(tab_x, y, tab_width, TAB_HEADER_HEIGHT, env.secondary_color);
+                renderer.draw_rect(tab_x, y + TAB_HEADER_HEIGHT - 2.0, tab_width, 2.0, env.primary_color);
+            }
+            renderer.draw_text(tab, tab_x + TAB_PADDING, y, env.font_size, env.text_color);
+            ranges.push((tab_x, tab_x + tab_width));
+            tab_x += tab_width;
+        }
+        // Only the selected tab's content is drawn
+        if let Some(content) = self.content.get(selected) {
+            content.render(renderer, env, x, y + TAB_HEADER_HEIGHT);
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        if let Event::Click { x, y } = event {
+            if let Some(index) = self.header_at(*x, *y) {
+                if index != self.selected.get() {
+                    self.selected.set(index);
+                }
+                return true;
+            }
+        }
+        let selected = self.selected.get();
+        self.content.get_mut(selected).is_some_and(|content| content.handle_event(event))
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        // Only the visible tab takes part in hit-testing and focus
+        self.content.get(self.selected.get()).map(|c| c.as_ref()).into_iter().collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        let selected = self.selected.get();
+        self.content.get_mut(selected).map(|c| c.as_mut() as &mut dyn View).into_iter().collect()
+    }
+
+    fn bounds(&self) -> Option<Bounds> {
+        Some(self.bounds.get())
+    }
+
+    fn size(&self, env: &Environment) -> (f32, f32) {
+        let content = self.content.get(self.selected.get()).map(|c| c.size(env)).unwrap_or((0.0, 0.0));
+        let tabs_width: f32 = self.header_widths(env).iter().sum();
+        (tabs_width.max(content.0), TAB_HEADER_HEIGHT + content.1)
+    }
+
+    fn accessibility(&self) -> Option<AccessibilityInfo> {
+        Some(AccessibilityInfo::new(Role::TabList))
+    }
+}
+
+// Indent of a disclosure group's content under its header
+const DISCLOSURE_INDENT: f32 = 16.0;
+
+/// Section with a clickable header that shows or hides its content.
+pub struct DisclosureGroup {
+    pub title: String,
+    pub expanded: Binding<bool>,
+    pub content: Box<dyn View>,
+    // Header row from the last render, the click target
+    header_bounds: Cell<Bounds>,
+}
+
+impl DisclosureGroup {
+    pub fn new(title: String, expanded: Binding<bool>, content: Box<dyn View>) -> Self {
+        DisclosureGroup {
+            title,
+            expanded,
+            content,
+            header_bounds: Cell::new(Bounds::default()),
+        }
+    }
+
+    // Title with an arrow showing the current state
+    fn header(&self) -> String {
+        let arrow = if self.expanded.get() { "v" } else { ">" };
+        format!("{} {}", arrow, self.title)
+    }
+}
+
+impl View for DisclosureGroup {
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        let header = self.header();
+        let (_, header_height) = measure_text(&header, env.font_size);
+        self.header_bounds.set(Bounds::new(x, y, self.size(env).0, header_height));
+        renderer.draw_text(&header, x, y, env.font_size, env.text_color);
+        if self.expanded.get() {
+            self.content.render(renderer, env, x + DISCLOSURE_INDENT, y + header_height);
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        if let Event::Click { x, y } = event {
+            if self.header_bounds.get().contains(*x, *y) {
+                self.expanded.set(!self.expanded.get());
+                return true;
+            }
+        }
+        self.expanded.get() && self.content.handle_event(event)
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        // Hidden content takes no part in focus or hit-testing
+        if self.expanded.get() {
+            vec![self.content.as_ref()]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        if self.expanded.get() {
+            vec![self.content.as_mut()]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn bounds(&self) -> Option<Bounds> {
+        Some(self.header_bounds.get())
+    }
+
+    // The header is the click target, but the group covers its content too
+    fn layout_bounds(&self) -> Option<Bounds> {
+        let header = self.header_bounds.get();
+        match self.children().first().and_then(|content| content.layout_bounds()) {
+            Some(content) => Some(header.union(&content)),
+            None => Some(header),
+        }
+    }
+
+    fn size(&self, env: &Environment) -> (f32, f32) {
+        let (header_width, header_height) = measure_text(&self.header(), env.font_size);
+        if !self.expanded.get() {
+            return (header_width, header_height);
+        }
+        let (content_width, content_height) = self.content.size(env);
+        (header_width.max(DISCLOSURE_INDENT + content_width), header_height + content_height)
+    }
+
+    fn accessibility(&self) -> Option<AccessibilityInfo> {
+        Some(AccessibilityInfo::new(Role::Group).label(self.title.clone()))
+    }
+}
+
+/// Custom drawing code, called with the renderer and the canvas origin.
+pub type DrawCallback = Box<dyn Fn(&mut dyn Renderer, f32, f32)>;
+pub type EventCallback = Box<dyn FnMut(&Event)>;
+
+pub struct Canvas {
+    pub width: f32,
+    pub height: f32,
+    pub draw_callback: Option<DrawCallback>,
+    pub event_callback: Option<EventCallback>,
+    frame: Cell<Bounds>,
+}
+
+impl Canvas {
+    pub fn new(width: f32, height: f32) -> Self {
+        Canvas { width, height, draw_callback: None, event_callback: None, frame: Cell::new(Bounds::default()) }
+    }
+
+    pub fn on_draw<F: Fn(&mut dyn Renderer, f32, f32) + 'static>(mut self, f: F) -> Self {
+        self.draw_callback = Some(Box::new(f));
+        self
+    }
+
+    pub fn on_event<F: FnMut(&Event) + 'static>(mut self, f: F) -> Self {
+        self.event_callback = Some(Box::new(f));
+        self
+    }
+}
+
+impl View for Canvas {
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        self.frame.set(Bounds::new(x, y, self.width, self.height));
+        // Draw border
+        renderer.draw_rect(x, y, self.width, self.height, env.secondary_color);
+        // Call custom draw
+        if let Some(ref callback) = self.draw_callback {
+            callback(renderer, x, y);
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        // The callback observes events without consuming them
+        if let Some(ref mut callback) = self.event_callback {
+            callback(event);
+        }
+        false
+    }
+
+    fn layout_bounds(&self) -> Option<Bounds> {
+        Some(self.frame.get())
+    }
+
+    fn size(&self, _env: &Environment) -> (f32, f32) {
+        (self.width, self.height)
+    }
+}
+
+// Pixels scrolled per wheel notch or arrow key press
+const SCROLL_STEP: f32 = 20.0;
+
+// Width of the bar along a ScrollView's right edge
+const SCROLLBAR_WIDTH: f32 = 10.0;
+
+pub struct ScrollView {
+    pub content: Box<dyn View>,
+    pub scroll_offset: Binding<f32>,
+    pub width: f32,
+    pub height: f32,
+    bounds: Cell<Bounds>,
+    content_height: Cell<f32>,
+    drawn_offset: DrawnVersion,
+}
+
+impl ScrollView {
+    pub fn new(content: Box<dyn View>, width: f32, height: f32) -> Self {
+        ScrollView {
+            content,
+            scroll_offset: State::new(0.0, Rc::new(RefCell::new(false))).binding(),
+            width,
+            height,
+            bounds: Cell::new(Bounds::default()),
+            content_height: Cell::new(0.0),
+            drawn_offset: DrawnVersion::new(),
+        }
+    }
+
+    /// Furthest the content can scroll, from the last measured content height.
+    pub fn max_offset(&self) -> f32 {
+        (self.content_height.get() - self.height).max(0.0)
+    }
+
+    /// Moves the content by `delta`, staying between the top and `max_offset`.
+    pub fn scroll_by(&mut self, delta: f32) {
+        self.scroll_offset.set_if_changed((self.scroll_offset.get() + delta).clamp(0.0, self.max_offset()));
+    }
+
+    // Content is offered the viewport beside the scrollbar
+    fn content_env<'e>(&self, env: &'e Environment) -> Environment<'e> {
+        env.with_available_size(Some(self.width - SCROLLBAR_WIDTH), Some(self.height))
+    }
+}
+
+impl View for ScrollView {
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        self.bounds.set(Bounds::new(x, y, self.width, self.height));
+        let content_env = self.content_env(env);
+        self.content_height.set(self.content.size(&content_env).1);
+        // Render content at offset, clipped to the viewport
+        renderer.push_clip(x, y, self.width, self.height);
+        self.drawn_offset.record(&self.scroll_offset);
+        self.content.render(renderer, &content_env, x, y - self.scroll_offset.get());
+        renderer.pop_clip();
+        // Render scrollbar placeholder
+        renderer.draw_rect(x + self.width - SCROLLBAR_WIDTH, y, SCROLLBAR_WIDTH, self.height, env.secondary_color);
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        match event {
+            Event::MouseWheel { x, y, dy, .. } => {
+                if !self.bounds.get().contains(*x, *y) {
+                    return false;
+                }
+                // Wheel up (positive dy) moves the content back toward the top
+                self.scroll_by(-dy * SCROLL_STEP);
+            }
+            Event::KeyDown(Key::Up) => self.scroll_by(-SCROLL_STEP),
+            Event::KeyDown(Key::Down) => self.scroll_by(SCROLL_STEP),
+            _ => return self.content.handle_event(event),
+        }
+        true
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        vec![self.content.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        vec![self.content.as_mut()]
+    }
+
+    fn size(&self, _env: &Environment) -> (f32, f32) {
+        (self.width, self.height)
+    }
+
+    fn bounds(&self) -> Option<Bounds> {
+        Some(self.bounds.get())
+    }
+
+    fn damage(&self) -> Damage {
+        self.drawn_offset.damage(&self.scroll_offset, self.bounds.get()).union(children_damage(self))
+    }
+}
+
+// Size a GeometryReader takes along an axis no container constrains
+const GEOMETRY_READER_DEFAULT_SIZE: Size = Size { width: 400.0, height: 300.0 };
+
+/// Fills the space its container offers and builds its content from that
+/// size. `builder` runs again only when the size changes.
+pub struct GeometryReader<F> {
+    pub builder: F,
+    // Content for the last size laid out. `render` can only build into
+    // `pending`; the next `&mut` call moves it here, where `children` sees it.
+    content: Option<(Size, Box<dyn View>)>,
+    pending: RefCell<Option<(Size, Box<dyn View>)>>,
+}
+
+impl<F> GeometryReader<F>
+where
+    F: Fn(Size) -> Box<dyn View>,
+{
+    pub fn new(builder: F) -> Self {
+        GeometryReader { builder, content: None, pending: RefCell::new(None) }
+    }
+
+    fn resolved_size(env: &Environment) -> Size {
+        Size::new(
+            env.available_width.unwrap_or(GEOMETRY_READER_DEFAULT_SIZE.width),
+            env.available_height.unwrap_or(GEOMETRY_READER_DEFAULT_SIZE.height),
+        )
+    }
+
+    fn adopt_pending(&mut self) {
+        if let Some(pending) = self.pending.get_mut().take() {
+            self.content = Some(pending);
+        }
+    }
+}
+
+impl<F> View for GeometryReader<F>
+where
+    F: Fn(Size) -> Box<dyn View>,
+{
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        let size = Self::resolved_size(env);
+        let env = env.with_available_size(Some(size.width), Some(size.height));
+        match &self.content {
+            Some((built_for, child)) if *built_for == size => child.render(renderer, &env, x, y),
+            _ => {
+                let mut pending = self.pending.borrow_mut();
+                if pending.as_ref().is_none_or(|(built_for, _)| *built_for != size) {
+                    *pending = Some((size, (self.builder)(size)));
+                }
+                if let Some((_, child)) = pending.as_ref() {
+                    child.render(renderer, &env, x, y);
+                }
+            }
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        self.adopt_pending();
+        self.content.as_mut().is_some_and(|(_, child)| child.handle_event(event))
+    }
+
+    fn update(&mut self, dt: f64) {
+        self.adopt_pending();
+        if let Some((_, child)) = &mut self.content {
+            child.update(dt);
+        }
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        self.content.iter().map(|(_, child)| child.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        self.adopt_pending();
+        self.content.iter_mut().map(|(_, child)| child.as_mut() as &mut dyn View).collect()
+    }
+
+    fn size(&self, env: &Environment) -> (f32, f32) {
+        let size = Self::resolved_size(env);
+        (size.width, size.height)
+    }
+}
+
+pub struct Spacer {
+    pub min_length: f32,
+    frame: Cell<Bounds>,
+}
+
+impl Spacer {
+    pub fn new() -> Self {
+        Spacer { min_length: 10.0, frame: Cell::new(Bounds::default()) }
+    }
+
+    pub fn min_length(mut self, len: f32) -> Self {
+        self.min_length = len;
+        self
+    }
+}
+
+impl Default for Spacer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl View for Spacer {
+    fn render(&self, _renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        // Nothing to draw; the frame is for layout debugging. A stack may
+        // stretch the spacer past the minimum recorded here.
+        let (width, height) = self.size(env);
+        self.frame.set(Bounds::new(x, y, width, height));
+    }
+
+    fn handle_event(&mut self, _event: &Event) -> bool {
+        // No events
+        false
+    }
+
+    fn size(&self, _env: &Environment) -> (f32, f32) {
+        (self.min_length, self.min_length)
+    }
+
+    fn layout_bounds(&self) -> Option<Bounds> {
+        Some(self.frame.get())
+    }
+
+    fn flex(&self) -> f32 {
+        1.0
+    }
+
+    fn rso_element(&self) -> Option<RsoElement> {
+        Some(RsoElement::new("Spacer").arg(RsoValue::Number(self.min_length)))
+    }
+
+    fn damage(&self) -> Damage {
+        Damage::None
+    }
+}
+
+pub struct Divider {
+    frame: Cell<Bounds>,
+}
+
+impl Divider {
+    pub fn new() -> Self {
+        Divider { frame: Cell::new(Bounds::default()) }
+    }
+}
+
+impl Default for Divider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl View for Divider {
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        self.frame.set(Bounds::new(x, y, 200.0, 1.0));
+        renderer.draw_rect(x, y, 200.0, 1.0, env.secondary_color); // Horizontal line
+    }
+
+    fn handle_event(&mut self, _event: &Event) -> bool {
+        // No events
+        false
+    }
+
+    fn size(&self, _env: &Environment) -> (f32, f32) {
+        (200.0, 1.0)
+    }
+
+    fn layout_bounds(&self) -> Option<Bounds> {
+        Some(self.frame.get())
+    }
+
+    fn rso_element(&self) -> Option<RsoElement> {
+        Some(RsoElement::new("Divider"))
+    }
+
+    fn damage(&self) -> Damage {
+        Damage::None
+    }
+}
+
+/// How an `Image` fits its pixels into its frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ContentMode {
+    /// Fill the frame exactly, ignoring the aspect ratio.
+    #[default]
+    Stretch,
+    /// Largest size that fits inside the frame, centered, keeping the aspect ratio.
+    Fit,
+    /// Smallest size that covers the frame, centered and clipped to it.
+    Fill,
+}
+
+impl ContentMode {
+    /// Where an image of `intrinsic` size is drawn within `frame`.
+    pub fn content_rect(&self, intrinsic: (f32, f32), frame: Bounds) -> Bounds {
+        let (image_width, image_height) = intrinsic;
+        if image_width <= 0.0 || image_height <= 0.0 {
+            return frame;
+        }
+        let scale_x = frame.width / image_width;
+        let scale_y = frame.height / image_height;
+        let scale = match self {
+            ContentMode::Stretch => return frame,
+            ContentMode::Fit => scale_x.min(scale_y),
+            ContentMode::Fill => scale_x.max(scale_y),
+        };
+        let (width, height) = (image_width * scale, image_height * scale);
+        Bounds::new(frame.x + (frame.width - width) / 2.0, frame.y + (frame.height - height) / 2.0, width, height)
+    }
+}
+
+pub struct Image {
+    pub width: f32,
+    pub height: f32,
+    /// File to draw; without one, or if it fails to load, a placeholder is drawn.
+    pub source: Option<PathBuf>,
+    pub content_mode: ContentMode,
+    // Pixel size of the source file, read when the image is created
+    intrinsic_size: Option<(f32, f32)>,
+    frame: Cell<Bounds>,
+}
+
+impl Image {
+    pub fn new(width: f32, height: f32) -> Self {
+        Image {
+            width,
+            height,
+            source: None,
+            content_mode: ContentMode::default(),
+            intrinsic_size: None,
+            frame: Cell::new(Bounds::default()),
+        }
+    }
+
+    /// Image loaded from a PNG or JPEG file, sized to the file's dimensions
+    /// until `width`/`height` say otherwise. Fails if the file can't be read
+    /// or isn't an image.
+    pub fn from_path(path: impl Into<PathBuf>) -> Result<Self, UiError> {
+        let path = path.into();
+        let (width, height) = image::image_dimensions(&path)?;
+        let (width, height) = (width as f32, height as f32);
+        Ok(Image { source: Some(path), intrinsic_size: Some((width, height)), ..Image::new(width, height) })
+    }
+
+    pub fn content_mode(mut self, mode: ContentMode) -> Self {
+        self.content_mode = mode;
+        self
+    }
+
+    /// Rectangle the image's pixels cover when its frame is at `(x, y)`.
+    pub fn content_rect(&self, x: f32, y: f32) -> Bounds {
+        let frame = Bounds::new(x, y, self.width, self.height);
+        match self.intrinsic_size {
+            Some(size) => self.content_mode.content_rect(size, frame),
+            None => frame,
+        }
+    }
+
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn height(mut self, height: f32) -> Self {
+        self.height = height;
+        self
+    }
+}
+
+impl View for Image {
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        self.frame.set(Bounds::new(x, y, self.width, self.height));
+        match self.source.as_deref().and_then(|path| renderer.load_image(path)) {
+            Some(texture) => {
+                let rect = self.content_rect(x, y);
+                // Only Fill overflows the frame
+                let clip = self.content_mode == ContentMode::Fill;
+                if clip {
+                    renderer.push_clip(x, y, self.width, self.height);
+                }
+                renderer.draw_image(texture, rect.x, rect.y, rect.width, rect.height, 1.0);
+                if clip {
+                    renderer.pop_clip();
+                }
+            }
+            None => renderer.draw_rect(x, y, self.width, self.height, env.secondary_color), // Placeholder
+        }
+    }
+
+    fn handle_event(&mut self, _event: &Event) -> bool {
+        // No events
+        false
+    }
+
+    fn size(&self, _env: &Environment) -> (f32, f32) {
+        (self.width, self.height)
+    }
+
+    fn layout_bounds(&self) -> Option<Bounds> {
+        Some(self.frame.get())
+    }
+
+    fn accessibility(&self) -> Option<AccessibilityInfo> {
+        Some(AccessibilityInfo::new(Role::Image))
+    }
+
+    fn damage(&self) -> Damage {
+        Damage::None
+    }
+}
+
+/// Square SVG icon, rasterized at the display's scale factor so it stays
+/// sharp at any size.
+pub struct Icon {
+    pub path: PathBuf,
+    pub size: f32,
+}
+
+impl Icon {
+    pub fn new(path: impl Into<PathBuf>, size: f32) -> Self {
+        Icon { path: path.into(), size }
+    }
+}
+
+impl View for Icon {
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        let pixels = (self.size * env.scale_factor).round().max(1.0) as u32;
+        match renderer.load_icon(&self.path, pixels, pixels) {
+            Some(texture) => renderer.draw_image(texture, x, y, self.size, self.size, 1.0),
+            None => renderer.draw_rect(x, y, self.size, self.size, env.secondary_color), // Placeholder
+        }
+    }
+
+    fn handle_event(&mut self, _event: &Event) -> bool {
+        // No events
+        false
+    }
+
+    fn size(&self, _env: &Environment) -> (f32, f32) {
+        (self.size, self.size)
+    }
+
+    fn accessibility(&self) -> Option<AccessibilityInfo> {
+        Some(AccessibilityInfo::new(Role::Image))
+    }
+
+    fn damage(&self) -> Damage {
+        Damage::None
+    }
+}
+
+// Placeholder for Renderer trait
+pub trait Renderer {
+    fn draw_text(&mut self, text: &str, x: f32, y: f32, font_size: u32, color: Color);
+    fn draw_rect(&mut self, x: f32, y: f32, w: f32, h: f32, color: Color);
+    /// Filled rectangle with corners rounded to `radius`.
+    fn draw_round_rect(&mut self, x: f32, y: f32, w: f32, h: f32, radius: f32, color: Color);
+    /// Border of a rectangle, drawn inside its bounds.
+    fn draw_rect_outline(&mut self, x: f32, y: f32, w: f32, h: f32, thickness: f32, color: Color);
+    /// Restricts drawing to a rectangle until the matching `pop_clip`.
+    /// Nested clips intersect with the enclosing one.
+    fn push_clip(&mut self, _x: f32, _y: f32, _w: f32, _h: f32) {}
+    fn pop_clip(&mut self) {}
+    /// Loads the image file at `path`, reusing it on later calls. `None` if
+    /// it can't be loaded or the renderer doesn't draw images.
+    fn load_image(&mut self, _path: &Path) -> Option<TextureId> {
+        None
+    }
+    /// Rasterizes the SVG file at `path` to `width` x `height` device pixels,
+    /// reusing the result for the same path and size.
+    fn load_icon(&mut self, _path: &Path, _width: u32, _height: u32) -> Option<TextureId> {
+        None
+    }
+    /// Draws a loaded image scaled to fill the rectangle, with `alpha` from
+    /// 0 (invisible) to 1 (as loaded).
+    fn draw_image(&mut self, _texture: TextureId, _x: f32, _y: f32, _w: f32, _h: f32, _alpha: f32) {}
+}
+
+// ViewModifier trait for SwiftUI-like modifiers
+pub trait ViewModifier {
+    fn modify_render(&self, view: &dyn View, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32);
+    /// Returns true when the event was consumed, like `View::handle_event`.
+    fn modify_event(&self, view: &mut dyn View, event: &Event) -> bool;
+    /// Draws the view's overlays; unchanged by default.
+    fn modify_overlay(&self, view: &dyn View, renderer: &mut dyn Renderer, env: &Environment) {
+        view.render_overlay(renderer, env);
+    }
+    /// Area of the modified view's overlays; unchanged by default.
+    fn modify_overlay_bounds(&self, view: &dyn View) -> Option<Bounds> {
+        view.overlay_bounds()
+    }
+    /// Closes the modifier's own overlay; see `View::dismiss_overlay`.
+    fn dismiss_overlay(&self) {}
+    /// Advances the modifier's own timers along with the view's.
+    fn modify_update(&self, view: &mut dyn View, dt: f64) {
+        view.update(dt);
+    }
+    fn modify_is_animating(&self, view: &dyn View) -> bool {
+        view.is_animating()
+    }
+    /// What changed since the modified view was last drawn; the view's own
+    /// damage by default.
+    fn modify_damage(&self, view: &dyn View) -> Damage {
+        view.damage()
+    }
+    /// Size of the modified view; unchanged by default.
+    fn modify_size(&self, view: &dyn View, env: &Environment) -> (f32, f32) {
+        view.size(env)
+    }
+    /// Stack flex weight of the modified view; unchanged by default.
+    fn modify_flex(&self, view: &dyn View) -> f32 {
+        view.flex()
+    }
+    /// Identity and hit area of the modified view, for modifiers that
+    /// receive targeted events.
+    fn id(&self) -> Option<ViewId> {
+        None
+    }
+    fn bounds(&self) -> Option<Bounds> {
+        None
+    }
+    fn lifecycle(&self) -> Option<LifecycleHooks> {
+        None
+    }
+    /// Whether the modified view accepts input.
+    fn enabled(&self) -> bool {
+        true
+    }
+    fn extends_to_edges(&self) -> bool {
+        false
+    }
+    /// Describes the modified view for assistive technology. Returning
+    /// `Some` makes the modifier stand in for the view in snapshots.
+    fn accessibility(&self, _view: &dyn View) -> Option<AccessibilityInfo> {
+        None
+    }
+}
+
+pub struct ModifiedContent<V: View, M: ViewModifier> {
+    pub view: V,
+    pub modifier: M,
+}
+
+impl<V: View, M: ViewModifier> View for ModifiedContent<V, M> {
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        self.modifier.modify_render(&self.view, renderer, env, x, y);
+    }
+
+    fn render_overlay(&self, renderer: &mut dyn Renderer, env: &Environment) {
+        self.modifier.modify_overlay(&self.view, renderer, env);
+    }
+
+    fn overlay_bounds(&self) -> Option<Bounds> {
+        self.modifier.modify_overlay_bounds(&self.view)
+    }
+
+    // The view is dismissed on its own as the tree is walked
+    fn dismiss_overlay(&mut self) {
+        self.modifier.dismiss_overlay();
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        self.modifier.modify_event(&mut self.view, event)
+    }
+
+    fn update(&mut self, dt: f64) {
+        self.modifier.modify_update(&mut self.view, dt);
+    }
+
+    fn is_animating(&self) -> bool {
+        self.modifier.modify_is_animating(&self.view)
+    }
+
+    fn damage(&self) -> Damage {
+        self.modifier.modify_damage(&self.view)
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        vec![&self.view]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        vec![&mut self.view]
+    }
+
+    fn size(&self, env: &Environment) -> (f32, f32) {
+        self.modifier.modify_size(&self.view, env)
+    }
+
+    fn id(&self) -> Option<ViewId> {
+        self.modifier.id()
+    }
+
+    fn bounds(&self) -> Option<Bounds> {
+        self.modifier.bounds().or_else(|| self.view.bounds())
+    }
+
+    fn layout_bounds(&self) -> Option<Bounds> {
+        self.modifier.bounds().or_else(|| self.view.layout_bounds())
+    }
+
+    fn flex(&self) -> f32 {
+        self.modifier.modify_flex(&self.view)
+    }
+
+    fn lifecycle(&self) -> Option<LifecycleHooks> {
+        self.modifier.lifecycle()
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.modifier.enabled()
+    }
+
+    fn extends_to_edges(&self) -> bool {
+        self.modifier.extends_to_edges() || self.view.extends_to_edges()
+    }
+
+    fn accessibility(&self) -> Option<AccessibilityInfo> {
+        self.modifier.accessibility(&self.view)
+    }
+
+    fn accessibility_children(&self) -> Vec<&dyn View> {
+        // A describing modifier replaces the view rather than wrapping it
+        if self.accessibility().is_some() {
+            self.view.accessibility_children()
+        } else {
+            vec![&self.view]
+        }
+    }
+}
+
+// Common modifiers
+pub struct PaddingModifier {
+    pub padding: f32,
+}
+
+impl PaddingModifier {
+    // Room left for the view inside the padding
+    fn child_env<'e>(&self, env: &'e Environment) -> Environment<'e> {
+        let inset = 2.0 * self.padding;
+        env.with_available_size(env.available_width.map(|w| w - inset), env.available_height.map(|h| h - inset))
+    }
+}
+
+impl ViewModifier for PaddingModifier {
+    fn modify_render(&self, view: &dyn View, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        view.render(renderer, &self.child_env(env), x + self.padding, y + self.padding);
+    }
+
+    fn modify_event(&self, view: &mut dyn View, event: &Event) -> bool {
+        view.handle_event(event)
+    }
+
+    fn modify_size(&self, view: &dyn View, env: &Environment) -> (f32, f32) {
+        let (width, height) = view.size(&self.child_env(env));
+        (width + self.padding * 2.0, height + self.padding * 2.0)
+    }
+}
+
+pub struct BackgroundModifier {
+    pub color: Color,
+}
+
+impl ViewModifier for BackgroundModifier {
+    fn modify_render(&self, view: &dyn View, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        let (width, height) = view.size(env);
+        renderer.draw_rect(x, y, width, height, self.color);
+        view.render(renderer, env, x, y);
+    }
+
+    fn modify_event(&self, view: &mut dyn View, event: &Event) -> bool {
+        view.handle_event(event)
+    }
+}
+
+/// Lets the wrapped view grow along a stack's axis, taking `weight` shares of
+/// the spare room.
+pub struct FlexModifier {
+    pub weight: f32,
+}
+
+impl ViewModifier for FlexModifier {
+    fn modify_render(&self, view: &dyn View, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        view.render(renderer, env, x, y);
+    }
+
+    fn modify_event(&self, view: &mut dyn View, event: &Event) -> bool {
+        view.handle_event(event)
+    }
+
+    fn modify_flex(&self, _view: &dyn View) -> f32 {
+        self.weight
+    }
+}
+
+/// Forces a size on the wrapped view; `None` axes keep the view's own.
+pub struct FrameModifier {
+    pub width: Option<f32>,
+    pub height: Option<f32>,
+    pub clip: bool,
+}
+
+impl ViewModifier for FrameModifier {
+    fn modify_render(&self, view: &dyn View, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        let env = &env.with_available_size(self.width, self.height);
+        if self.clip {
+            let (width, height) = self.modify_size(view, env);
+            renderer.push_clip(x, y, width, height);
+            view.render(renderer, env, x, y);
+            renderer.pop_clip();
+        } else {
+            view.render(renderer, env, x, y);
+        }
+    }
+
+    fn modify_event(&self, view: &mut dyn View, event: &Event) -> bool {
+        view.handle_event(event)
+    }
+
+    fn modify_size(&self, view: &dyn View, env: &Environment) -> (f32, f32) {
+        let (width, height) = view.size(&env.with_available_size(self.width, self.height));
+        (self.width.unwrap_or(width), self.height.unwrap_or(height))
+    }
+}
+
+impl<V: View> ModifiedContent<V, FrameModifier> {
+    /// Clips the view's drawing to the frame.
+    pub fn clipped(mut self) -> Self {
+        self.modifier.clip = true;
+        self
+    }
+}
+
+/// Sizes the wrapped view as a fraction of the room its container offers;
+/// `None` axes, or axes the container doesn't know, keep the view's own.
+pub struct RelativeFrameModifier {
+    pub width: Option<f32>,
+    pub height: Option<f32>,
+}
+
+impl RelativeFrameModifier {
+    fn resolve(&self, env: &Environment) -> (Option<f32>, Option<f32>) {
+        let width = self.width.zip(env.available_width).map(|(fraction, available)| fraction * available);
+        let height = self.height.zip(env.available_height).map(|(fraction, available)| fraction * available);
+        (width, height)
+    }
+}
+
+impl ViewModifier for RelativeFrameModifier {
+    fn modify_render(&self, view: &dyn View, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        let (width, height) = self.resolve(env);
+        view.render(renderer, &env.with_available_size(width, height), x, y);
+    }
+
+    fn modify_event(&self, view: &mut dyn View, event: &Event) -> bool {
+        view.handle_event(event)
+    }
+
+    fn modify_size(&self, view: &dyn View, env: &Environment) -> (f32, f32) {
+        let (width, height) = self.resolve(env);
+        let (own_width, own_height) = view.size(&env.with_available_size(width, height));
+        (width.unwrap_or(own_width), height.unwrap_or(own_height))
+    }
+}
+
+pub struct ForegroundColorModifier {
+    pub color: Color,
+}
+
+impl ViewModifier for ForegroundColorModifier {
+    fn modify_render(&self, view: &dyn View, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        // For now, assume it affects text, but since placeholder, just render
+        view.render(renderer, env, x, y);
+    }
+
+    fn modify_event(&self, view: &mut dyn View, event: &Event) -> bool {
+        view.handle_event(event)
+    }
+}
+
+pub struct CornerRadiusModifier {
+    pub radius: f32,
+}
+
+impl ViewModifier for CornerRadiusModifier {
+    fn modify_render(&self, view: &dyn View, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        let (width, height) = view.size(env);
+        let mut rounded = RoundedRenderer {
+            inner: renderer,
+            bounds: Bounds::new(x, y, width, height),
+            radius: self.radius,
+        };
+        view.render(&mut rounded, env, x, y);
+    }
+
+    fn modify_event(&self, view: &mut dyn View, event: &Event) -> bool {
+        view.handle_event(event)
+    }
+}
+
+// Rounds fills that cover the whole view (its background), passing
+// everything else through
+struct RoundedRenderer<'a> {
+    inner: &'a mut dyn Renderer,
+    bounds: Bounds,
+    radius: f32,
+}
+
+impl Renderer for RoundedRenderer<'_> {
+    fn draw_text(&mut self, text: &str, x: f32, y: f32, font_size: u32, color: Color) {
+        self.inner.draw_text(text, x, y, font_size, color);
+    }
+
+    fn draw_rect(&mut self, x: f32, y: f32, w: f32, h: f32, color: Color) {
+        if Bounds::new(x, y, w, h) == self.bounds {
+            self.inner.draw_round_rect(x, y, w, h, self.radius, color);
+        } else {
+            self.inner.draw_rect(x, y, w, h, color);
+        }
+    }
+
+    fn draw_round_rect(&mut self, x: f32, y: f32, w: f32, h: f32, radius: f32, color: Color) {
+        self.inner.draw_round_rect(x, y, w, h, radius, color);
+    }
+
+    fn draw_rect_outline(&mut self, x: f32, y: f32, w: f32, h: f32, thickness: f32, color: Color) {
+        self.inner.draw_rect_outline(x, y, w, h, thickness, color);
+    }
+
+    fn push_clip(&mut self, x: f32, y: f32, w: f32, h: f32) {
+        self.inner.push_clip(x, y, w, h);
+    }
+
+    fn pop_clip(&mut self) {
+        self.inner.pop_clip();
+    }
+
+    fn load_image(&mut self, path: &Path) -> Option<TextureId> {
+        self.inner.load_image(path)
+    }
+
+    fn load_icon(&mut self, path: &Path, width: u32, height: u32) -> Option<TextureId> {
+        self.inner.load_icon(path, width, height)
+    }
+
+    fn draw_image(&mut self, texture: TextureId, x: f32, y: f32, w: f32, h: f32, alpha: f32) {
+        self.inner.draw_image(texture, x, y, w, h, alpha);
+    }
+}
+
+// Scales the alpha of every draw call by `opacity`
+struct FadedRenderer<'a> {
+    inner: &'a mut dyn Renderer,
+    opacity: f32,
+}
+
+impl Renderer for FadedRenderer<'_> {
+    fn draw_text(&mut self, text: &str, x: f32, y: f32, font_size: u32, color: Color) {
+        self.inner.draw_text(text, x, y, font_size, color.faded(self.opacity));
+    }
+
+    fn draw_rect(&mut self, x: f32, y: f32, w: f32, h: f32, color: Color) {
+        self.inner.draw_rect(x, y, w, h, color.faded(self.opacity));
+    }
+
+    fn draw_round_rect(&mut self, x: f32, y: f32, w: f32, h: f32, radius: f32, color: Color) {
+        self.inner.draw_round_rect(x, y, w, h, radius, color.faded(self.opacity));
+    }
+
+    fn draw_rect_outline(&mut self, x: f32, y: f32, w: f32, h: f32, thickness: f32, color: Color) {
+        self.inner.draw_rect_outline(x, y, w, h, thickness, color.faded(self.opacity));
+    }
+
+    fn push_clip(&mut self, x: f32, y: f32, w: f32, h: f32) {
+        self.inner.push_clip(x, y, w, h);
+    }
+
+    fn pop_clip(&mut self) {
+        self.inner.pop_clip();
+    }
+
+    fn load_image(&mut self, path: &Path) -> Option<TextureId> {
+        self.inner.load_image(path)
+    }
+
+    fn load_icon(&mut self, path: &Path, width: u32, height: u32) -> Option<TextureId> {
+        self.inner.load_icon(path, width, height)
+    }
+
+    fn draw_image(&mut self, texture: TextureId, x: f32, y: f32, w: f32, h: f32, alpha: f32) {
+        self.inner.draw_image(texture, x, y, w, h, alpha * self.opacity.clamp(0.0, 1.0));
+    }
+}
+
+// Renders `view` with every draw's alpha scaled by `opacity`
+fn render_faded(view: &dyn View, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32, opacity: f32) {
+    if opacity >= 1.0 {
+        view.render(renderer, env, x, y);
+    } else {
+        view.render(&mut FadedRenderer { inner: renderer, opacity }, env, x, y);
+    }
+}
+
+/// Draws a copy of the view's bounds, offset and in `color`, behind it.
+pub struct ShadowModifier {
+    pub offset: (f32, f32),
+    /// Width in pixels of the faded edge around the shadow.
+    pub blur: f32,
+    pub color: Color,
+}
+
+impl ViewModifier for ShadowModifier {
+    fn modify_render(&self, view: &dyn View, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        let (width, height) = view.size(env);
+        let (shadow_x, shadow_y) = (x + self.offset.0, y + self.offset.1);
+        // Fake the blur with progressively larger, fainter rects
+        let layers = self.blur.round().max(0.0) as u32;
+        for layer in (1..=layers).rev() {
+            let spread = layer as f32;
+            let fade = 1.0 - spread / (layers + 1) as f32;
+            let color = Color { a: (self.color.a as f32 * fade / layers as f32) as u8, ..self.color };
+            renderer.draw_rect(shadow_x - spread, shadow_y - spread, width + spread * 2.0, height + spread * 2.0, color);
+        }
+        renderer.draw_rect(shadow_x, shadow_y, width, height, self.color);
+        view.render(renderer, env, x, y);
+    }
+
+    fn modify_event(&self, view: &mut dyn View, event: &Event) -> bool {
+        view.handle_event(event)
+    }
+}
+
+impl<V: View> ModifiedContent<V, ShadowModifier> {
+    pub fn blur(mut self, blur: f32) -> Self {
+        self.modifier.blur = blur;
+        self
+    }
+}
+
+pub struct LifecycleModifier {
+    pub hooks: LifecycleHooks,
+}
+
+impl ViewModifier for LifecycleModifier {
+    fn modify_render(&self, view: &dyn View, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        view.render(renderer, env, x, y);
+    }
+
+    fn modify_event(&self, view: &mut dyn View, event: &Event) -> bool {
+        view.handle_event(event)
+    }
+
+    fn lifecycle(&self) -> Option<LifecycleHooks> {
+        Some(self.hooks.clone())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Gesture {
+    DoubleClick,
+    LongPress,
+}
+
+/// Runs a callback when a gesture lands inside the modified view.
+pub struct GestureModifier {
+    pub gesture: Gesture,
+    pub action: LifecycleCallback,
+    // Where the view was last drawn, for hit-testing
+    bounds: Cell<Bounds>,
+}
+
+impl ViewModifier for GestureModifier {
+    fn modify_render(&self, view: &dyn View, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        let (width, height) = view.size(env);
+        self.bounds.set(Bounds::new(x, y, width, height));
+        view.render(renderer, env, x, y);
+    }
+
+    fn modify_event(&self, view: &mut dyn View, event: &Event) -> bool {
+        let hit = match (self.gesture, event) {
+            (Gesture::DoubleClick, Event::DoubleClick { x, y }) | (Gesture::LongPress, Event::LongPress { x, y }) => {
+                self.bounds.get().contains(*x, *y)
             }
+            _ => false,
+        };
+        let consumed = view.handle_event(event);
+        if hit {
+            (self.action.borrow_mut())();
         }
-        // Handle content events
-        let selected = self.selected.get();
-        if selected < self.content.len() {
-            self.content[selected].handle_event(event);
-        }
+        consumed || hit
     }
 }
 
-pub struct Canvas {
-    pub width: f32,
-    pub height: f32,
-    pub draw_callback: Option<Box<dyn Fn(&mut dyn Renderer)>>,
+/// Overrides an environment value for the wrapped subtree.
+pub struct EnvironmentModifier {
+    pub key: String,
+    pub value: Rc<dyn std::any::Any>,
 }
 
-impl Canvas {
-    pub fn new(width: f32, height: f32) -> Self {
-        Canvas { width, height, draw_callback: None }
+impl EnvironmentModifier {
+    fn apply<'e>(&'e self, env: &'e Environment) -> Environment<'e> {
+        env.with_value(&self.key, self.value.as_ref())
     }
+}
 
-    pub fn on_draw<F: Fn(&mut dyn Renderer) + 'static>(mut self, f: F) -> Self {
-        self.draw_callback = Some(Box::new(f));
-        self
+impl ViewModifier for EnvironmentModifier {
+    fn modify_render(&self, view: &dyn View, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        view.render(renderer, &self.apply(env), x, y);
+    }
+
+    fn modify_event(&self, view: &mut dyn View, event: &Event) -> bool {
+        view.handle_event(event)
+    }
+
+    fn modify_overlay(&self, view: &dyn View, renderer: &mut dyn Renderer, env: &Environment) {
+        view.render_overlay(renderer, &self.apply(env));
+    }
+
+    fn modify_size(&self, view: &dyn View, env: &Environment) -> (f32, f32) {
+        view.size(&self.apply(env))
     }
 }
 
-impl View for Canvas {
-    fn render(&self, renderer: &mut dyn Renderer, _theme: &Theme, x: f32, y: f32) {
-        // Draw border
-        renderer.draw_rect(x, y, self.width, self.height);
-        // Call custom draw
-        if let Some(ref callback) = self.draw_callback {
-            callback(renderer);
+/// Calls `action(true)` when the pointer enters the view and `action(false)`
+/// when it leaves.
+pub struct HoverModifier {
+    pub action: RefCell<Box<dyn FnMut(bool)>>,
+    id: ViewId,
+    bounds: Cell<Bounds>,
+}
+
+impl ViewModifier for HoverModifier {
+    fn modify_render(&self, view: &dyn View, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        let (width, height) = view.size(env);
+        self.bounds.set(Bounds::new(x, y, width, height));
+        view.render(renderer, env, x, y);
+    }
+
+    fn modify_event(&self, view: &mut dyn View, event: &Event) -> bool {
+        // Enter/leave are addressed to this modifier; the view gets its own
+        match event {
+            Event::MouseEnter => (self.action.borrow_mut())(true),
+            Event::MouseLeave => (self.action.borrow_mut())(false),
+            _ => return view.handle_event(event),
         }
+        false
     }
 
-    fn handle_event(&mut self, _event: &Event) {
-        // Canvas doesn't handle events by default
+    fn id(&self) -> Option<ViewId> {
+        Some(self.id)
+    }
+
+    fn bounds(&self) -> Option<Bounds> {
+        Some(self.bounds.get())
     }
 }
 
-pub struct ScrollView {
-    pub content: Box<dyn View>,
-    pub scroll_offset: Binding<f32>,
-    pub width: f32,
-    pub height: f32,
+/// Overrides the role and/or label the view reports to assistive technology.
+pub struct AccessibilityModifier {
+    pub role: Option<Role>,
+    pub label: Option<String>,
 }
 
-impl ScrollView {
-    pub fn new(content: Box<dyn View>, width: f32, height: f32) -> Self {
-        ScrollView {
-            content,
-            scroll_offset: State::new(0.0, Rc::new(RefCell::new(false))).binding(),
-            width,
-            height,
+impl ViewModifier for AccessibilityModifier {
+    fn modify_render(&self, view: &dyn View, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        view.render(renderer, env, x, y);
+    }
+
+    fn modify_event(&self, view: &mut dyn View, event: &Event) -> bool {
+        view.handle_event(event)
+    }
+
+    fn accessibility(&self, view: &dyn View) -> Option<AccessibilityInfo> {
+        let own = view.accessibility();
+        Some(AccessibilityInfo {
+            role: self.role.or(own.as_ref().map(|info| info.role)).unwrap_or(Role::Group),
+            label: self.label.clone().or(own.and_then(|info| info.label)),
+        })
+    }
+}
+
+/// Shows a floating menu at the pointer when the view is right-clicked.
+pub struct ContextMenuModifier {
+    pub items: RefCell<Vec<MenuItem>>,
+    id: ViewId,
+    bounds: Cell<Bounds>,
+    // Top-left corner of the open menu
+    position: Cell<Option<(f32, f32)>>,
+    font_size: Cell<u32>,
+}
+
+impl ContextMenuModifier {
+    pub fn is_open(&self) -> bool {
+        self.position.get().is_some()
+    }
+
+    fn menu_bounds(&self) -> Option<Bounds> {
+        let (x, y) = self.position.get()?;
+        Some(MenuPanel::new(&self.items.borrow(), x, y, self.font_size.get()).bounds)
+    }
+
+    // Runs the item under a click inside the open menu; separators keep it open
+    fn select(&self, y: f32) {
+        let Some((left, top)) = self.position.get() else {
+            return;
+        };
+        let mut items = self.items.borrow_mut();
+        let Some(row) = MenuPanel::new(&items, left, top, self.font_size.get()).row_at(y) else {
+            return;
+        };
+        let item = &mut items[row];
+        if item.is_separator {
+            return;
+        }
+        self.position.set(None);
+        if let Some(ref mut action) = item.action {
+            action();
         }
     }
 }
 
-impl View for ScrollView {
-    fn render(&self, renderer: &mut dyn Renderer, theme: &Theme, x: f32, y: f32) {
-        // Render content at offset
-        self.content.render(renderer, theme, x, y - self.scroll_offset.get());
-        // Render scrollbar placeholder
-        renderer.draw_rect(x + self.width - 10.0, y, 10.0, self.height);
+impl ViewModifier for ContextMenuModifier {
+    fn modify_render(&self, view: &dyn View, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        let (width, height) = view.size(env);
+        self.bounds.set(Bounds::new(x, y, width, height));
+        self.font_size.set(env.font_size);
+        view.render(renderer, env, x, y);
     }
 
-    fn handle_event(&mut self, event: &Event) {
+    fn modify_event(&self, view: &mut dyn View, event: &Event) -> bool {
         match event {
-            Event::KeyDown(key) => {
-                if *key == sdl2::keyboard::Keycode::Up {
-                    let current = self.scroll_offset.get();
-                    self.scroll_offset.set((current - 10.0).max(0.0));
-                } else if *key == sdl2::keyboard::Keycode::Down {
-                    let current = self.scroll_offset.get();
-                    self.scroll_offset.set(current + 10.0);
+            Event::RightClick { x, y } => {
+                let inside = self.bounds.get().contains(*x, *y);
+                self.position.set(inside.then_some((*x, *y)));
+                if inside {
+                    return true;
+                }
+            }
+            Event::Click { x, y } if self.is_open() => {
+                if self.menu_bounds().is_some_and(|menu| menu.contains(*x, *y)) {
+                    self.select(*y);
+                    return true;
                 }
+                // Clicking anywhere else dismisses the menu
+                self.position.set(None);
             }
-            _ => self.content.handle_event(event),
+            _ => {}
         }
+        view.handle_event(event)
     }
-}
 
-pub struct GeometryReader<F> {
-    pub builder: F,
-}
+    fn modify_overlay(&self, view: &dyn View, renderer: &mut dyn Renderer, env: &Environment) {
+        view.render_overlay(renderer, env);
+        if let Some((x, y)) = self.position.get() {
+            MenuPanel::new(&self.items.borrow(), x, y, env.font_size).render(renderer, env, None);
+        }
+    }
 
-impl<F> GeometryReader<F>
-where
-    F: Fn(f32, f32) -> Box<dyn View>,
-{
-    pub fn new(builder: F) -> Self {
-        GeometryReader { builder }
+    fn modify_overlay_bounds(&self, view: &dyn View) -> Option<Bounds> {
+        self.menu_bounds().or_else(|| view.overlay_bounds())
     }
-}
 
-impl<F> View for GeometryReader<F>
-where
-    F: Fn(f32, f32) -> Box<dyn View>,
-{
-    fn render(&self, renderer: &mut dyn Renderer, theme: &Theme, x: f32, y: f32) {
-        // Assume size, in real impl get from layout
-        let width = 400.0;
-        let height = 300.0;
-        let child = (self.builder)(width, height);
-        child.render(renderer, theme, x, y);
+    fn dismiss_overlay(&self) {
+        self.position.set(None);
+    }
+
+    fn id(&self) -> Option<ViewId> {
+        Some(self.id)
     }
 
-    fn handle_event(&mut self, event: &Event) {
-        // For simplicity, no event handling
+    fn bounds(&self) -> Option<Bounds> {
+        Some(self.bounds.get())
     }
 }
 
-pub struct Spacer {
-    pub min_length: f32,
+/// Decides when a tooltip shows: once the pointer has rested on the view
+/// for `delay` seconds. Moving restarts the wait until the tooltip is up.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HoverDelay {
+    pub delay: f64,
+    // Seconds the pointer has rested, while it's over the view
+    rested: Option<f64>,
 }
 
-impl Spacer {
-    pub fn new() -> Self {
-        Spacer { min_length: 10.0 }
+impl HoverDelay {
+    pub fn new(delay: f64) -> Self {
+        HoverDelay { delay, rested: None }
     }
 
-    pub fn min_length(mut self, len: f32) -> Self {
-        self.min_length = len;
-        self
+    pub fn enter(&mut self) {
+        self.rested = Some(0.0);
     }
-}
 
-impl View for Spacer {
-    fn render(&self, _renderer: &mut dyn Renderer, _theme: &Theme, _x: f32, _y: f32) {
-        // Spacer doesn't render anything
+    pub fn moved(&mut self) {
+        if self.is_pending() {
+            self.rested = Some(0.0);
+        }
     }
 
-    fn handle_event(&mut self, _event: &Event) {
-        // No events
+    pub fn leave(&mut self) {
+        self.rested = None;
     }
-}
-
-pub struct Divider {}
 
-impl Divider {
-    pub fn new() -> Self {
-        Divider {}
+    /// Advances the wait by `dt` seconds. Returns true on the tick the
+    /// tooltip becomes visible.
+    pub fn tick(&mut self, dt: f64) -> bool {
+        let Some(rested) = self.rested else {
+            return false;
+        };
+        let was_visible = self.is_visible();
+        self.rested = Some(rested + dt);
+        !was_visible && self.is_visible()
     }
-}
 
-impl View for Divider {
-    fn render(&self, renderer: &mut dyn Renderer, theme: &Theme, x: f32, y: f32) {
-        renderer.draw_rect(x, y, 200.0, 1.0); // Horizontal line
+    pub fn is_visible(&self) -> bool {
+        self.rested.is_some_and(|rested| rested >= self.delay)
     }
 
-    fn handle_event(&mut self, _event: &Event) {
-        // No events
+    /// Whether the pointer is over the view but the delay hasn't passed.
+    pub fn is_pending(&self) -> bool {
+        self.rested.is_some() && !self.is_visible()
     }
 }
 
-pub struct Image {
-    pub width: f32,
-    pub height: f32,
+// Seconds the pointer rests on a view before its tooltip shows
+const TOOLTIP_DELAY: f64 = 0.5;
+const TOOLTIP_PADDING: f32 = 4.0;
+// Offset of a tooltip from the pointer, so the cursor doesn't cover it
+const TOOLTIP_OFFSET: f32 = 16.0;
+
+/// Shows `text` in a small box by the pointer once it has rested on the
+/// view for the delay; leaving the view or clicking hides it.
+pub struct TooltipModifier {
+    pub text: String,
+    hover: Cell<HoverDelay>,
+    id: ViewId,
+    bounds: Cell<Bounds>,
+    pointer: Cell<(f32, f32)>,
 }
 
-impl Image {
-    pub fn new(width: f32, height: f32) -> Self {
-        Image { width, height }
+impl TooltipModifier {
+    pub fn is_visible(&self) -> bool {
+        self.hover.get().is_visible()
+    }
+
+    fn update_hover(&self, f: impl FnOnce(&mut HoverDelay)) {
+        let mut hover = self.hover.get();
+        f(&mut hover);
+        self.hover.set(hover);
     }
 }
 
-impl View for Image {
-    fn render(&self, renderer: &mut dyn Renderer, theme: &Theme, x: f32, y: f32) {
-        renderer.draw_rect(x, y, self.width, self.height); // Placeholder
+impl ViewModifier for TooltipModifier {
+    fn modify_render(&self, view: &dyn View, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        let (width, height) = view.size(env);
+        self.bounds.set(Bounds::new(x, y, width, height));
+        view.render(renderer, env, x, y);
     }
 
-    fn handle_event(&mut self, _event: &Event) {
-        // No events
+    fn modify_event(&self, view: &mut dyn View, event: &Event) -> bool {
+        match event {
+            // The view gets its own hover events if it tracks them
+            Event::MouseEnter => {
+                self.update_hover(HoverDelay::enter);
+                return false;
+            }
+            Event::MouseLeave => {
+                self.update_hover(HoverDelay::leave);
+                return false;
+            }
+            Event::MouseMove { x, y } if self.bounds.get().contains(*x, *y) => {
+                self.pointer.set((*x, *y));
+                self.update_hover(HoverDelay::moved);
+            }
+            Event::Click { .. } | Event::RightClick { .. } => self.update_hover(HoverDelay::leave),
+            _ => {}
+        }
+        view.handle_event(event)
     }
-}
 
-// Placeholder for Renderer trait
-pub trait Renderer {
-    fn draw_text(&mut self, text: &str, x: f32, y: f32);
-    fn draw_rect(&mut self, x: f32, y: f32, w: f32, h: f32);
-}
+    fn modify_update(&self, view: &mut dyn View, dt: f64) {
+        let mut hover = self.hover.get();
+        hover.tick(dt);
+        self.hover.set(hover);
+        view.update(dt);
+    }
 
-// ViewModifier trait for SwiftUI-like modifiers
-pub trait ViewModifier {
-    fn modify_render(&self, view: &dyn View, renderer: &mut dyn Renderer, theme: &Theme, x: f32, y: f32);
-    fn modify_event(&self, view: &mut dyn View, event: &Event);
-}
+    // Keeps frames coming while the delay runs, so the tooltip appears on time
+    fn modify_is_animating(&self, view: &dyn View) -> bool {
+        self.hover.get().is_pending() || view.is_animating()
+    }
 
-pub struct ModifiedContent<V: View, M: ViewModifier> {
-    pub view: V,
-    pub modifier: M,
-}
+    fn modify_overlay(&self, view: &dyn View, renderer: &mut dyn Renderer, env: &Environment) {
+        view.render_overlay(renderer, env);
+        if !self.is_visible() {
+            return;
+        }
+        let (x, y) = self.pointer.get();
+        let (text_width, text_height) = measure_text(&self.text, env.font_size);
+        let (box_x, box_y) = (x, y + TOOLTIP_OFFSET);
+        let (width, height) = (text_width + 2.0 * TOOLTIP_PADDING, text_height + 2.0 * TOOLTIP_PADDING);
+        renderer.draw_rect(box_x, box_y, width, height, env.background_color);
+        renderer.draw_rect_outline(box_x, box_y, width, height, 1.0, env.secondary_color);
+        renderer.draw_text(&self.text, box_x + TOOLTIP_PADDING, box_y + TOOLTIP_PADDING, env.font_size, env.text_color);
+    }
 
-impl<V: View, M: ViewModifier> View for ModifiedContent<V, M> {
-    fn render(&self, renderer: &mut dyn Renderer, theme: &Theme, x: f32, y: f32) {
-        self.modifier.modify_render(&self.view, renderer, theme, x, y);
+    fn id(&self) -> Option<ViewId> {
+        Some(self.id)
     }
 
-    fn handle_event(&mut self, event: &Event) {
-        self.modifier.modify_event(&mut self.view, event);
+    fn bounds(&self) -> Option<Bounds> {
+        Some(self.bounds.get())
     }
 }
 
-// Common modifiers
-pub struct PaddingModifier {
-    pub padding: f32,
+impl<V: View> ModifiedContent<V, TooltipModifier> {
+    /// Seconds the pointer must rest on the view first; 0.5 by default.
+    pub fn delay(self, seconds: f64) -> Self {
+        self.modifier.hover.set(HoverDelay::new(seconds));
+        self
+    }
 }
 
-impl ViewModifier for PaddingModifier {
-    fn modify_render(&self, view: &dyn View, renderer: &mut dyn Renderer, theme: &Theme, x: f32, y: f32) {
-        view.render(renderer, theme, x + self.padding, y + self.padding);
+/// Calls `action` with the new value when a render finds that `value`
+/// changed since the previous render. The first render only records it.
+pub struct OnChangeModifier<T> {
+    pub value: Binding<T>,
+    pub action: RefCell<Box<dyn FnMut(T)>>,
+    last: RefCell<Option<T>>,
+}
+
+impl<T: PartialEq + Clone> ViewModifier for OnChangeModifier<T> {
+    fn modify_render(&self, view: &dyn View, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        let current = self.value.get();
+        let previous = self.last.replace(Some(current.clone()));
+        if previous.is_some_and(|previous| previous != current) {
+            (self.action.borrow_mut())(current);
+        }
+        view.render(renderer, env, x, y);
     }
 
-    fn modify_event(&self, view: &mut dyn View, event: &Event) {
-        view.handle_event(event);
+    fn modify_event(&self, view: &mut dyn View, event: &Event) -> bool {
+        view.handle_event(event)
     }
 }
 
-pub struct BackgroundModifier {
-    pub color: (u8, u8, u8),
-}
+/// Lets a `Window` child extend to the window's edges.
+pub struct IgnoresSafeAreaModifier;
 
-impl ViewModifier for BackgroundModifier {
-    fn modify_render(&self, view: &dyn View, renderer: &mut dyn Renderer, theme: &Theme, x: f32, y: f32) {
-        renderer.draw_rect(x, y, 100.0, 30.0); // Placeholder size
-        view.render(renderer, theme, x, y);
+impl ViewModifier for IgnoresSafeAreaModifier {
+    fn modify_render(&self, view: &dyn View, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        view.render(renderer, env, x, y);
+    }
+
+    fn modify_event(&self, view: &mut dyn View, event: &Event) -> bool {
+        view.handle_event(event)
     }
 
-    fn modify_event(&self, view: &mut dyn View, event: &Event) {
-        view.handle_event(event);
+    fn extends_to_edges(&self) -> bool {
+        true
     }
 }
 
-pub struct FrameModifier {
-    pub width: f32,
-    pub height: f32,
+// Opacity of a disabled view's content
+const DISABLED_OPACITY: f32 = 0.4;
+
+/// Dims the view and keeps input from reaching it while `disabled` holds true.
+pub struct DisabledModifier {
+    pub disabled: Binding<bool>,
+    drawn: DrawnVersion,
 }
 
-impl ViewModifier for FrameModifier {
-    fn modify_render(&self, view: &dyn View, renderer: &mut dyn Renderer, theme: &Theme, x: f32, y: f32) {
-        // Draw frame
-        renderer.draw_rect(x, y, self.width, self.height);
-        view.render(renderer, theme, x, y);
+impl ViewModifier for DisabledModifier {
+    fn modify_render(&self, view: &dyn View, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        self.drawn.record(&self.disabled);
+        let opacity = if self.disabled.get() { DISABLED_OPACITY } else { 1.0 };
+        render_faded(view, renderer, env, x, y, opacity);
+    }
+
+    fn modify_event(&self, view: &mut dyn View, event: &Event) -> bool {
+        let is_input = matches!(
+            event,
+            Event::Click { .. }
+                | Event::RightClick { .. }
+                | Event::MouseUp { .. }
+                | Event::DoubleClick { .. }
+                | Event::LongPress { .. }
+                | Event::Drag { .. }
+                | Event::MouseWheel { .. }
+                | Event::KeyDown(_)
+                | Event::ShiftKeyDown(_)
+                | Event::KeyPress(_)
+                | Event::TextInput(_)
+                | Event::Cut(_)
+                | Event::Paste(_)
+        );
+        // Dropped input isn't consumed, so views beneath still get it
+        !(is_input && self.disabled.get()) && view.handle_event(event)
+    }
+
+    fn enabled(&self) -> bool {
+        !self.disabled.get()
     }
 
-    fn modify_event(&self, view: &mut dyn View, event: &Event) {
-        view.handle_event(event);
+    // Toggling dims or restores the whole view
+    fn modify_damage(&self, view: &dyn View) -> Damage {
+        match view.layout_bounds() {
+            Some(area) => self.drawn.damage(&self.disabled, area).union(view.damage()),
+            None => Damage::Full,
+        }
     }
 }
 
-pub struct ForegroundColorModifier {
-    pub color: (u8, u8, u8),
+/// Remembers the view's measured size and reuses it until one of its
+/// inputs changes: a dependency is written, or the environment's theme,
+/// locale, scale or available size differ. Views added or removed, and
+/// state the view reads without listing it, need `invalidate`.
+pub struct LayoutCacheModifier {
+    deps: Vec<Version>,
+    cached: RefCell<Option<(LayoutKey, (f32, f32))>>,
 }
 
-impl ViewModifier for ForegroundColorModifier {
-    fn modify_render(&self, view: &dyn View, renderer: &mut dyn Renderer, theme: &Theme, x: f32, y: f32) {
-        // For now, assume it affects text, but since placeholder, just render
-        view.render(renderer, theme, x, y);
+impl LayoutCacheModifier {
+    pub fn new(deps: &[&dyn Dependency]) -> Self {
+        LayoutCacheModifier {
+            deps: deps.iter().map(|dep| dep.version_counter()).collect(),
+            cached: RefCell::new(None),
+        }
     }
 
-    fn modify_event(&self, view: &mut dyn View, event: &Event) {
-        view.handle_event(event);
+    /// Makes the next `size` call measure the view again.
+    pub fn invalidate(&self) {
+        *self.cached.borrow_mut() = None;
     }
 }
 
-pub struct CornerRadiusModifier {
-    pub radius: f32,
+// The inputs a cached size was measured with. Checked in place on every
+// lookup, so a hit costs no more than reading the versions.
+struct LayoutKey {
+    versions: Vec<u64>,
+    theme: Theme,
+    locale: Option<String>,
+    scale_factor: f32,
+    available: (Option<f32>, Option<f32>),
 }
 
-impl ViewModifier for CornerRadiusModifier {
-    fn modify_render(&self, view: &dyn View, renderer: &mut dyn Renderer, theme: &Theme, x: f32, y: f32) {
-        // Placeholder: render as rect
-        view.render(renderer, theme, x, y);
+impl LayoutKey {
+    fn new(deps: &[Version], env: &Environment) -> Self {
+        LayoutKey {
+            versions: deps.iter().map(Version::get).collect(),
+            theme: Theme::clone(&env.theme),
+            locale: env.localization().map(|localization| localization.locale.clone()),
+            scale_factor: env.scale_factor,
+            available: (env.available_width, env.available_height),
+        }
     }
 
-    fn modify_event(&self, view: &mut dyn View, event: &Event) {
-        view.handle_event(event);
+    fn matches(&self, deps: &[Version], env: &Environment) -> bool {
+        self.versions.iter().copied().eq(deps.iter().map(Version::get))
+            && self.scale_factor == env.scale_factor
+            && self.available == (env.available_width, env.available_height)
+            && self.locale.as_deref() == env.localization().map(|localization| localization.locale.as_str())
+            && self.theme == *env.theme
     }
 }
 
-pub struct ShadowModifier {
-    pub radius: f32,
-    pub color: (u8, u8, u8),
-}
+impl ViewModifier for LayoutCacheModifier {
+    fn modify_render(&self, view: &dyn View, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        // Measured through the cache, so a view drawn before anything sized
+        // it still fills the cache for the next layout pass
+        self.modify_size(view, env);
+        view.render(renderer, env, x, y);
+    }
 
-impl ViewModifier for ShadowModifier {
-    fn modify_render(&self, view: &dyn View, renderer: &mut dyn Renderer, theme: &Theme, x: f32, y: f32) {
-        // Placeholder: render shadow then view
-        view.render(renderer, theme, x, y);
+    fn modify_event(&self, view: &mut dyn View, event: &Event) -> bool {
+        view.handle_event(event)
     }
 
-    fn modify_event(&self, view: &mut dyn View, event: &Event) {
-        view.handle_event(event);
+    fn modify_size(&self, view: &dyn View, env: &Environment) -> (f32, f32) {
+        if let Some((key, size)) = &*self.cached.borrow() {
+            if key.matches(&self.deps, env) {
+                return *size;
+            }
+        }
+        let size = view.size(env);
+        *self.cached.borrow_mut() = Some((LayoutKey::new(&self.deps, env), size));
+        size
     }
 }
 
@@ -965,24 +4992,43 @@ pub trait ViewExt: View + Sized {
         }
     }
 
-    fn background(self, color: (u8, u8, u8)) -> ModifiedContent<Self, BackgroundModifier> {
+    fn background(self, color: impl Into<Color>) -> ModifiedContent<Self, BackgroundModifier> {
+        ModifiedContent {
+            view: self,
+            modifier: BackgroundModifier { color: color.into() },
+        }
+    }
+
+    /// Makes the view `fraction` of its container's available width.
+    fn relative_width(self, fraction: f32) -> ModifiedContent<Self, RelativeFrameModifier> {
+        self.relative_frame(fraction, None)
+    }
+
+    /// Makes the view `fraction` of its container's available height.
+    fn relative_height(self, fraction: f32) -> ModifiedContent<Self, RelativeFrameModifier> {
+        self.relative_frame(None, fraction)
+    }
+
+    /// Sizes the view as fractions of its container's available room.
+    fn relative_frame(self, width: impl Into<Option<f32>>, height: impl Into<Option<f32>>) -> ModifiedContent<Self, RelativeFrameModifier> {
         ModifiedContent {
             view: self,
-            modifier: BackgroundModifier { color },
+            modifier: RelativeFrameModifier { width: width.into(), height: height.into() },
         }
     }
 
-    fn frame(self, width: f32, height: f32) -> ModifiedContent<Self, FrameModifier> {
+    /// `.frame(120.0, None)` fixes the width and keeps the view's height.
+    fn frame(self, width: impl Into<Option<f32>>, height: impl Into<Option<f32>>) -> ModifiedContent<Self, FrameModifier> {
         ModifiedContent {
             view: self,
-            modifier: FrameModifier { width, height },
+            modifier: FrameModifier { width: width.into(), height: height.into(), clip: false },
         }
     }
 
-    fn foreground_color(self, color: (u8, u8, u8)) -> ModifiedContent<Self, ForegroundColorModifier> {
+    fn foreground_color(self, color: impl Into<Color>) -> ModifiedContent<Self, ForegroundColorModifier> {
         ModifiedContent {
             view: self,
-            modifier: ForegroundColorModifier { color },
+            modifier: ForegroundColorModifier { color: color.into() },
         }
     }
 
@@ -993,21 +5039,216 @@ pub trait ViewExt: View + Sized {
         }
     }
 
-    fn shadow(self, radius: f32, color: (u8, u8, u8)) -> ModifiedContent<Self, ShadowModifier> {
+    /// Hard-edged shadow offset by `(dx, dy)`; pass a translucent color,
+    /// e.g. `Color::rgba(0, 0, 0, 96)`, and chain `.blur()` to soften it.
+    fn shadow(self, dx: f32, dy: f32, color: impl Into<Color>) -> ModifiedContent<Self, ShadowModifier> {
+        ModifiedContent {
+            view: self,
+            modifier: ShadowModifier { offset: (dx, dy), blur: 0.0, color: color.into() },
+        }
+    }
+
+    /// Runs `f` the first frame this view is rendered, and again each time
+    /// it comes back after being removed.
+    fn on_appear<F: FnMut() + 'static>(self, f: F) -> ModifiedContent<Self, LifecycleModifier> {
+        let callback: LifecycleCallback = Rc::new(RefCell::new(f));
+        ModifiedContent {
+            view: self,
+            modifier: LifecycleModifier {
+                hooks: LifecycleHooks { id: ViewId::next(), on_appear: Some(callback), on_disappear: None },
+            },
+        }
+    }
+
+    /// Runs `f` when this view leaves the rendered tree.
+    fn on_disappear<F: FnMut() + 'static>(self, f: F) -> ModifiedContent<Self, LifecycleModifier> {
+        let callback: LifecycleCallback = Rc::new(RefCell::new(f));
+        ModifiedContent {
+            view: self,
+            modifier: LifecycleModifier {
+                hooks: LifecycleHooks { id: ViewId::next(), on_appear: None, on_disappear: Some(callback) },
+            },
+        }
+    }
+
+    fn on_double_click<F: FnMut() + 'static>(self, f: F) -> ModifiedContent<Self, GestureModifier> {
+        self.on_gesture(Gesture::DoubleClick, f)
+    }
+
+    fn on_long_press<F: FnMut() + 'static>(self, f: F) -> ModifiedContent<Self, GestureModifier> {
+        self.on_gesture(Gesture::LongPress, f)
+    }
+
+    /// Makes `value` visible to this view and its descendants via
+    /// `env.get(key)`.
+    fn environment<T: 'static>(self, key: impl Into<String>, value: T) -> ModifiedContent<Self, EnvironmentModifier> {
+        ModifiedContent {
+            view: self,
+            modifier: EnvironmentModifier { key: key.into(), value: Rc::new(value) },
+        }
+    }
+
+    fn on_hover<F: FnMut(bool) + 'static>(self, f: F) -> ModifiedContent<Self, HoverModifier> {
+        ModifiedContent {
+            view: self,
+            modifier: HoverModifier {
+                action: RefCell::new(Box::new(f)),
+                id: ViewId::next(),
+                bounds: Cell::new(Bounds::default()),
+            },
+        }
+    }
+
+    /// Grow with weight `weight` when the enclosing stack has spare room.
+    fn flex(self, weight: f32) -> ModifiedContent<Self, FlexModifier> {
+        ModifiedContent {
+            view: self,
+            modifier: FlexModifier { weight },
+        }
+    }
+
+    /// Opens a menu of `items` at the pointer on right-click. Submenus of
+    /// context menu items are not shown.
+    fn context_menu(self, items: Vec<MenuItem>) -> ModifiedContent<Self, ContextMenuModifier> {
+        ModifiedContent {
+            view: self,
+            modifier: ContextMenuModifier {
+                items: RefCell::new(items),
+                id: ViewId::next(),
+                bounds: Cell::new(Bounds::default()),
+                position: Cell::new(None),
+                font_size: Cell::new(Environment::default().font_size),
+            },
+        }
+    }
+
+    /// Places this `Window` child at the window's edges, ignoring the
+    /// safe area insets and the title bar.
+    fn ignores_safe_area(self) -> ModifiedContent<Self, IgnoresSafeAreaModifier> {
+        ModifiedContent {
+            view: self,
+            modifier: IgnoresSafeAreaModifier,
+        }
+    }
+
+    /// Runs `f` with the new value whenever `value` differs from what the
+    /// previous render saw, like SwiftUI's `.onChange(of:)`.
+    fn on_change_of<T, F>(self, value: Binding<T>, f: F) -> ModifiedContent<Self, OnChangeModifier<T>>
+    where
+        T: PartialEq + Clone,
+        F: FnMut(T) + 'static,
+    {
+        ModifiedContent {
+            view: self,
+            modifier: OnChangeModifier { value, action: RefCell::new(Box::new(f)), last: RefCell::new(None) },
+        }
+    }
+
+    /// While `disabled` is true the view is drawn dimmed and ignores clicks
+    /// and keys; false leaves it unchanged. Pass a `Binding<bool>` to
+    /// disable it while some state holds.
+    fn disabled(self, disabled: impl Into<Binding<bool>>) -> ModifiedContent<Self, DisabledModifier> {
+        ModifiedContent {
+            view: self,
+            modifier: DisabledModifier { disabled: disabled.into(), drawn: DrawnVersion::new() },
+        }
+    }
+
+    /// Shows `text` by the pointer after it rests on the view for a moment.
+    fn tooltip(self, text: impl Into<String>) -> ModifiedContent<Self, TooltipModifier> {
+        ModifiedContent {
+            view: self,
+            modifier: TooltipModifier {
+                text: text.into(),
+                hover: Cell::new(HoverDelay::new(TOOLTIP_DELAY)),
+                id: ViewId::next(),
+                bounds: Cell::new(Bounds::default()),
+                pointer: Cell::new((0.0, 0.0)),
+            },
+        }
+    }
+
+    /// Measures the view once and reuses the size until one of `deps` is
+    /// written or the environment changes.
+    fn cached_layout(self, deps: &[&dyn Dependency]) -> ModifiedContent<Self, LayoutCacheModifier> {
+        ModifiedContent {
+            view: self,
+            modifier: LayoutCacheModifier::new(deps),
+        }
+    }
+
+    fn accessibility_label(self, label: impl Into<String>) -> ModifiedContent<Self, AccessibilityModifier> {
         ModifiedContent {
             view: self,
-            modifier: ShadowModifier { radius, color },
+            modifier: AccessibilityModifier { role: None, label: Some(label.into()) },
+        }
+    }
+
+    fn accessibility_role(self, role: Role) -> ModifiedContent<Self, AccessibilityModifier> {
+        ModifiedContent {
+            view: self,
+            modifier: AccessibilityModifier { role: Some(role), label: None },
+        }
+    }
+
+    fn on_gesture<F: FnMut() + 'static>(self, gesture: Gesture, f: F) -> ModifiedContent<Self, GestureModifier> {
+        ModifiedContent {
+            view: self,
+            modifier: GestureModifier {
+                gesture,
+                action: Rc::new(RefCell::new(f)),
+                bounds: Cell::new(Bounds::default()),
+            },
         }
     }
 }
 
 impl<V: View> ViewExt for V {}
 
+/// Receives the text a view copies while handling `Copy` or `Cut`.
+#[derive(Clone, Default)]
+pub struct Clipboard(Rc<RefCell<Option<String>>>);
+
+impl Clipboard {
+    pub fn set_text(&self, text: impl Into<String>) {
+        *self.0.borrow_mut() = Some(text.into());
+    }
+
+    pub fn take(&self) -> Option<String> {
+        self.0.borrow_mut().take()
+    }
+}
+
 // Placeholder for Event
 pub enum Event {
     Click { x: f32, y: f32 },
+    /// Secondary-button press, e.g. to open a context menu.
+    RightClick { x: f32, y: f32 },
+    MouseUp { x: f32, y: f32 },
     MouseMove { x: f32, y: f32 },
-    KeyDown(sdl2::keyboard::Keycode),
+    KeyDown(Key),
+    /// Navigation key pressed with Shift held, extending the selection.
+    ShiftKeyDown(Key),
     KeyPress(char),
+    TextInput(String),
+    /// Wheel motion at the pointer position; positive `dy` scrolls up.
+    MouseWheel { x: f32, y: f32, dx: f32, dy: f32 },
+    /// Pointer movement since the previous drag event while the button is held.
     Drag { dx: f32, dy: f32 },
+    /// The OS window was resized to the given size in pixels.
+    Resize { width: u32, height: u32 },
+    /// Sent to a view when the pointer moves onto its bounds.
+    MouseEnter,
+    /// Sent to a view when the pointer moves off its bounds.
+    MouseLeave,
+    /// Ctrl+C: the focused view puts its selection in the clipboard.
+    Copy(Clipboard),
+    /// Ctrl+X: like `Copy`, then deletes the selection.
+    Cut(Clipboard),
+    /// Ctrl+V with the system clipboard's text.
+    Paste(String),
+    /// Second click close in time and position to the previous one.
+    DoubleClick { x: f32, y: f32 },
+    /// The button was held without moving for the long-press duration.
+    LongPress { x: f32, y: f32 },
 }
\ No newline at end of file
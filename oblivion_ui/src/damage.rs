@@ -0,0 +1,158 @@
+// Dirty-rectangle tracking, so a redraw only repaints what changed
+
+use std::cell::Cell;
+
+use crate::components::{Bounds, View};
+use crate::environment::Environment;
+use crate::state::Dependency;
+use crate::themes::Theme;
+
+/// Rectangles that need repainting, kept merged so none overlap.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DirtyRegion {
+    rects: Vec<Bounds>,
+}
+
+impl DirtyRegion {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `rect`, merging it with every rectangle it overlaps. Empty
+    /// rectangles are ignored.
+    pub fn add(&mut self, rect: Bounds) {
+        if rect.is_empty() {
+            return;
+        }
+        let mut merged = rect;
+        // A merge can grow the rectangle into ones it missed before
+        loop {
+            let before = self.rects.len();
+            self.rects.retain(|other| {
+                if merged.intersects(other) {
+                    merged = merged.union(other);
+                    false
+                } else {
+                    true
+                }
+            });
+            if self.rects.len() == before {
+                break;
+            }
+        }
+        self.rects.push(merged);
+    }
+
+    pub fn rects(&self) -> &[Bounds] {
+        &self.rects
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rects.is_empty()
+    }
+
+    /// Smallest rectangle covering the whole region.
+    pub fn bounds(&self) -> Option<Bounds> {
+        self.rects.iter().copied().reduce(|a, b| a.union(&b))
+    }
+
+    pub fn clear(&mut self) {
+        self.rects.clear();
+    }
+}
+
+/// What a frame has to repaint.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Damage {
+    /// Nothing changed; the last frame can stay on screen.
+    #[default]
+    None,
+    Region(Bounds),
+    Full,
+}
+
+impl Damage {
+    /// Both at once: a full repaint wins, and regions merge.
+    pub fn union(self, other: Damage) -> Damage {
+        match (self, other) {
+            (Damage::Full, _) | (_, Damage::Full) => Damage::Full,
+            (Damage::None, damage) | (damage, Damage::None) => damage,
+            (Damage::Region(a), Damage::Region(b)) => Damage::Region(a.union(&b)),
+        }
+    }
+}
+
+/// What the children of `view` report changed, for containers that draw
+/// nothing that changes on its own.
+pub fn children_damage(view: &dyn View) -> Damage {
+    view.children().into_iter().fold(Damage::None, |damage, child| damage.union(child.damage()))
+}
+
+/// The version of a binding a view last drew, so the view can report its
+/// area dirty once the binding has been written since.
+#[derive(Debug, Default)]
+pub struct DrawnVersion(Cell<Option<u64>>);
+
+impl DrawnVersion {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Call while drawing the value of `dependency`.
+    pub fn record(&self, dependency: &dyn Dependency) {
+        self.0.set(Some(dependency.version_counter().get()));
+    }
+
+    /// `area` if `dependency` changed since it was drawn; everything if it
+    /// never was.
+    pub fn damage(&self, dependency: &dyn Dependency, area: Bounds) -> Damage {
+        match self.0.get() {
+            None => Damage::Full,
+            Some(version) if version == dependency.version_counter().get() => Damage::None,
+            Some(_) => Damage::Region(area),
+        }
+    }
+}
+
+/// Collects what the next frame has to repaint: the areas views report
+/// changed, plus what the host saw happen, such as input or hover changes.
+/// A new theme or scale factor repaints everything.
+#[derive(Default)]
+pub struct DamageTracker {
+    // Theme and scale factor of the last frame
+    drawn: Option<(Theme, f32)>,
+    pending: Damage,
+}
+
+impl DamageTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Makes the next frame repaint everything, e.g. after a resize or
+    /// when the window's contents were lost.
+    pub fn invalidate(&mut self) {
+        self.pending = Damage::Full;
+    }
+
+    /// Adds `area` to what the next frame repaints.
+    pub fn add(&mut self, area: Bounds) {
+        self.pending = self.pending.union(Damage::Region(area));
+    }
+
+    /// What a frame of `root` under `env` has to repaint, clearing what
+    /// was added for it.
+    pub fn take(&mut self, root: &dyn View, env: &Environment) -> Damage {
+        let frame = (Theme::clone(&env.theme), env.scale_factor);
+        let changed = self.drawn.as_ref() != Some(&frame);
+        self.drawn = Some(frame);
+        let pending = std::mem::take(&mut self.pending);
+        if changed {
+            return Damage::Full;
+        }
+        match pending.union(root.damage()) {
+            Damage::Region(region) if region.is_empty() => Damage::None,
+            damage => damage,
+        }
+    }
+}
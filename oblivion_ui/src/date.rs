@@ -0,0 +1,94 @@
+// Calendar dates for date pickers, without a time zone or time of day
+
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: i32,
+    /// 1 to 12.
+    pub month: u32,
+    /// 1 to the length of the month.
+    pub day: u32,
+}
+
+/// Part of a date a picker steps on its own.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DateField {
+    Year,
+    Month,
+    Day,
+}
+
+impl DateField {
+    pub const ALL: [DateField; 3] = [DateField::Year, DateField::Month, DateField::Day];
+}
+
+pub fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl Date {
+    /// The date, or `None` if the month or day doesn't exist.
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Self> {
+        let valid = (1..=12).contains(&month) && (1..=days_in_month(year, month)).contains(&day);
+        valid.then_some(Date { year, month, day })
+    }
+
+    /// Moves `field` by `delta`. Months past December or before January
+    /// roll into the next or previous year, and days roll into the next or
+    /// previous month. When a year or month change leaves the day past the
+    /// end of the month (Jan 31 to Feb), the day is clamped to the last one.
+    pub fn step(self, field: DateField, delta: i32) -> Self {
+        match field {
+            DateField::Year => Self::clamped(self.year.saturating_add(delta), self.month, self.day),
+            DateField::Month => {
+                let months = self.year as i64 * 12 + (self.month as i64 - 1) + delta as i64;
+                Self::clamped(months.div_euclid(12) as i32, months.rem_euclid(12) as u32 + 1, self.day)
+            }
+            DateField::Day => {
+                let mut date = self;
+                for _ in 0..delta.unsigned_abs() {
+                    date = if delta > 0 { date.next_day() } else { date.previous_day() };
+                }
+                date
+            }
+        }
+    }
+
+    fn clamped(year: i32, month: u32, day: u32) -> Self {
+        Date { year, month, day: day.min(days_in_month(year, month)) }
+    }
+
+    fn next_day(self) -> Self {
+        if self.day < days_in_month(self.year, self.month) {
+            Date { day: self.day + 1, ..self }
+        } else {
+            Date { day: 1, ..self.step(DateField::Month, 1) }
+        }
+    }
+
+    fn previous_day(self) -> Self {
+        if self.day > 1 {
+            Date { day: self.day - 1, ..self }
+        } else {
+            let month = self.step(DateField::Month, -1);
+            Date { day: days_in_month(month.year, month.month), ..month }
+        }
+    }
+}
+
+/// ISO 8601: `2024-02-29`.
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
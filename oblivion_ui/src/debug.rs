@@ -0,0 +1,88 @@
+// Layout debugging: where each view was drawn, as outlines or a text tree
+
+use std::fmt;
+
+use crate::color::Color;
+use crate::components::{Bounds, Renderer, View};
+use crate::environment::Environment;
+
+/// Colour of the outlines drawn by `draw_layout_outlines`.
+pub const OUTLINE_COLOR: Color = Color::rgb(255, 0, 255);
+
+/// One view of a layout snapshot.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayoutNode {
+    /// The view's type, without module paths.
+    pub name: String,
+    /// Where the view was last drawn. For views that don't record it, the
+    /// area their children cover, or their size at the parent's origin.
+    pub bounds: Bounds,
+    /// The size the view asks its container for.
+    pub size: (f32, f32),
+    pub children: Vec<LayoutNode>,
+}
+
+impl LayoutNode {
+    fn write(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        let b = self.bounds;
+        writeln!(f, "{:indent$}{} ({}, {}, {}, {})", "", self.name, b.x, b.y, b.width, b.height, indent = depth * 2)?;
+        for child in &self.children {
+            child.write(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+/// One line per view, indented by depth: `VStack (0, 0, 120, 40)`.
+impl fmt::Display for LayoutNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, 0)
+    }
+}
+
+/// Describes the tree under `root` as laid out by the last render.
+pub fn layout_snapshot(root: &dyn View, env: &Environment) -> LayoutNode {
+    snapshot_at(root, env, (0.0, 0.0))
+}
+
+// Views that don't record their frame are placed from what's around them
+fn snapshot_at(view: &dyn View, env: &Environment, origin: (f32, f32)) -> LayoutNode {
+    let recorded = view.layout_bounds();
+    let child_origin = recorded.map_or(origin, |b| (b.x, b.y));
+    let children: Vec<LayoutNode> = view.children().into_iter().map(|child| snapshot_at(child, env, child_origin)).collect();
+    let size = view.size(env);
+    let covered = children.iter().map(|child| child.bounds).reduce(|a, b| a.union(&b));
+    let bounds = recorded.or(covered).unwrap_or(Bounds::new(origin.0, origin.1, size.0, size.1));
+    LayoutNode { name: short_type_name(view.type_name()), bounds, size, children }
+}
+
+/// Outlines every view that recorded where it was drawn.
+pub fn draw_layout_outlines(renderer: &mut dyn Renderer, root: &dyn View) {
+    if let Some(b) = root.layout_bounds().filter(|b| !b.is_empty()) {
+        renderer.draw_rect_outline(b.x, b.y, b.width, b.height, 1.0, OUTLINE_COLOR);
+    }
+    for child in root.children() {
+        draw_layout_outlines(renderer, child);
+    }
+}
+
+// `a::b::Outer<a::Inner>` becomes `Outer<Inner>`
+fn short_type_name(name: &str) -> String {
+    let mut short = String::new();
+    let mut segment = String::new();
+    let mut chars = name.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == ':' && chars.peek() == Some(&':') {
+            chars.next();
+            segment.clear();
+        } else if c.is_alphanumeric() || c == '_' {
+            segment.push(c);
+        } else {
+            short.push_str(&segment);
+            segment.clear();
+            short.push(c);
+        }
+    }
+    short.push_str(&segment);
+    short
+}
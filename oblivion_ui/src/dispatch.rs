@@ -0,0 +1,44 @@
+// Elm-style message queue for updating UI state from other threads
+
+use std::sync::mpsc::{self, Receiver, Sender};
+
+/// Queue of messages for the UI thread. Background threads send through
+/// `sender()`; the engine drains the queue every frame (see
+/// `SDLEngine::add_dispatcher`) and applies each message to the model.
+pub struct Dispatcher<Msg> {
+    sender: Sender<Msg>,
+    receiver: Receiver<Msg>,
+}
+
+impl<Msg> Dispatcher<Msg> {
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Dispatcher { sender, receiver }
+    }
+
+    pub fn sender(&self) -> Sender<Msg> {
+        self.sender.clone()
+    }
+
+    /// Applies every queued message to `model` in the order sent; returns
+    /// how many there were.
+    pub fn drain<Model>(&self, model: &mut Model, mut update: impl FnMut(&mut Model, Msg)) -> usize {
+        let mut count = 0;
+        for msg in self.receiver.try_iter() {
+            update(model, msg);
+            count += 1;
+        }
+        count
+    }
+
+    /// Takes everything queued so far without applying it.
+    pub fn pending(&self) -> Vec<Msg> {
+        self.receiver.try_iter().collect()
+    }
+}
+
+impl<Msg> Default for Dispatcher<Msg> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
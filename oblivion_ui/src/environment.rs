@@ -0,0 +1,122 @@
+// Ambient values handed down the view tree while rendering
+
+use crate::localization::{Localization, LOCALIZATION_KEY};
+use crate::themes::Theme;
+use std::any::Any;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::rc::Rc;
+
+/// What a view sees while rendering: the theme, the display scale factor and
+/// any values set with `.environment(key, value)` on an ancestor.
+///
+/// Derefs to the theme, so `env.primary_color` works directly. What a
+/// container hands its children borrows its own environment and overrides
+/// only what changed, so nothing is copied on the way down the tree.
+#[derive(Clone)]
+pub struct Environment<'a> {
+    pub theme: Cow<'a, Theme>,
+    pub scale_factor: f32,
+    /// Room the enclosing container offers its children, for sizes given as
+    /// a fraction of the parent. `None` where the container doesn't know it.
+    pub available_width: Option<f32>,
+    pub available_height: Option<f32>,
+    values: HashMap<String, Rc<dyn Any>>,
+    // A value this layer borrows from the modifier that set it
+    borrowed: Option<(&'a str, &'a (dyn Any + 'static))>,
+    // Looked up for values this layer doesn't set
+    parent: Option<&'a Environment<'a>>,
+}
+
+impl<'a> Environment<'a> {
+    pub fn new(theme: Theme) -> Self {
+        Environment {
+            theme: Cow::Owned(theme),
+            scale_factor: 1.0,
+            available_width: None,
+            available_height: None,
+            values: HashMap::new(),
+            borrowed: None,
+            parent: None,
+        }
+    }
+
+    pub fn with_scale_factor(mut self, scale_factor: f32) -> Self {
+        self.scale_factor = scale_factor;
+        self
+    }
+
+    // A layer over this environment that changes nothing yet
+    fn layer(&self) -> Environment<'_> {
+        Environment {
+            theme: Cow::Borrowed(&self.theme),
+            scale_factor: self.scale_factor,
+            available_width: self.available_width,
+            available_height: self.available_height,
+            values: HashMap::new(),
+            borrowed: None,
+            parent: Some(self),
+        }
+    }
+
+    /// This environment with the given room offered to children; `None`
+    /// keeps the enclosing container's.
+    pub fn with_available_size(&self, width: Option<f32>, height: Option<f32>) -> Environment<'_> {
+        let mut env = self.layer();
+        env.available_width = width.or(self.available_width);
+        env.available_height = height.or(self.available_height);
+        env
+    }
+
+    /// This environment with `value` stored under `key`, borrowing both.
+    pub(crate) fn with_value<'e>(&'e self, key: &'e str, value: &'e (dyn Any + 'static)) -> Environment<'e> {
+        let mut env = self.layer();
+        env.borrowed = Some((key, value));
+        env
+    }
+
+    /// The value stored under `key`, if there is one of type `T`.
+    pub fn get<T: 'static>(&self, key: &str) -> Option<&T> {
+        if let Some(value) = self.values.get(key) {
+            return value.downcast_ref();
+        }
+        match self.borrowed {
+            Some((borrowed_key, value)) if borrowed_key == key => value.downcast_ref(),
+            _ => self.parent.and_then(|parent| parent.get(key)),
+        }
+    }
+
+    pub fn set<T: 'static>(&mut self, key: impl Into<String>, value: T) {
+        self.values.insert(key.into(), Rc::new(value));
+    }
+
+    /// Strings for `Text::localized`, if a locale has been set.
+    pub fn localization(&self) -> Option<&Localization> {
+        self.get(LOCALIZATION_KEY)
+    }
+
+    pub fn set_localization(&mut self, localization: Localization) {
+        self.set(LOCALIZATION_KEY, localization);
+    }
+}
+
+impl Default for Environment<'_> {
+    fn default() -> Self {
+        Environment::new(Theme::default())
+    }
+}
+
+impl From<Theme> for Environment<'_> {
+    fn from(theme: Theme) -> Self {
+        Environment::new(theme)
+    }
+}
+
+impl Deref for Environment<'_> {
+    type Target = Theme;
+
+    fn deref(&self) -> &Theme {
+        &self.theme
+    }
+}
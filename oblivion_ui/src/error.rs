@@ -8,10 +8,12 @@ pub enum UiError {
     FontError,
     #[error("Rendering error: {0}")]
     RenderError(String),
+    #[error("Theme error: {0}")]
+    ThemeError(String),
+    #[error("Parse error: {0}")]
+    ParseError(String),
+    #[error("I/O error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Image error: {0}")]
+    ImageError(#[from] image::ImageError),
 }
-
-impl From<String> for UiError {
-    fn from(s: String) -> Self {
-        UiError::SdlError(s)
-    }
-}
\ No newline at end of file
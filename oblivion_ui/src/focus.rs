@@ -0,0 +1,382 @@
+// Keyboard focus tracking for the view tree
+
+use crate::components::{Bounds, Event, View, ViewId};
+use crate::key::Key;
+
+#[derive(Default)]
+pub struct FocusManager {
+    focused: Option<ViewId>,
+    // Views visited by Tab, in order
+    tab_order: Vec<ViewId>,
+}
+
+impl FocusManager {
+    pub fn new() -> Self {
+        FocusManager { focused: None, tab_order: Vec::new() }
+    }
+
+    pub fn focused(&self) -> Option<ViewId> {
+        self.focused
+    }
+
+    /// Appends a view to the tab order.
+    pub fn register(&mut self, id: ViewId) {
+        if !self.tab_order.contains(&id) {
+            self.tab_order.push(id);
+        }
+    }
+
+    /// Rebuilds the tab order from the focusable views in tree order.
+    pub fn collect_tab_order(&mut self, root: &dyn View) {
+        self.tab_order.clear();
+        collect_focusable(root, &mut self.tab_order);
+    }
+
+    pub fn tab_order(&self) -> &[ViewId] {
+        &self.tab_order
+    }
+
+    /// Focuses the view after the current one, wrapping at the end.
+    pub fn focus_next(&mut self, root: &mut dyn View) {
+        self.step(root, 1);
+    }
+
+    /// Focuses the view before the current one, wrapping at the start.
+    pub fn focus_previous(&mut self, root: &mut dyn View) {
+        self.step(root, -1);
+    }
+
+    fn step(&mut self, root: &mut dyn View, delta: isize) {
+        let len = self.tab_order.len() as isize;
+        if len == 0 {
+            return;
+        }
+        let current = self.focused.and_then(|id| self.tab_order.iter().position(|&t| t == id));
+        let next = match current {
+            Some(index) => (index as isize + delta).rem_euclid(len),
+            None if delta > 0 => 0,
+            None => len - 1,
+        };
+        self.set_focus(root, Some(self.tab_order[next as usize]));
+    }
+
+    /// Moves focus to `id` (or clears it), notifying the old and new views.
+    pub fn set_focus(&mut self, root: &mut dyn View, id: Option<ViewId>) {
+        if self.focused == id {
+            return;
+        }
+        if let Some(old) = self.focused {
+            if let Some(view) = find_view_mut(root, old) {
+                view.set_focused(false);
+            }
+        }
+        self.focused = id;
+        if let Some(new) = id {
+            if let Some(view) = find_view_mut(root, new) {
+                view.set_focused(true);
+            }
+        }
+    }
+
+    /// Focuses the topmost focusable view under the point, or clears focus.
+    pub fn focus_at(&mut self, root: &mut dyn View, x: f32, y: f32) {
+        let target = focusable_at(root, x, y);
+        self.set_focus(root, target);
+    }
+
+    /// Delivers an event to the tree. An open modal view gets every event
+    /// except resizes, which the whole tree needs to lay out again;
+    /// otherwise clicks on an open overlay go only to its owner. Other
+    /// pointer presses go to the views under the pointer, frontmost first,
+    /// until one consumes them, and are broadcast only when nothing is hit.
+    /// Keyboard events go only to the focused view while one exists;
+    /// everything else is broadcast from the root.
+    pub fn route(&mut self, root: &mut dyn View, event: &Event) {
+        // A modal view swallows everything until it closes
+        if let Some(id) = modal_view(root).filter(|_| !matches!(event, Event::Resize { .. })) {
+            if let Some(view) = find_view_mut(root, id) {
+                view.handle_event(event);
+            }
+            return;
+        }
+        if let Event::Click { x, y } = event {
+            // Whatever ends up consuming the click, popups elsewhere close
+            dismiss_overlays_outside(root, *x, *y);
+            // An open popup takes the click before anything beneath it
+            if let Some(id) = overlay_at(root, *x, *y) {
+                if let Some(view) = find_view_mut(root, id) {
+                    view.handle_event(event);
+                }
+                return;
+            }
+            self.focus_at(root, *x, *y);
+        }
+        // With a popup open elsewhere the click must reach its owner to close it
+        if let Some((x, y)) = pointer_position(event).filter(|_| !has_overlay(root)) {
+            let hits = hit_test(root, x, y);
+            if !hits.is_empty() {
+                deliver_to_hits(root, &hits, event);
+                return;
+            }
+        }
+        if let Event::KeyDown(Key::Tab) = event {
+            self.focus_next(root);
+            return;
+        }
+        let is_key = matches!(event, Event::KeyDown(_)
+                | Event::ShiftKeyDown(_)
+                | Event::KeyPress(_)
+                | Event::TextInput(_)
+                | Event::Copy(_)
+                | Event::Cut(_)
+                | Event::Paste(_));
+        match self.focused {
+            Some(id) if is_key => {
+                if let Some(view) = find_enabled_view_mut(root, id) {
+                    view.handle_event(event);
+                }
+            }
+            _ => {
+                root.handle_event(event);
+            }
+        }
+    }
+}
+
+/// Tracks which views are under the pointer and sends them
+/// `MouseEnter`/`MouseLeave` as it crosses their bounds.
+#[derive(Default)]
+pub struct HoverTracker {
+    hovered: Vec<ViewId>,
+}
+
+impl HoverTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn hovered(&self) -> &[ViewId] {
+        &self.hovered
+    }
+
+    /// Re-hit-tests after the pointer moved; returns whether anything changed.
+    pub fn update(&mut self, root: &mut dyn View, x: f32, y: f32) -> bool {
+        let mut under = Vec::new();
+        collect_hovered(root, x, y, &mut under);
+        self.transition(root, under)
+    }
+
+    /// Leaves every hovered view, e.g. when the pointer exits the window.
+    pub fn clear(&mut self, root: &mut dyn View) -> bool {
+        self.transition(root, Vec::new())
+    }
+
+    fn transition(&mut self, root: &mut dyn View, under: Vec<ViewId>) -> bool {
+        let left: Vec<ViewId> = self.hovered.iter().filter(|id| !under.contains(id)).copied().collect();
+        let entered: Vec<ViewId> = under.iter().filter(|id| !self.hovered.contains(id)).copied().collect();
+        for id in &left {
+            if let Some(view) = find_view_mut(root, *id) {
+                view.handle_event(&Event::MouseLeave);
+            }
+        }
+        for id in &entered {
+            if let Some(view) = find_view_mut(root, *id) {
+                view.handle_event(&Event::MouseEnter);
+            }
+        }
+        self.hovered = under;
+        !left.is_empty() || !entered.is_empty()
+    }
+}
+
+// Where a pointer press happened, for events that are hit-tested
+fn pointer_position(event: &Event) -> Option<(f32, f32)> {
+    match event {
+        Event::Click { x, y }
+        | Event::RightClick { x, y }
+        | Event::DoubleClick { x, y }
+        | Event::LongPress { x, y }
+        | Event::MouseWheel { x, y, .. } => Some((*x, *y)),
+        _ => None,
+    }
+}
+
+/// Views under the point, frontmost first, as index paths from `root`
+/// through `children()`. Open overlays come first since they're drawn over
+/// everything; the rest follow in reverse draw order, so children come
+/// before their parents and later siblings before earlier ones. Disabled
+/// subtrees are skipped.
+pub fn hit_test(root: &dyn View, x: f32, y: f32) -> Vec<Vec<usize>> {
+    let (mut overlays, mut hits) = (Vec::new(), Vec::new());
+    collect_hits(root, x, y, &mut Vec::new(), &mut overlays, &mut hits);
+    overlays.reverse();
+    hits.reverse();
+    for path in hits {
+        if !overlays.contains(&path) {
+            overlays.push(path);
+        }
+    }
+    overlays
+}
+
+fn collect_hits(
+    view: &dyn View,
+    x: f32,
+    y: f32,
+    path: &mut Vec<usize>,
+    overlays: &mut Vec<Vec<usize>>,
+    hits: &mut Vec<Vec<usize>>,
+) {
+    if !view.is_enabled() {
+        return;
+    }
+    if view.overlay_bounds().is_some_and(|b| b.contains(x, y)) {
+        overlays.push(path.clone());
+    }
+    if view.bounds().is_some_and(|b| b.contains(x, y)) {
+        hits.push(path.clone());
+    }
+    for (index, child) in view.children().into_iter().enumerate() {
+        path.push(index);
+        collect_hits(child, x, y, path, overlays, hits);
+        path.pop();
+    }
+}
+
+// Offers the event to each hit in turn until one consumes it. A hit view
+// passes events on to its own subtree, so only the outermost hit on each
+// path gets it; that way no view sees the press twice.
+fn deliver_to_hits(root: &mut dyn View, hits: &[Vec<usize>], event: &Event) {
+    let mut delivered: Vec<&Vec<usize>> = Vec::new();
+    for path in hits {
+        let ancestors = hits.iter().filter(|other| path.starts_with(other));
+        let Some(outermost) = ancestors.min_by_key(|other| other.len()) else {
+            continue;
+        };
+        if delivered.contains(&outermost) {
+            continue;
+        }
+        delivered.push(outermost);
+        if find_path_mut(root, outermost).is_some_and(|view| view.handle_event(event)) {
+            return;
+        }
+    }
+}
+
+/// The view at an index path returned by `hit_test`.
+pub fn find_path_mut<'a>(view: &'a mut dyn View, path: &[usize]) -> Option<&'a mut dyn View> {
+    match path.split_first() {
+        None => Some(view),
+        Some((&index, rest)) => {
+            let child = view.children_mut().into_iter().nth(index)?;
+            find_path_mut(child, rest)
+        }
+    }
+}
+
+// Closes every open overlay not under the point. Views under it are left
+// alone, since clicking a popup's owner (a Picker's header) toggles it.
+fn dismiss_overlays_outside(view: &mut dyn View, x: f32, y: f32) {
+    let inside = |bounds: Option<Bounds>| bounds.is_some_and(|b| b.contains(x, y));
+    if view.overlay_bounds().is_some() && !inside(view.overlay_bounds()) && !inside(view.bounds()) {
+        view.dismiss_overlay();
+    }
+    for child in view.children_mut() {
+        dismiss_overlays_outside(child, x, y);
+    }
+}
+
+fn has_overlay(view: &dyn View) -> bool {
+    view.overlay_bounds().is_some() || view.children().into_iter().any(has_overlay)
+}
+
+fn collect_hovered(view: &dyn View, x: f32, y: f32, hovered: &mut Vec<ViewId>) {
+    if let (Some(id), Some(bounds)) = (view.id(), view.bounds()) {
+        if bounds.contains(x, y) {
+            hovered.push(id);
+        }
+    }
+    for child in view.children() {
+        collect_hovered(child, x, y, hovered);
+    }
+}
+
+pub fn find_view(view: &dyn View, id: ViewId) -> Option<&dyn View> {
+    if view.id() == Some(id) {
+        return Some(view);
+    }
+    view.children().into_iter().find_map(|child| find_view(child, id))
+}
+
+pub fn find_view_mut(view: &mut dyn View, id: ViewId) -> Option<&mut dyn View> {
+    if view.id() == Some(id) {
+        return Some(view);
+    }
+    for child in view.children_mut() {
+        if let Some(found) = find_view_mut(child, id) {
+            return Some(found);
+        }
+    }
+    None
+}
+
+fn modal_view(view: &dyn View) -> Option<ViewId> {
+    if view.is_modal() {
+        if let Some(id) = view.id() {
+            return Some(id);
+        }
+    }
+    view.children().into_iter().rev().find_map(modal_view)
+}
+
+fn overlay_at(view: &dyn View, x: f32, y: f32) -> Option<ViewId> {
+    if view.overlay_bounds().is_some_and(|b| b.contains(x, y)) {
+        if let Some(id) = view.id() {
+            return Some(id);
+        }
+    }
+    view.children().into_iter().rev().find_map(|child| overlay_at(child, x, y))
+}
+
+// Like `find_view_mut`, but a view inside a disabled subtree isn't found
+fn find_enabled_view_mut(view: &mut dyn View, id: ViewId) -> Option<&mut dyn View> {
+    if !view.is_enabled() {
+        return None;
+    }
+    if view.id() == Some(id) {
+        return Some(view);
+    }
+    view.children_mut().into_iter().find_map(|child| find_enabled_view_mut(child, id))
+}
+
+fn focusable_at(view: &dyn View, x: f32, y: f32) -> Option<ViewId> {
+    if !view.is_enabled() {
+        return None;
+    }
+    // Later children draw on top, so they get the first chance
+    for child in view.children().into_iter().rev() {
+        if let Some(id) = focusable_at(child, x, y) {
+            return Some(id);
+        }
+    }
+    let hit = view.bounds().is_some_and(|b| b.contains(x, y));
+    if view.can_focus() && hit {
+        view.id()
+    } else {
+        None
+    }
+}
+
+fn collect_focusable(view: &dyn View, order: &mut Vec<ViewId>) {
+    if !view.is_enabled() {
+        return;
+    }
+    if view.can_focus() {
+        if let Some(id) = view.id() {
+            order.push(id);
+        }
+    }
+    for child in view.children() {
+        collect_focusable(child, order);
+    }
+}
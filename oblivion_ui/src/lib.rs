@@ -1,9 +1,30 @@
+pub mod accessibility;
+pub mod backend;
+pub mod color;
 pub mod components;
+pub mod damage;
+pub mod date;
+pub mod debug;
+pub mod dispatch;
+pub mod environment;
 pub mod state;
 pub mod rendering;
 pub mod themes;
 pub mod error;
 pub mod rso;
+pub mod scene;
+pub mod shortcut;
+pub mod focus;
+pub mod key;
+pub mod lifecycle;
+pub mod localization;
+pub mod texture;
+pub mod timer;
+pub mod window_state;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
+pub use key::Key;
 
 #[cfg(test)]
 mod tests {
@@ -43,4 +64,2744 @@ mod tests {
         animated.update(1.0);
         assert_eq!(animated.offset_x, 100.0);
     }
-}
\ No newline at end of file
+
+    fn text_view(s: &str) -> Box<dyn View> {
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(false));
+        Box::new(components::Text::new(state::State::new(s.to_string(), redraw).binding()))
+    }
+
+    #[test]
+    fn test_zstack_overlays_children() {
+        let mut zstack = components::ZStack::new();
+        zstack.add_child(text_view("bottom"));
+        zstack.add_child(text_view("top"));
+        let mut renderer = testing::MockRenderer::new();
+        zstack.render(&mut renderer, &environment::Environment::default(), 10.0, 20.0);
+        assert_eq!(renderer.texts().len(), 2);
+        assert!(renderer.texts().iter().all(|(_, x, y)| *x == 10.0 && *y == 20.0));
+        assert_eq!(renderer.texts()[1].0, "top");
+    }
+
+    #[test]
+    fn test_list_renders_top_to_bottom() {
+        let mut list = components::List::new(Vec::new()).separators(true);
+        list.add_child(text_view("first"));
+        list.add_child(text_view("second"));
+        list.add_child(text_view("third"));
+        let mut renderer = testing::MockRenderer::new();
+        list.render(&mut renderer, &environment::Environment::default(), 0.0, 0.0);
+        let texts = renderer.texts();
+        let names: Vec<&str> = texts.iter().map(|(t, _, _)| t.as_str()).collect();
+        assert_eq!(names, vec!["first", "second", "third"]);
+        assert!(texts.windows(2).all(|w| w[0].2 < w[1].2));
+        // Separators between the rows span the list
+        let width = list.size(&environment::Environment::default()).0;
+        assert_eq!(renderer.rects().iter().filter(|rect| rect.2 == width && rect.3 == 1.0).count(), 2);
+    }
+
+    #[test]
+    fn test_mock_renderer_records_button() {
+        let button = components::Button::new("OK".to_string());
+        let mut renderer = testing::MockRenderer::new();
+        button.render(&mut renderer, &environment::Environment::default(), 10.0, 10.0);
+        assert_eq!(renderer.calls.len(), 3);
+        assert!(matches!(renderer.calls[0], testing::DrawCall::Rect { x, y, .. } if x == 10.0 && y == 10.0));
+        assert!(matches!(renderer.calls[1], testing::DrawCall::RectOutline { x, y, .. } if x == 10.0 && y == 10.0));
+        assert!(matches!(renderer.calls[2], testing::DrawCall::Text { ref text, .. } if text == "OK"));
+    }
+
+    #[test]
+    fn test_slider_click_uses_width_and_origin() {
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(false));
+        let value = state::State::new(0.0f32, redraw);
+        let mut slider = components::Slider::new(value.binding(), 0.0, 10.0).width(200.0);
+        let mut renderer = testing::MockRenderer::new();
+        slider.render(&mut renderer, &environment::Environment::default(), 50.0, 0.0);
+        assert_eq!(renderer.rects()[0].2, 200.0);
+        slider.handle_event(&components::Event::Click { x: 150.0, y: 10.0 });
+        assert_eq!(value.get(), 5.0);
+    }
+
+    #[test]
+    fn test_button_click_respects_bounds() {
+        let clicked = std::rc::Rc::new(std::cell::Cell::new(false));
+        let flag = clicked.clone();
+        let mut button = components::Button::new("Go".to_string()).on_click(move || flag.set(true));
+        let mut renderer = testing::MockRenderer::new();
+        button.render(&mut renderer, &environment::Environment::default(), 10.0, 10.0);
+        button.handle_event(&components::Event::Click { x: 300.0, y: 300.0 });
+        assert!(!clicked.get());
+        button.handle_event(&components::Event::Click { x: 20.0, y: 20.0 });
+        assert!(clicked.get());
+    }
+
+    #[test]
+    fn test_focus_routes_keys_to_clicked_input() {
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(false));
+        let first = state::State::new(String::new(), redraw.clone());
+        let second = state::State::new(String::new(), redraw);
+        let mut vstack = components::VStack::new(10.0);
+        vstack.add_child(Box::new(components::Input::new(first.binding(), "First".to_string())));
+        vstack.add_child(Box::new(components::Input::new(second.binding(), "Second".to_string())));
+        let mut renderer = testing::MockRenderer::new();
+        vstack.render(&mut renderer, &environment::Environment::default(), 0.0, 0.0);
+
+        let mut focus = focus::FocusManager::new();
+        // Second input is laid out one row (30 + spacing) below the first
+        focus.route(&mut vstack, &components::Event::Click { x: 5.0, y: 45.0 });
+        focus.route(&mut vstack, &components::Event::TextInput("A".to_string()));
+        assert_eq!(first.get(), "");
+        assert_eq!(second.get(), "A");
+        assert!(focus.focused().is_some());
+
+        focus.route(&mut vstack, &components::Event::Click { x: 500.0, y: 500.0 });
+        assert!(focus.focused().is_none());
+    }
+
+    #[test]
+    fn test_input_text_input_events() {
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(false));
+        let text = state::State::new(String::new(), redraw);
+        let mut input = components::Input::new(text.binding(), String::new());
+        input.set_focused(true);
+        for chunk in ["H", "i", "!"] {
+            input.handle_event(&components::Event::TextInput(chunk.to_string()));
+        }
+        assert_eq!(text.get(), "Hi!");
+        input.handle_event(&components::Event::KeyDown(Key::Backspace));
+        assert_eq!(text.get(), "Hi");
+    }
+
+    #[test]
+    fn test_computed_state() {
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(false));
+        let a = state::State::new(2, redraw.clone());
+        let b = state::State::new(3, redraw.clone());
+        let (a_in, b_in) = (a.binding(), b.binding());
+        let sum = state::Computed::new(move || a_in.get() + b_in.get(), &[&a, &b]);
+        assert_eq!(sum.get(), 5);
+        a.set(10);
+        assert!(*redraw.borrow());
+        assert_eq!(sum.get(), 13);
+
+        let sum_in = sum.clone();
+        let label = state::Computed::new(move || format!("Sum: {}", sum_in.get()), &[&a, &b]);
+        let text = components::Text::new(label.binding());
+        let mut renderer = testing::MockRenderer::new();
+        text.render(&mut renderer, &environment::Environment::default(), 0.0, 0.0);
+        assert_eq!(renderer.texts()[0].0, "Sum: 13");
+    }
+
+    #[test]
+    fn test_grid_measures_columns() {
+        let env = environment::Environment::default();
+        let mut grid = components::Grid::new(2, 2, 5.0);
+        grid.set_child(0, 0, text_view("A much wider label"));
+        grid.set_child(0, 1, text_view("B"));
+        grid.set_child(1, 0, text_view("C"));
+        grid.set_child(1, 1, text_view("D"));
+        let mut renderer = testing::MockRenderer::new();
+        grid.render(&mut renderer, &env, 0.0, 0.0);
+        let texts = renderer.texts();
+        let wide_width = text_view("A much wider label").size(&env).0;
+        assert_eq!(texts[1].0, "B");
+        assert_eq!(texts[1].1, wide_width + 5.0);
+        assert_eq!(texts[3].1, wide_width + 5.0);
+
+        let grid = grid.column_widths(vec![50.0]);
+        renderer.clear();
+        grid.render(&mut renderer, &env, 0.0, 0.0);
+        assert_eq!(renderer.texts()[1].1, 55.0);
+    }
+
+    #[test]
+    fn test_theme_handle_switches_theme() {
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(false));
+        let handle = themes::ThemeHandle::new(themes::Theme::default(), redraw.clone());
+        let shared = handle.clone();
+        assert!(!handle.get().is_dark);
+        shared.set_theme(themes::Theme::dark());
+        assert!(handle.get().is_dark);
+        assert!(*redraw.borrow());
+        // `run` passes its theme as a default, which keeps the chosen one
+        handle.set_default(themes::Theme::default());
+        assert!(handle.get().is_dark);
+    }
+
+    #[test]
+    fn test_text_roles_use_theme_font_sizes() {
+        let env = environment::Environment::new(themes::Theme {
+            title_font_size: 30,
+            caption_font_size: 10,
+            ..themes::Theme::default()
+        });
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(false));
+        let label = state::State::new("Heading".to_string(), redraw);
+        let title = components::Text::new(label.binding()).role(themes::TextRole::Title);
+        let caption = components::Text::new(label.binding()).role(themes::TextRole::Caption);
+        let (title_w, title_h) = title.size(&env);
+        let (caption_w, caption_h) = caption.size(&env);
+        assert!(title_w > caption_w && title_h > caption_h);
+
+        let mut renderer = testing::MockRenderer::new();
+        title.render(&mut renderer, &env, 0.0, 0.0);
+        assert!(matches!(renderer.calls[0], testing::DrawCall::Text { font_size: 30, .. }));
+    }
+
+    #[test]
+    fn test_theme_from_file() {
+        let path = std::env::temp_dir().join(format!("oblivion_theme_{}.toml", std::process::id()));
+        std::fs::write(
+            &path,
+            "primary_color = \"#ff8000\"\nbackground_color = [10, 20, 30]\nfont_size = 16\nis_dark = true\n",
+        )
+        .unwrap();
+        let theme = themes::Theme::from_file(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(theme.primary_color, color::Color::rgb(255, 128, 0));
+        assert_eq!(theme.background_color, color::Color::rgb(10, 20, 30));
+        assert_eq!(theme.text_color, themes::Theme::dark().text_color);
+        assert_eq!(theme.font_size, 16);
+        assert!(theme.is_dark);
+
+        let bad = themes::Theme::from_toml_str("primary_color = [300, 0, 0]");
+        assert!(matches!(bad, Err(error::UiError::ThemeError(_))));
+        let bad_hex = themes::Theme::from_toml_str("text_color = \"#12\"").err().unwrap();
+        assert_eq!(bad_hex.to_string(), "Theme error: text_color: invalid hex color '#12'");
+        assert!(matches!(themes::Theme::from_toml_str("font_size = 0"), Err(error::UiError::ThemeError(_))));
+    }
+
+    #[test]
+    fn test_parse_hex_colors() {
+        use themes::Theme;
+        assert_eq!(Theme::parse_hex("#f80").unwrap(), (255, 136, 0).into());
+        assert_eq!(Theme::parse_hex("#007aff").unwrap(), (0, 122, 255).into());
+        assert_eq!(Theme::parse_hex("007AFF").unwrap(), (0, 122, 255).into());
+        assert_eq!(Theme::parse_hex("#007aff80").unwrap(), (0, 122, 255).into());
+        assert!(Theme::parse_hex("#00zaff").is_err());
+        assert!(Theme::parse_hex("#12345").is_err());
+        assert!(Theme::parse_hex("").is_err());
+        assert_eq!(Theme::to_hex((0, 122, 255)), "#007aff");
+    }
+
+    #[test]
+    fn test_for_each_rebuilds_on_binding_change() {
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(false));
+        let items = state::State::new(vec!["a".to_string(), "b".to_string()], redraw.clone());
+        let row_redraw = redraw.clone();
+        let mut list = components::ForEach::new(items.binding(), move |item: &String| {
+            Box::new(components::Text::new(state::State::new(item.clone(), row_redraw.clone()).binding())) as Box<dyn View>
+        });
+        let mut renderer = testing::MockRenderer::new();
+        list.render(&mut renderer, &environment::Environment::default(), 0.0, 0.0);
+        assert_eq!(renderer.texts().len(), 2);
+
+        items.set(vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+        list.update(0.0);
+        renderer.clear();
+        list.render(&mut renderer, &environment::Environment::default(), 0.0, 0.0);
+        assert_eq!(renderer.texts().len(), 3);
+        assert_eq!(renderer.texts()[2].0, "c");
+    }
+
+    #[test]
+    fn test_lifecycle_appear_and_disappear() {
+        use components::ViewExt;
+        let log = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let (appear_log, disappear_log) = (log.clone(), log.clone());
+        let panel = components::Panel::new(1.0, 5.0)
+            .on_appear(move || appear_log.borrow_mut().push("appear"))
+            .on_disappear(move || disappear_log.borrow_mut().push("disappear"));
+        let mut vstack = components::VStack::new(0.0);
+        vstack.add_child(Box::new(panel));
+
+        let mut tracker = lifecycle::LifecycleTracker::new();
+        tracker.update(&vstack);
+        tracker.update(&vstack);
+        assert_eq!(*log.borrow(), vec!["appear"]);
+
+        let panel = vstack.children.pop().unwrap();
+        tracker.update(&vstack);
+        assert_eq!(*log.borrow(), vec!["appear", "disappear"]);
+
+        vstack.add_child(panel);
+        tracker.update(&vstack);
+        assert_eq!(*log.borrow(), vec!["appear", "disappear", "appear"]);
+    }
+
+    #[test]
+    fn test_scroll_view_wheel_scrolls_and_clamps() {
+        let mut content = components::VStack::new(0.0);
+        for i in 0..10 {
+            content.add_child(text_view(&format!("Row {}", i)));
+        }
+        let env = environment::Environment::default();
+        let content_height = content.size(&env).1;
+        let mut scroll = components::ScrollView::new(Box::new(content), 200.0, 100.0);
+        let mut renderer = testing::MockRenderer::new();
+        scroll.render(&mut renderer, &env, 0.0, 0.0);
+
+        let wheel = |dy: f32| components::Event::MouseWheel { x: 50.0, y: 50.0, dx: 0.0, dy };
+        scroll.handle_event(&wheel(-1.0));
+        assert_eq!(scroll.scroll_offset.get(), 20.0);
+        scroll.handle_event(&wheel(5.0));
+        assert_eq!(scroll.scroll_offset.get(), 0.0);
+        scroll.handle_event(&wheel(-100.0));
+        assert_eq!(scroll.scroll_offset.get(), content_height - 100.0);
+
+        renderer.clear();
+        scroll.render(&mut renderer, &env, 0.0, 0.0);
+        assert_eq!(renderer.texts()[0].2, 100.0 - content_height);
+    }
+
+    #[test]
+    fn test_clip_stack_intersects() {
+        use components::Renderer;
+        let mut renderer = testing::MockRenderer::new();
+        renderer.push_clip(0.0, 0.0, 100.0, 100.0);
+        renderer.push_clip(50.0, 50.0, 100.0, 100.0);
+        renderer.draw_rect(200.0, 200.0, 10.0, 10.0, color::Color::BLACK);
+        assert_eq!(renderer.current_clip(), Some(components::Bounds::new(50.0, 50.0, 50.0, 50.0)));
+        assert_eq!(renderer.rects(), vec![(200.0, 200.0, 10.0, 10.0)]);
+        renderer.pop_clip();
+        assert_eq!(renderer.current_clip(), Some(components::Bounds::new(0.0, 0.0, 100.0, 100.0)));
+        renderer.pop_clip();
+        assert_eq!(renderer.current_clip(), None);
+        assert_eq!(renderer.calls.iter().filter(|c| matches!(c, testing::DrawCall::PopClip)).count(), 2);
+    }
+
+    #[test]
+    fn test_button_draws_with_primary_color() {
+        let env = environment::Environment::default();
+        let button = components::Button::new("OK".to_string());
+        let mut renderer = testing::MockRenderer::new();
+        button.render(&mut renderer, &env, 0.0, 0.0);
+        assert_eq!(renderer.rect_colors(), vec![env.primary_color]);
+    }
+
+    #[test]
+    fn test_borders_draw_outlines() {
+        let env = environment::Environment::default();
+        let mut stack = components::VStack::new(0.0).border(3.0);
+        stack.add_child(text_view("Hi"));
+        let mut renderer = testing::MockRenderer::new();
+        stack.render(&mut renderer, &env, 0.0, 0.0);
+        let (w, h) = stack.size(&env);
+        assert_eq!(renderer.outlines(), vec![(0.0, 0.0, w, h, 3.0)]);
+
+        renderer.clear();
+        components::Button::new("OK".to_string()).render(&mut renderer, &env, 0.0, 0.0);
+        assert_eq!(renderer.outlines(), vec![(0.0, 0.0, 100.0, 30.0, 1.0)]);
+
+        renderer.clear();
+        components::Button::new("OK".to_string()).border(3.0).render(&mut renderer, &env, 0.0, 0.0);
+        assert_eq!(renderer.outlines(), vec![(0.0, 0.0, 100.0, 30.0, 3.0)]);
+
+        renderer.clear();
+        components::Button::new("OK".to_string()).border(0.0).render(&mut renderer, &env, 0.0, 0.0);
+        assert!(renderer.outlines().is_empty());
+    }
+
+    #[test]
+    fn test_spacer_pushes_siblings_apart() {
+        let env = environment::Environment::default();
+        let mut row = components::HStack::new(0.0).width(300.0);
+        row.add_child(text_view("L"));
+        row.add_child(Box::new(components::Spacer::new()));
+        row.add_child(text_view("R"));
+        let mut renderer = testing::MockRenderer::new();
+        row.render(&mut renderer, &env, 0.0, 0.0);
+        let texts = renderer.texts();
+        let (right_width, _) = components::measure_text("R", env.font_size);
+        assert_eq!(texts[0].1, 0.0);
+        assert_eq!(texts[1].1, 300.0 - right_width);
+        assert_eq!(row.size(&env).0, 300.0);
+    }
+
+    #[test]
+    fn test_stack_alignment_centers_children() {
+        let env = environment::Environment::default();
+        let mut column = components::VStack::new(0.0).alignment(components::Alignment::Center);
+        column.add_child(text_view("A much longer line"));
+        column.add_child(text_view("Short"));
+        let mut renderer = testing::MockRenderer::new();
+        column.render(&mut renderer, &env, 0.0, 0.0);
+        let container_width = column.size(&env).0;
+        let (child_width, _) = components::measure_text("Short", env.font_size);
+        assert_eq!(renderer.texts()[0].1, 0.0);
+        assert_eq!(renderer.texts()[1].1, (container_width - child_width) / 2.0);
+
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(false));
+        let title = state::State::new("Big".to_string(), redraw).binding();
+        let mut row = components::HStack::new(0.0).alignment(components::VerticalAlignment::Bottom);
+        row.add_child(Box::new(components::Text::new(title).role(themes::TextRole::Title)));
+        row.add_child(text_view("small"));
+        renderer.clear();
+        row.render(&mut renderer, &env, 0.0, 0.0);
+        let (_, small_height) = components::measure_text("small", env.font_size);
+        assert_eq!(renderer.texts()[1].2, row.size(&env).1 - small_height);
+    }
+
+    #[test]
+    fn test_window_fills_background_before_children() {
+        let env = environment::Environment::default();
+        let mut window = components::Window::new("Main".to_string(), 640, 480);
+        window.add_child(text_view("First"));
+        window.add_child(text_view("Second"));
+        let mut renderer = testing::MockRenderer::new();
+        window.render(&mut renderer, &env, 0.0, 0.0);
+        assert_eq!(
+            renderer.calls[0],
+            testing::DrawCall::Rect { x: 0.0, y: 0.0, w: 640.0, h: 480.0, color: env.background_color }
+        );
+        let texts = renderer.texts();
+        let (_, title_height) = components::measure_text("Main", env.title_font_size);
+        let (_, first_height) = components::measure_text("First", env.font_size);
+        assert_eq!(texts[0].0, "Main");
+        assert_eq!(texts[1].2, title_height);
+        assert_eq!(texts[2].2, title_height + first_height);
+    }
+
+    #[test]
+    fn test_canvas_draws_at_origin() {
+        let origin = std::rc::Rc::new(std::cell::Cell::new((0.0, 0.0)));
+        let seen = origin.clone();
+        let clicks = std::rc::Rc::new(std::cell::Cell::new(0));
+        let counter = clicks.clone();
+        let mut canvas = components::Canvas::new(100.0, 50.0)
+            .on_draw(move |renderer, x, y| {
+                seen.set((x, y));
+                renderer.draw_rect(x + 10.0, y, 5.0, 5.0, color::Color::rgb(255, 0, 0));
+            })
+            .on_event(move |_| counter.set(counter.get() + 1));
+        let mut renderer = testing::MockRenderer::new();
+        canvas.render(&mut renderer, &environment::Environment::default(), 30.0, 40.0);
+        assert_eq!(origin.get(), (30.0, 40.0));
+        assert_eq!(renderer.rects()[1], (40.0, 40.0, 5.0, 5.0));
+        canvas.handle_event(&components::Event::Click { x: 35.0, y: 45.0 });
+        assert_eq!(clicks.get(), 1);
+    }
+
+    #[test]
+    fn test_drag_tracker_reports_deltas() {
+        let mut drag = rendering::DragTracker::new();
+        assert!(drag.motion(5.0, 5.0).is_none());
+        drag.press(10.0, 10.0);
+        assert!(matches!(drag.motion(15.0, 8.0), Some(components::Event::Drag { dx, dy }) if dx == 5.0 && dy == -2.0));
+        assert!(matches!(drag.motion(12.0, 8.0), Some(components::Event::Drag { dx, dy }) if dx == -3.0 && dy == 0.0));
+        drag.release();
+        assert!(drag.motion(20.0, 20.0).is_none());
+    }
+
+    #[test]
+    fn test_slider_follows_drag() {
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(false));
+        let value = state::State::new(0.0f32, redraw);
+        let mut slider = components::Slider::new(value.binding(), 0.0, 100.0);
+        let mut renderer = testing::MockRenderer::new();
+        slider.render(&mut renderer, &environment::Environment::default(), 0.0, 0.0);
+        slider.handle_event(&components::Event::Click { x: 20.0, y: 10.0 });
+        slider.handle_event(&components::Event::Drag { dx: 30.0, dy: 0.0 });
+        assert_eq!(value.get(), 50.0);
+        slider.handle_event(&components::Event::MouseUp { x: 50.0, y: 10.0 });
+        slider.handle_event(&components::Event::Drag { dx: 30.0, dy: 0.0 });
+        assert_eq!(value.get(), 50.0);
+    }
+
+    #[test]
+    fn test_tab_cycles_focus_and_wraps() {
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(false));
+        let mut form = components::VStack::new(0.0);
+        let mut ids = Vec::new();
+        for _ in 0..3 {
+            let input = components::Input::new(state::State::new(String::new(), redraw.clone()).binding(), String::new());
+            ids.push(input.id().unwrap());
+            form.add_child(Box::new(input));
+        }
+        let mut focus = focus::FocusManager::new();
+        for id in &ids {
+            focus.register(*id);
+        }
+        let tab = components::Event::KeyDown(Key::Tab);
+        let mut visited = Vec::new();
+        for _ in 0..4 {
+            focus.route(&mut form, &tab);
+            visited.push(focus.focused().unwrap());
+        }
+        assert_eq!(visited, vec![ids[0], ids[1], ids[2], ids[0]]);
+        focus.focus_previous(&mut form);
+        assert_eq!(focus.focused(), Some(ids[2]));
+    }
+
+    #[test]
+    fn test_animated_view_easing() {
+        let mut linear = components::AnimatedView::new(text_view("a"), 0.0, 100.0, 4.0);
+        let mut eased = components::AnimatedView::new(text_view("b"), 0.0, 100.0, 4.0)
+            .with_easing(components::Easing::EaseInOut);
+        linear.update(1.0);
+        eased.update(1.0);
+        assert_eq!(linear.offset_x, 25.0);
+        assert_eq!(eased.offset_x, 12.5);
+        // The curve is symmetric, so both meet at the halfway point
+        linear.update(1.0);
+        eased.update(1.0);
+        assert_eq!(linear.offset_x, 50.0);
+        assert_eq!(eased.offset_x, 50.0);
+        eased.update(5.0);
+        assert_eq!(eased.offset_x, 100.0);
+    }
+
+    #[test]
+    fn test_animated_view_y_and_opacity() {
+        let from = components::AnimatedProperties { x: 0.0, y: 40.0, opacity: 0.0 };
+        let to = components::AnimatedProperties { x: 0.0, y: 0.0, opacity: 1.0 };
+        let mut fade = components::AnimatedView::new_full(text_view("hi"), from, to, 2.0);
+        fade.update(0.5);
+        assert_eq!(fade.offset_y, 30.0);
+        assert_eq!(fade.opacity, 0.25);
+        let mut renderer = testing::MockRenderer::new();
+        fade.render(&mut renderer, &environment::Environment::default(), 0.0, 0.0);
+        assert_eq!(renderer.text_colors()[0].a, 64);
+        assert_eq!(renderer.texts()[0].2, 30.0);
+        fade.update(2.0);
+        assert_eq!(fade.offset_y, 0.0);
+        assert_eq!(fade.opacity, 1.0);
+    }
+
+    #[test]
+    fn test_frame_clock_drives_animation_proportionally() {
+        let start = std::time::Instant::now();
+        let mut clock = rendering::FrameClock::starting_at(start);
+        let mut animated = components::AnimatedView::new(text_view("a"), 0.0, 100.0, 1.0);
+        animated.update(clock.tick_at(start + std::time::Duration::from_millis(100)));
+        let first = animated.offset_x;
+        animated.update(clock.tick_at(start + std::time::Duration::from_millis(300)));
+        assert!((first - 10.0).abs() < 1e-3);
+        assert!((animated.offset_x - 3.0 * first).abs() < 1e-3);
+        assert!(animated.is_animating());
+        animated.update(clock.tick_at(start + std::time::Duration::from_secs(2)));
+        assert!(!animated.is_animating());
+    }
+
+    #[test]
+    fn test_frame_budget() {
+        use std::time::Duration;
+        assert_eq!(rendering::frame_budget(50, Duration::from_millis(5)), Some(Duration::from_millis(15)));
+        assert_eq!(rendering::frame_budget(50, Duration::from_millis(30)), Some(Duration::ZERO));
+        assert_eq!(rendering::frame_budget(0, Duration::from_millis(5)), None);
+    }
+
+    #[test]
+    fn test_window_resize_updates_layout() {
+        let env = environment::Environment::default();
+        let mut window = components::Window::new("Main".to_string(), 640, 480);
+        let mut row = components::HStack::new(0.0);
+        row.add_child(text_view("child"));
+        window.add_child(Box::new(row));
+        window.handle_event(&components::Event::Resize { width: 1024, height: 768 });
+        assert_eq!(window.size(&env), (1024.0, 768.0));
+        let mut renderer = testing::MockRenderer::new();
+        window.render(&mut renderer, &env, 0.0, 0.0);
+        assert_eq!(renderer.rects()[0], (0.0, 0.0, 1024.0, 768.0));
+    }
+
+    #[test]
+    fn test_color_hex_and_tuples() {
+        use color::Color;
+        assert_eq!(Color::from_hex("#ff8000").unwrap(), Color::rgb(255, 128, 0));
+        assert_eq!(Color::from_hex("0f0").unwrap(), Color::rgb(0, 255, 0));
+        assert_eq!(Color::from_hex("#00000080").unwrap(), Color::rgba(0, 0, 0, 128));
+        assert!(Color::from_hex("#ff80").is_err());
+        assert_eq!(Color::rgba(1, 2, 3, 4).to_hex(), "#01020304");
+        assert_eq!(Color::from((1, 2, 3)), Color::rgb(1, 2, 3));
+        assert_eq!(<(u8, u8, u8)>::from(Color::WHITE), (255, 255, 255));
+        assert_eq!(Color::CLEAR.a, 0);
+    }
+
+    #[test]
+    fn test_progress_bar_fill_is_clamped() {
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(false));
+        let progress = state::State::new(0.5f32, redraw);
+        let bar = components::ProgressBar::new(progress.binding()).width(300.0);
+        let env = environment::Environment::default();
+        let mut renderer = testing::MockRenderer::new();
+        bar.render(&mut renderer, &env, 0.0, 0.0);
+        assert_eq!(renderer.rects(), vec![(0.0, 0.0, 300.0, 20.0), (0.0, 0.0, 150.0, 20.0)]);
+        assert_eq!(renderer.rect_colors(), vec![env.secondary_color, env.primary_color]);
+        for (value, expected) in [(1.7, 300.0), (-0.2, 0.0)] {
+            progress.set(value);
+            renderer.clear();
+            bar.render(&mut renderer, &env, 0.0, 0.0);
+            assert_eq!(renderer.rects()[1].2, expected);
+        }
+    }
+
+    #[test]
+    fn test_radio_group_selects_clicked_option() {
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(false));
+        let selected = state::State::new(0usize, redraw);
+        let changes = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let log = changes.clone();
+        let options = vec!["Small".to_string(), "Medium".to_string(), "Large".to_string()];
+        let mut group = components::RadioGroup::new(options, selected.binding())
+            .on_change(move |i| log.borrow_mut().push(i));
+        let env = environment::Environment::default();
+        let mut renderer = testing::MockRenderer::new();
+        group.render(&mut renderer, &env, 10.0, 10.0);
+        // Third row spans y = 58..82
+        group.handle_event(&components::Event::Click { x: 20.0, y: 60.0 });
+        assert_eq!(selected.get(), 2);
+        assert_eq!(*changes.borrow(), vec![2]);
+
+        renderer.clear();
+        group.render(&mut renderer, &env, 10.0, 10.0);
+        assert_eq!(renderer.rects().len(), 1);
+        assert_eq!(renderer.rects()[0].1, 10.0 + 2.0 * 24.0 + 6.0);
+        assert_eq!(renderer.outlines().len(), 2);
+    }
+
+    #[test]
+    fn test_picker_expands_and_selects() {
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(false));
+        let selected = state::State::new(0usize, redraw);
+        let options = vec!["Red".to_string(), "Green".to_string(), "Blue".to_string()];
+        let mut root = components::VStack::new(0.0);
+        root.add_child(Box::new(components::Picker::new(options, selected.binding())));
+        let clicked = std::rc::Rc::new(std::cell::Cell::new(false));
+        let flag = clicked.clone();
+        // A button right below the picker, covered by the open list
+        root.add_child(Box::new(components::Button::new("Under".to_string()).on_click(move || flag.set(true))));
+        let env = environment::Environment::default();
+        let mut renderer = testing::MockRenderer::new();
+        root.render(&mut renderer, &env, 0.0, 0.0);
+        let mut focus = focus::FocusManager::new();
+
+        focus.route(&mut root, &components::Event::Click { x: 10.0, y: 10.0 });
+        assert!(root.children()[0].overlay_bounds().is_some());
+        renderer.clear();
+        root.render(&mut renderer, &env, 0.0, 0.0);
+        root.render_overlay(&mut renderer, &env);
+        // Overlay text is drawn after the button label
+        assert_eq!(renderer.texts().last().unwrap().0, "Blue");
+
+        // Third option sits at y = 24 + 2 * 24
+        focus.route(&mut root, &components::Event::Click { x: 10.0, y: 75.0 });
+        assert_eq!(selected.get(), 2);
+        assert!(root.children()[0].overlay_bounds().is_none());
+
+        // Opening then clicking on the list over the button doesn't reach it
+        focus.route(&mut root, &components::Event::Click { x: 10.0, y: 10.0 });
+        focus.route(&mut root, &components::Event::Click { x: 10.0, y: 30.0 });
+        assert_eq!(selected.get(), 0);
+        assert!(!clicked.get());
+    }
+
+    #[test]
+    fn test_text_wraps_at_max_width() {
+        let env = environment::Environment::default();
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(false));
+        let content = state::State::new("the quick brown fox jumps over".to_string(), redraw);
+        let single = components::Text::new(content.binding());
+        let wrapped = components::Text::new(content.binding()).max_width(100.0);
+        let mut renderer = testing::MockRenderer::new();
+        wrapped.render(&mut renderer, &env, 0.0, 0.0);
+        let lines = renderer.texts();
+        assert!(lines.len() > 1);
+        assert_eq!(lines[0].0, "the quick");
+        let (_, line_height) = components::measure_text("", env.font_size);
+        assert_eq!(lines[1].2, line_height);
+        assert!(lines.iter().all(|l| components::measure_text(&l.0, env.font_size).0 <= 100.0));
+        assert_eq!(wrapped.size(&env).1, line_height * lines.len() as f32);
+        assert!(wrapped.size(&env).1 > single.size(&env).1);
+    }
+
+    #[test]
+    fn test_text_alignment_and_color() {
+        let env = environment::Environment::default();
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(false));
+        let content = state::State::new("Title".to_string(), redraw);
+        let text = components::Text::new(content.binding())
+            .max_width(200.0)
+            .alignment(components::TextAlignment::Center)
+            .color((200, 0, 0));
+        let mut renderer = testing::MockRenderer::new();
+        text.render(&mut renderer, &env, 10.0, 0.0);
+        let (width, _) = components::measure_text("Title", env.font_size);
+        assert_eq!(renderer.texts()[0].1, 10.0 + (200.0 - width) / 2.0);
+        assert_eq!(renderer.text_colors(), vec![color::Color::rgb(200, 0, 0)]);
+
+        renderer.clear();
+        text_view("plain").render(&mut renderer, &env, 0.0, 0.0);
+        assert_eq!(renderer.text_colors(), vec![env.text_color]);
+    }
+
+    #[test]
+    fn test_window_geometry_round_trip() {
+        use window_state::WindowGeometry;
+        let geometry = WindowGeometry { x: -20, y: 40, width: 1024, height: 768 };
+        assert_eq!(WindowGeometry::from_json(&geometry.to_json()), Some(geometry));
+        assert_eq!(WindowGeometry::from_json("{\"x\": 1, \"y\""), None);
+        assert_eq!(WindowGeometry::load(std::path::Path::new("/nonexistent/window.json")), None);
+    }
+
+    #[test]
+    fn test_window_handles_have_independent_redraw_flags() {
+        let palette = rendering::WindowHandle::new(1);
+        let dialog = rendering::WindowHandle::new(2);
+        palette.clear_redraw();
+        dialog.clear_redraw();
+        let redraw = palette.redraw_trigger();
+        let count = state::State::new(0, redraw);
+        count.set(1);
+        assert!(palette.needs_redraw());
+        assert!(!dialog.needs_redraw());
+        dialog.request_redraw();
+        palette.clear_redraw();
+        assert!(!palette.needs_redraw());
+        assert!(dialog.needs_redraw());
+    }
+
+    #[test]
+    fn test_alert_blocks_clicks_while_presented() {
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(false));
+        let presented = state::State::new(true, redraw);
+        let clicked = std::rc::Rc::new(std::cell::Cell::new(false));
+        let flag = clicked.clone();
+        let result = std::rc::Rc::new(std::cell::Cell::new(None));
+        let chosen = result.clone();
+        let mut root = components::ZStack::new();
+        root.add_child(Box::new(components::Button::new("Behind".to_string()).on_click(move || flag.set(true))));
+        root.add_child(Box::new(
+            components::Alert::new("Delete?".to_string(), "This can't be undone".to_string(), presented.binding())
+                .area(400.0, 300.0)
+                .on_result(move |r| chosen.set(Some(r))),
+        ));
+        let env = environment::Environment::default();
+        let mut renderer = testing::MockRenderer::new();
+        root.render(&mut renderer, &env, 0.0, 0.0);
+        root.render_overlay(&mut renderer, &env);
+        assert_eq!(renderer.rects()[1], (0.0, 0.0, 400.0, 300.0));
+
+        let mut focus = focus::FocusManager::new();
+        focus.route(&mut root, &components::Event::Click { x: 10.0, y: 10.0 });
+        assert!(!clicked.get());
+        assert!(presented.get());
+
+        // OK button: panel at (50, 75), button at its bottom-right corner
+        focus.route(&mut root, &components::Event::Click { x: 300.0, y: 200.0 });
+        assert_eq!(result.get(), Some(components::AlertResult::Ok));
+        assert!(!presented.get());
+        focus.route(&mut root, &components::Event::Click { x: 10.0, y: 10.0 });
+        assert!(clicked.get());
+    }
+
+    #[test]
+    fn test_alert_covers_the_window_and_follows_resizes() {
+        use components::Event;
+        let presented = state::State::new(true, std::rc::Rc::new(std::cell::RefCell::new(false)));
+        let mut window = components::Window::new("App".to_string(), 1000, 700);
+        window.add_child(Box::new(components::Alert::new("Quit?".to_string(), String::new(), presented.binding())));
+        let env = environment::Environment::default();
+        let window_env = env.with_available_size(Some(1000.0), Some(700.0));
+        let mut renderer = testing::MockRenderer::new();
+        window.render_overlay(&mut renderer, &window_env);
+        assert_eq!(renderer.rects()[0], (0.0, 0.0, 1000.0, 700.0));
+
+        // The resize reaches the window under the open alert, and the alert
+        let mut focus = focus::FocusManager::new();
+        focus.route(&mut window, &Event::Resize { width: 640, height: 480 });
+        assert_eq!(window.size(&env), (640.0, 480.0));
+        renderer.clear();
+        window.render_overlay(&mut renderer, &env);
+        assert_eq!(renderer.rects()[0], (0.0, 0.0, 640.0, 480.0));
+        assert!(presented.get());
+    }
+
+    #[test]
+    fn test_gesture_recognizer_thresholds() {
+        use crate::rendering::GestureRecognizer;
+        use std::time::{Duration, Instant};
+
+        use components::Event;
+
+        let start = Instant::now();
+        let ms = |n| start + Duration::from_millis(n);
+        let mut gestures = GestureRecognizer::new();
+
+        // Two quick clicks in place make a double-click
+        assert!(gestures.press(10.0, 10.0, start).is_none());
+        gestures.release();
+        assert!(matches!(gestures.press(12.0, 11.0, ms(200)), Some(Event::DoubleClick { .. })));
+        gestures.release();
+
+        // Too slow
+        assert!(gestures.press(10.0, 10.0, ms(1000)).is_none());
+        gestures.release();
+        assert!(gestures.press(10.0, 10.0, ms(1500)).is_none());
+        gestures.release();
+
+        // Too far apart
+        assert!(gestures.press(50.0, 10.0, ms(1600)).is_none());
+        gestures.release();
+
+        // Held long enough without moving
+        gestures.press(10.0, 10.0, ms(5000));
+        assert!(gestures.poll(ms(5300)).is_none());
+        assert!(matches!(gestures.poll(ms(5600)), Some(Event::LongPress { x, y }) if x == 10.0 && y == 10.0));
+        // Only reported once per press
+        assert!(gestures.poll(ms(6000)).is_none());
+        gestures.release();
+
+        // Moving past the tolerance cancels it
+        gestures.press(10.0, 10.0, ms(9000));
+        gestures.motion(30.0, 10.0);
+        assert!(gestures.poll(ms(10000)).is_none());
+    }
+
+    #[test]
+    fn test_on_double_click_fires_inside_view() {
+        use components::{Button, Event, ViewExt};
+
+        let count = std::rc::Rc::new(std::cell::RefCell::new(0));
+        let counter = count.clone();
+        let mut view = Button::new("Open".to_string()).on_double_click(move || *counter.borrow_mut() += 1);
+        let env = environment::Environment::default();
+        let mut renderer = testing::MockRenderer::new();
+        view.render(&mut renderer, &env, 0.0, 0.0);
+
+        view.handle_event(&Event::DoubleClick { x: 5.0, y: 5.0 });
+        view.handle_event(&Event::DoubleClick { x: 500.0, y: 500.0 });
+        view.handle_event(&Event::LongPress { x: 5.0, y: 5.0 });
+        assert_eq!(*count.borrow(), 1);
+    }
+
+    #[test]
+    fn test_text_area_inserts_newline() {
+        use components::{Event, TextArea};
+
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(false));
+        let text = state::State::new("one".to_string(), redraw);
+        let mut area = TextArea::new(text.binding());
+
+        // Ignored until focused
+        area.handle_event(&Event::KeyDown(Key::Enter));
+        assert_eq!(text.get(), "one");
+
+        area.set_focused(true);
+        area.handle_event(&Event::KeyDown(Key::Enter));
+        area.handle_event(&Event::TextInput("two".to_string()));
+        assert_eq!(text.get(), "one\ntwo");
+
+        let env = environment::Environment::default();
+        let mut renderer = testing::MockRenderer::new();
+        area.render(&mut renderer, &env, 0.0, 0.0);
+        let lines: Vec<String> = renderer.texts().into_iter().map(|(text, ..)| text).collect();
+        assert_eq!(lines, vec!["one", "two"]);
+    }
+
+    #[test]
+    fn test_text_area_backspace_merges_lines() {
+        use components::{Event, TextArea};
+
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(false));
+        let text = state::State::new("one\n".to_string(), redraw);
+        let mut area = TextArea::new(text.binding());
+        area.set_focused(true);
+
+        area.handle_event(&Event::KeyDown(Key::Backspace));
+        area.handle_event(&Event::TextInput("two".to_string()));
+        assert_eq!(text.get(), "onetwo");
+    }
+
+    #[test]
+    fn test_input_cursor_stays_in_bounds() {
+        use components::{Event, Input};
+
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(false));
+        let text = state::State::new("ab".to_string(), redraw);
+        let mut input = Input::new(text.binding(), String::new());
+        input.set_focused(true);
+        assert_eq!(input.cursor(), 2);
+
+        input.handle_event(&Event::KeyDown(Key::Right));
+        assert_eq!(input.cursor(), 2);
+        for _ in 0..3 {
+            input.handle_event(&Event::KeyDown(Key::Left));
+        }
+        assert_eq!(input.cursor(), 0);
+        input.handle_event(&Event::KeyDown(Key::End));
+        assert_eq!(input.cursor(), 2);
+        input.handle_event(&Event::KeyDown(Key::Home));
+        assert_eq!(input.cursor(), 0);
+
+        // Nothing is filled over the caret; the border is an outline
+        let mut renderer = testing::MockRenderer::new();
+        input.render(&mut renderer, &environment::Environment::default(), 0.0, 0.0);
+        assert_eq!(renderer.rects().last(), Some(&(0.0, 0.0, 1.0, 25.0)));
+        assert_eq!(renderer.outlines(), vec![(0.0, 0.0, 200.0, 25.0, 1.0)]);
+    }
+
+    #[test]
+    fn test_input_edits_at_cursor_and_replaces_selection() {
+        use components::{Event, Input};
+
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(false));
+        let text = state::State::new("held".to_string(), redraw);
+        let mut input = Input::new(text.binding(), String::new());
+        input.set_focused(true);
+
+        input.handle_event(&Event::KeyDown(Key::Left));
+        input.handle_event(&Event::KeyDown(Key::Left));
+        input.handle_event(&Event::TextInput("l".to_string()));
+        assert_eq!(text.get(), "helld");
+        assert_eq!(input.cursor(), 3);
+
+        input.handle_event(&Event::KeyDown(Key::Backspace));
+        assert_eq!(text.get(), "held");
+
+        // Select "ld" and type over it
+        input.handle_event(&Event::ShiftKeyDown(Key::End));
+        assert_eq!(input.selection(), Some((2, 4)));
+        input.handle_event(&Event::TextInput("lo".to_string()));
+        assert_eq!(text.get(), "helo");
+        assert_eq!(input.selection(), None);
+    }
+
+    #[test]
+    fn test_input_clipboard_actions() {
+        use components::{Clipboard, Event, Input};
+
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(false));
+        let text = state::State::new("hello".to_string(), redraw);
+        let mut input = Input::new(text.binding(), String::new());
+        input.set_focused(true);
+
+        // Paste lands at the cursor, not the end
+        input.handle_event(&Event::KeyDown(Key::Home));
+        input.handle_event(&Event::KeyDown(Key::Right));
+        input.handle_event(&Event::Paste("EE".to_string()));
+        assert_eq!(text.get(), "hEEello");
+        assert_eq!(input.cursor(), 3);
+
+        // Nothing selected, nothing copied
+        let clipboard = Clipboard::default();
+        input.handle_event(&Event::Copy(clipboard.clone()));
+        assert_eq!(clipboard.take(), None);
+
+        input.handle_event(&Event::ShiftKeyDown(Key::Left));
+        input.handle_event(&Event::ShiftKeyDown(Key::Left));
+        input.handle_event(&Event::Copy(clipboard.clone()));
+        assert_eq!(clipboard.take().as_deref(), Some("EE"));
+        assert_eq!(text.get(), "hEEello");
+
+        input.handle_event(&Event::Cut(clipboard.clone()));
+        assert_eq!(clipboard.take().as_deref(), Some("EE"));
+        assert_eq!(text.get(), "hello");
+    }
+
+    #[test]
+    fn test_modifiers_compose_sizes() {
+        use components::{Canvas, ViewExt};
+
+        let env = environment::Environment::default();
+        let padded = Canvas::new(40.0, 20.0).padding(5.0);
+        assert_eq!(padded.size(&env), (50.0, 30.0));
+
+        // Background inside padding covers only the child; outside, the padded area
+        let mut renderer = testing::MockRenderer::new();
+        Canvas::new(40.0, 20.0).background((255, 0, 0)).padding(5.0).render(&mut renderer, &env, 0.0, 0.0);
+        Canvas::new(40.0, 20.0).padding(5.0).background((255, 0, 0)).render(&mut renderer, &env, 0.0, 0.0);
+        let red: Vec<_> = renderer
+            .rects()
+            .into_iter()
+            .zip(renderer.rect_colors())
+            .filter(|(_, color)| *color == color::Color::rgb(255, 0, 0))
+            .map(|(rect, _)| rect)
+            .collect();
+        assert_eq!(red, vec![(5.0, 5.0, 40.0, 20.0), (0.0, 0.0, 50.0, 30.0)]);
+    }
+
+    #[test]
+    fn test_frame_forces_size() {
+        use components::{Text, ViewExt};
+
+        let env = environment::Environment::default();
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(false));
+        let label = state::State::new("Hello".to_string(), redraw);
+        let (text_width, text_height) = Text::new(label.binding()).size(&env);
+
+        assert_eq!(Text::new(label.binding()).frame(120.0, 40.0).size(&env), (120.0, 40.0));
+        assert_eq!(Text::new(label.binding()).frame(120.0, None).size(&env), (120.0, text_height));
+        assert_eq!(Text::new(label.binding()).frame(None, 40.0).size(&env), (text_width, 40.0));
+
+        let mut renderer = testing::MockRenderer::new();
+        Text::new(label.binding()).frame(10.0, 10.0).clipped().render(&mut renderer, &env, 0.0, 0.0);
+        assert!(matches!(renderer.calls.first(), Some(testing::DrawCall::PushClip { w, h, .. }) if *w == 10.0 && *h == 10.0));
+    }
+
+    #[test]
+    fn test_shadow_and_corner_radius_modifiers() {
+        use color::Color;
+        use components::{Canvas, ViewExt};
+        use testing::DrawCall;
+
+        let env = environment::Environment::default();
+        let shadow = Color::rgba(0, 0, 0, 96);
+        let mut renderer = testing::MockRenderer::new();
+        Canvas::new(40.0, 20.0).shadow(4.0, 6.0, shadow).render(&mut renderer, &env, 10.0, 10.0);
+        // Shadow first, at the offset, then the canvas itself
+        assert_eq!(renderer.rects(), vec![(14.0, 16.0, 40.0, 20.0), (10.0, 10.0, 40.0, 20.0)]);
+        assert_eq!(renderer.rect_colors()[0], shadow);
+
+        // Only the fill covering the whole view gets rounded
+        let mut renderer = testing::MockRenderer::new();
+        Canvas::new(40.0, 20.0)
+            .on_draw(|r, x, y| r.draw_rect(x, y, 5.0, 5.0, Color::WHITE))
+            .background(Color::BLACK)
+            .corner_radius(6.0)
+            .render(&mut renderer, &env, 0.0, 0.0);
+        let rounded = renderer.calls.iter().filter(|call| matches!(call, DrawCall::RoundRect { radius, .. } if *radius == 6.0)).count();
+        assert_eq!(rounded, 2);
+        assert_eq!(renderer.rects(), vec![(0.0, 0.0, 5.0, 5.0)]);
+    }
+
+    #[test]
+    fn test_hover_enters_and_leaves() {
+        use components::{Canvas, ViewExt};
+        use focus::HoverTracker;
+
+        let changes = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let log = changes.clone();
+        let mut view = Canvas::new(40.0, 20.0).on_hover(move |hovered| log.borrow_mut().push(hovered));
+        let env = environment::Environment::default();
+        view.render(&mut testing::MockRenderer::new(), &env, 10.0, 10.0);
+
+        let mut hover = HoverTracker::new();
+        assert!(!hover.update(&mut view, 0.0, 0.0));
+        assert!(hover.update(&mut view, 20.0, 20.0));
+        // Moving within the bounds isn't a transition
+        assert!(!hover.update(&mut view, 30.0, 25.0));
+        assert!(hover.update(&mut view, 60.0, 20.0));
+        assert!(hover.update(&mut view, 15.0, 15.0));
+        assert!(hover.clear(&mut view));
+        assert_eq!(*changes.borrow(), vec![true, false, true, false]);
+    }
+
+    #[test]
+    fn test_environment_override_is_scoped_to_subtree() {
+        use components::{Renderer, VStack, ViewExt};
+        use environment::Environment;
+
+        // Records the locale it was rendered with
+        struct LocaleProbe(std::rc::Rc<std::cell::RefCell<Vec<String>>>);
+        impl View for LocaleProbe {
+            fn render(&self, _renderer: &mut dyn Renderer, env: &Environment, _x: f32, _y: f32) {
+                self.0.borrow_mut().push(env.get::<String>("locale").cloned().unwrap_or_default());
+            }
+            fn handle_event(&mut self, _event: &components::Event) -> bool {
+                false
+            }
+        }
+
+        let seen = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let mut stack = VStack::new(0.0);
+        stack.add_child(Box::new(LocaleProbe(seen.clone())));
+        stack.add_child(Box::new(LocaleProbe(seen.clone()).environment("locale", "fr".to_string())));
+        stack.add_child(Box::new(LocaleProbe(seen.clone())));
+        // The innermost override wins
+        let nested = LocaleProbe(seen.clone()).environment("locale", "de".to_string()).environment("locale", "fr".to_string());
+        stack.add_child(Box::new(nested));
+
+        let mut env = Environment::default();
+        env.set("locale", "en".to_string());
+        stack.render(&mut testing::MockRenderer::new(), &env, 0.0, 0.0);
+        assert_eq!(*seen.borrow(), vec!["en", "fr", "en", "de"]);
+    }
+
+    #[test]
+    fn test_hidpi_scaling() {
+        use rendering::{physical_rect, scale_factor};
+
+        assert_eq!(scale_factor((800, 600), (1600, 1200)), 2.0);
+        assert_eq!(scale_factor((800, 600), (800, 600)), 1.0);
+        assert_eq!(scale_factor((0, 0), (0, 0)), 1.0);
+
+        let rect = physical_rect(10.0, 5.5, 100.0, 30.0, 2.0);
+        assert_eq!((rect.x(), rect.y(), rect.width(), rect.height()), (20, 11, 200, 60));
+        // Edges round independently, so neighbours still touch
+        let left = physical_rect(0.0, 0.0, 10.3, 1.0, 1.5);
+        let right = physical_rect(10.3, 0.0, 10.0, 1.0, 1.5);
+        assert_eq!(left.x() + left.width() as i32, right.x());
+    }
+
+    #[test]
+    fn test_error_variants_display() {
+        use error::UiError;
+
+        let parse = UiError::ParseError("unexpected '}' at line 3".to_string());
+        assert_eq!(parse.to_string(), "Parse error: unexpected '}' at line 3");
+        let theme = UiError::ThemeError("unknown key 'colour'".to_string());
+        assert_eq!(theme.to_string(), "Theme error: unknown key 'colour'");
+
+        let io: UiError = std::io::Error::new(std::io::ErrorKind::NotFound, "app.rso").into();
+        assert!(matches!(io, UiError::IoError(_)));
+        assert_eq!(io.to_string(), "I/O error: app.rso");
+
+        assert!(matches!(rso::load_rso("  ", &rso::RsoRegistry::new()), Err(UiError::ParseError(_))));
+        assert!(rso::load_rso("VStack { Text('Hello') }", &rso::RsoRegistry::new()).is_ok());
+    }
+
+    #[test]
+    fn test_parse_errors_are_not_labelled_sdl() {
+        let Err(err) = rso::load_rso("", &rso::RsoRegistry::new()) else {
+            panic!("empty document should fail to parse");
+        };
+        assert!(!err.to_string().contains("SDL2"), "{}", err);
+    }
+
+    #[test]
+    fn test_rso_reloader_keeps_last_good_view() {
+        use rso::RsoReloader;
+
+        let texts = |view: &dyn View| {
+            let mut renderer = testing::MockRenderer::new();
+            view.render(&mut renderer, &environment::Environment::default(), 0.0, 0.0);
+            renderer.texts().into_iter().map(|(text, ..)| text).collect::<Vec<_>>()
+        };
+
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(false));
+        let (sender, updates) = std::sync::mpsc::channel();
+        let errors = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let sink = errors.clone();
+        let greeting = state::State::new("Hello".to_string(), redraw.clone());
+        let registry = rso::RsoRegistry::new().binding("greeting", greeting.binding());
+        let mut reloader = RsoReloader::new("Text(bind: 'greeting')", registry)
+            .unwrap()
+            .watch(updates, redraw.clone())
+            .on_error(move |e| sink.borrow_mut().push(e.to_string()));
+        assert_eq!(texts(&reloader), vec!["Hello"]);
+
+        sender.send("VStack { Text(bind: 'greeting') Text('World') }".to_string()).unwrap();
+        reloader.update(0.0);
+        assert_eq!(texts(&reloader), vec!["Hello", "World"]);
+        assert!(*redraw.borrow());
+
+        // A syntax error and an unknown binding both keep the previous view
+        *redraw.borrow_mut() = false;
+        sender.send("VStack { Text('Hello' }".to_string()).unwrap();
+        reloader.update(0.0);
+        sender.send("Text(bind: 'farewell')".to_string()).unwrap();
+        reloader.update(0.0);
+        assert_eq!(texts(&reloader), vec!["Hello", "World"]);
+        assert!(!*redraw.borrow());
+        assert_eq!(errors.borrow().len(), 2);
+
+        sender.send("Button('Fixed')".to_string()).unwrap();
+        reloader.update(0.0);
+        assert_eq!(texts(&reloader), vec!["Fixed"]);
+    }
+
+    #[test]
+    fn test_binding_map_projects_a_field() {
+        #[derive(Clone, Debug, PartialEq)]
+        struct Profile {
+            name: String,
+            age: u32,
+        }
+
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(false));
+        let profile = state::State::new(Profile { name: "Ada".to_string(), age: 36 }, redraw.clone());
+        let name = profile.binding().map(|p| p.name.clone(), |p, name| p.name = name);
+        assert_eq!(name.get(), "Ada");
+
+        name.set("Grace".to_string());
+        assert_eq!(profile.get(), Profile { name: "Grace".to_string(), age: 36 });
+        assert!(*redraw.borrow());
+
+        // An Input can edit just the field
+        let mut input = components::Input::new(name.clone(), String::new());
+        input.set_focused(true);
+        input.handle_event(&components::Event::TextInput("!".to_string()));
+        assert_eq!(profile.get().name, "Grace!");
+
+        // Projections compose
+        let initial = name.map(|n| n.chars().next().unwrap_or(' '), |n, c| n.replace_range(..1, &c.to_string()));
+        initial.set('B');
+        assert_eq!(profile.get().name, "Brace!");
+    }
+
+    #[test]
+    fn test_state_observers_fire_on_set() {
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(false));
+        let count = state::State::new(0, redraw.clone());
+        let seen = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let first = seen.clone();
+        count.on_change(move |value| first.borrow_mut().push(("first", *value)));
+        let second = seen.clone();
+        count.on_change(move |value| second.borrow_mut().push(("second", *value)));
+
+        count.set(1);
+        assert_eq!(*seen.borrow(), vec![("first", 1), ("second", 1)]);
+        assert!(*redraw.borrow());
+
+        // Writes through a binding notify too
+        count.binding().set(2);
+        assert_eq!(seen.borrow().last(), Some(&("second", 2)));
+        assert_eq!(seen.borrow().len(), 4);
+    }
+
+    #[test]
+    fn test_shared_state_set_from_thread() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+
+        let flag = Arc::new(AtomicBool::new(false));
+        let status = state::SharedState::new("Downloading".to_string(), flag.clone());
+        let binding = status.binding();
+
+        let worker = status.clone();
+        std::thread::spawn(move || worker.set("Done".to_string())).join().unwrap();
+
+        assert!(flag.load(Ordering::Acquire));
+        assert_eq!(status.get(), "Done");
+        assert_eq!(binding.get(), "Done");
+    }
+
+    #[test]
+    fn test_dispatcher_applies_messages_in_order() {
+        enum Msg {
+            Add(i32),
+            Double,
+        }
+
+        let dispatcher = dispatch::Dispatcher::new();
+        let sender = dispatcher.sender();
+        std::thread::spawn(move || {
+            sender.send(Msg::Add(2)).unwrap();
+            sender.send(Msg::Double).unwrap();
+            sender.send(Msg::Add(1)).unwrap();
+        })
+        .join()
+        .unwrap();
+
+        let mut total = 0;
+        let applied = dispatcher.drain(&mut total, |total, msg| match msg {
+            Msg::Add(n) => *total += n,
+            Msg::Double => *total *= 2,
+        });
+        assert_eq!(applied, 3);
+        assert_eq!(total, 5);
+        assert_eq!(dispatcher.drain(&mut total, |_, _| panic!("queue should be empty")), 0);
+    }
+
+    #[test]
+    fn test_timers_fire_when_due() {
+        use std::time::{Duration, Instant};
+        use timer::{due_timers, TimerQueue};
+
+        let start = Instant::now();
+        let ms = |n| start + Duration::from_millis(n);
+        assert_eq!(due_timers([ms(30), ms(10), ms(50), ms(20)], ms(25)), vec![1, 3]);
+        assert!(due_timers([ms(30)], ms(29)).is_empty());
+        assert_eq!(due_timers([ms(30)], ms(30)), vec![0]);
+
+        let log = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let mut timers = TimerQueue::new();
+        let once = log.clone();
+        timers.schedule_at(ms(10), None, move || once.borrow_mut().push("once"));
+        let tick = log.clone();
+        let repeating = timers.schedule_at(ms(20), Some(Duration::from_millis(20)), move || tick.borrow_mut().push("tick"));
+
+        assert!(!timers.fire_due(ms(5)));
+        assert!(timers.fire_due(ms(20)));
+        assert_eq!(*log.borrow(), vec!["once", "tick"]);
+        assert_eq!(timers.next_due(), Some(ms(40)));
+
+        assert!(timers.fire_due(ms(45)));
+        repeating.cancel();
+        assert!(!timers.fire_due(ms(100)));
+        assert_eq!(*log.borrow(), vec!["once", "tick", "tick"]);
+        assert_eq!(timers.next_due(), None);
+
+        // A zero interval still repeats, at the minimum interval
+        timers.schedule_at(ms(100), Some(Duration::ZERO), || {});
+        assert!(timers.fire_due(ms(100)));
+        assert_eq!(timers.next_due(), Some(ms(100) + timer::MIN_INTERVAL));
+    }
+
+    #[test]
+    fn test_key_from_sdl() {
+        use sdl2::keyboard::Keycode;
+        assert_eq!(rendering::key_from_sdl(Keycode::A), Some(Key::A));
+        assert_eq!(rendering::key_from_sdl(Keycode::Num7), Some(Key::Num7));
+        assert_eq!(rendering::key_from_sdl(Keycode::Kp7), Some(Key::Num7));
+        assert_eq!(rendering::key_from_sdl(Keycode::Return), Some(Key::Enter));
+        assert_eq!(rendering::key_from_sdl(Keycode::KpEnter), Some(Key::Enter));
+        assert_eq!(rendering::key_from_sdl(Keycode::Left), Some(Key::Left));
+        assert_eq!(rendering::key_from_sdl(Keycode::RShift), Some(Key::Shift));
+        assert_eq!(rendering::key_from_sdl(Keycode::LCtrl), Some(Key::Control));
+        assert_eq!(rendering::key_from_sdl(Keycode::Escape), Some(Key::Escape));
+        assert_eq!(rendering::key_from_sdl(Keycode::CapsLock), None);
+    }
+
+    struct ScriptedBackend {
+        frames: std::collections::VecDeque<Vec<(backend::WindowId, backend::BackendEvent)>>,
+        renderer: testing::MockRenderer,
+    }
+
+    impl backend::EventSource for ScriptedBackend {
+        fn poll_events(&mut self, _timeout: std::time::Duration) -> Vec<(backend::WindowId, backend::BackendEvent)> {
+            self.frames.pop_front().unwrap_or_else(|| vec![(0, backend::BackendEvent::Quit)])
+        }
+    }
+
+    impl backend::Backend for ScriptedBackend {
+        fn draw_frame(&mut self, _window: backend::WindowId, _background: color::Color, draw: &mut dyn FnMut(&mut dyn components::Renderer)) {
+            self.renderer = testing::MockRenderer::new();
+            draw(&mut self.renderer);
+        }
+    }
+
+    #[test]
+    fn test_backend_runs_view_tree_end_to_end() {
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(true));
+        let count = state::State::new("0".to_string(), redraw.clone());
+        let label = components::Text::new(count.binding());
+        let mut vstack = components::VStack::new(10.0);
+        vstack.add_child(Box::new(components::Button::new("Add".to_string()).on_click(move || {
+            let current: i32 = count.get().parse().unwrap_or(0);
+            count.set((current + 1).to_string());
+        })));
+        vstack.add_child(Box::new(label));
+
+        let click = components::Event::Click { x: 5.0, y: 5.0 };
+        let mut backend = ScriptedBackend {
+            frames: vec![vec![], vec![(0, backend::BackendEvent::Input(click))]].into(),
+            renderer: testing::MockRenderer::new(),
+        };
+        backend::run(&mut backend, Box::new(vstack), &themes::Theme::default(), redraw).unwrap();
+
+        let texts: Vec<String> = backend.renderer.texts().into_iter().map(|(text, _, _)| text).collect();
+        assert!(texts.contains(&"1".to_string()), "{:?}", texts);
+    }
+
+    #[test]
+    fn test_headless_counter_app() {
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(true));
+        let count = state::State::new("0".to_string(), redraw.clone());
+        let label = components::Text::new(count.binding());
+        let mut vstack = components::VStack::new(10.0);
+        vstack.add_child(Box::new(components::Button::new("Add".to_string()).on_click(move || {
+            let current: i32 = count.get().parse().unwrap_or(0);
+            count.set((current + 1).to_string());
+        })));
+        vstack.add_child(Box::new(label));
+
+        let mut engine = testing::HeadlessEngine::new(Box::new(vstack), redraw);
+        assert!(engine.frame());
+        assert!(engine.texts().contains(&"0".to_string()));
+        // Nothing changed, so nothing is drawn
+        assert!(!engine.frame());
+
+        engine.click(5.0, 5.0);
+        assert!(engine.frame());
+        assert!(engine.texts().contains(&"1".to_string()));
+        assert_eq!(engine.frame_count(), 2);
+    }
+
+    #[test]
+    fn test_headless_timers_follow_virtual_clock() {
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(true));
+        let text = state::State::new("waiting".to_string(), redraw.clone());
+        let mut engine = testing::HeadlessEngine::new(Box::new(components::Text::new(text.binding())), redraw);
+        engine.schedule(std::time::Duration::from_secs(1), move || text.set("done".to_string()));
+
+        engine.advance(std::time::Duration::from_millis(500));
+        assert_eq!(engine.texts(), vec!["waiting".to_string()]);
+        assert!(engine.advance(std::time::Duration::from_millis(500)));
+        assert_eq!(engine.texts(), vec!["done".to_string()]);
+    }
+
+    #[test]
+    fn test_argb_to_rgba_repacks_rows() {
+        // 2x2 frame with 4 bytes of row padding
+        let pitch = 12;
+        let mut pixels = vec![0u8; pitch * 2];
+        let argb = [[0xff10_2030u32, 0x8040_5060], [0x0070_8090, 0xffa0_b0c0]];
+        for (row, values) in argb.iter().enumerate() {
+            for (column, value) in values.iter().enumerate() {
+                let offset = row * pitch + column * 4;
+                pixels[offset..offset + 4].copy_from_slice(&value.to_ne_bytes());
+            }
+        }
+        assert_eq!(
+            rendering::argb_to_rgba(&pixels, pitch, 2, 2),
+            vec![
+                0x10, 0x20, 0x30, 0xff, 0x40, 0x50, 0x60, 0x80,
+                0x70, 0x80, 0x90, 0x00, 0xa0, 0xb0, 0xc0, 0xff,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_accessibility_snapshot_of_labeled_button() {
+        use components::ViewExt;
+        let env = environment::Environment::default();
+        let mut vstack = components::VStack::new(10.0);
+        vstack.add_child(Box::new(components::Button::new("+".to_string()).accessibility_label("Increment")));
+        vstack.add_child(text_view("Count"));
+        vstack.render(&mut testing::MockRenderer::new(), &env, 0.0, 0.0);
+
+        // The stack itself is structural, so its children come out on top
+        let nodes = accessibility::snapshot(&vstack);
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[0].role, accessibility::Role::Button);
+        assert_eq!(nodes[0].label.as_deref(), Some("Increment"));
+        assert!(nodes[0].children.is_empty());
+        assert!(nodes[0].bounds.is_some_and(|b| b.width > 0.0));
+        assert_eq!(nodes[1].role, accessibility::Role::Text);
+        assert_eq!(nodes[1].label.as_deref(), Some("Count"));
+    }
+
+    #[test]
+    fn test_texture_cache_reuses_loaded_path() {
+        let mut cache = texture::TextureCache::new();
+        let decodes = std::cell::Cell::new(0);
+        let decode = |_: &std::path::Path| {
+            decodes.set(decodes.get() + 1);
+            Ok(())
+        };
+        let first = cache.load(std::path::Path::new("logo.png"), decode).unwrap();
+        let second = cache.load(std::path::Path::new("logo.png"), decode).unwrap();
+        let other = cache.load(std::path::Path::new("icon.png"), decode).unwrap();
+        assert!(first.is_some());
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+        assert_eq!(decodes.get(), 2);
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_texture_cache_remembers_failed_loads() {
+        let mut cache: texture::TextureCache<()> = texture::TextureCache::new();
+        let decodes = std::cell::Cell::new(0);
+        let decode = |_: &std::path::Path| {
+            decodes.set(decodes.get() + 1);
+            Err(error::UiError::RenderError("missing".to_string()))
+        };
+        assert!(cache.load(std::path::Path::new("missing.png"), decode).is_err());
+        assert!(matches!(cache.load(std::path::Path::new("missing.png"), decode), Ok(None)));
+        assert_eq!(decodes.get(), 1);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_image_from_missing_path_fails() {
+        assert!(components::Image::from_path("missing.png").is_err());
+    }
+
+    #[test]
+    fn test_image_draws_loaded_texture_at_its_size() {
+        let env = environment::Environment::default();
+        let mut image = components::Image::new(40.0, 30.0);
+        image.source = Some("logo.png".into());
+        let mut renderer = testing::MockRenderer::new();
+        image.render(&mut renderer, &env, 5.0, 5.0);
+        image.render(&mut renderer, &env, 5.0, 5.0);
+        assert_eq!(renderer.images.len(), 1);
+        assert!(matches!(
+            renderer.calls[1],
+            testing::DrawCall::Image { x: 5.0, y: 5.0, w: 40.0, h: 30.0, .. }
+        ));
+    }
+
+    #[test]
+    fn test_content_modes_place_wide_image_in_square_frame() {
+        let frame = components::Bounds::new(0.0, 0.0, 100.0, 100.0);
+        let wide = (200.0, 100.0);
+        assert_eq!(
+            components::ContentMode::Fit.content_rect(wide, frame),
+            components::Bounds::new(0.0, 25.0, 100.0, 50.0)
+        );
+        assert_eq!(
+            components::ContentMode::Fill.content_rect(wide, frame),
+            components::Bounds::new(-50.0, 0.0, 200.0, 100.0)
+        );
+        assert_eq!(components::ContentMode::Stretch.content_rect(wide, frame), frame);
+    }
+
+    #[test]
+    fn test_fill_image_is_clipped_to_frame() {
+        let env = environment::Environment::default();
+        let path = std::env::temp_dir().join(format!("oblivion-wide-{}.png", std::process::id()));
+        image::RgbaImage::new(20, 10).save(&path).unwrap();
+        let image = components::Image::from_path(&path)
+            .unwrap()
+            .width(10.0)
+            .height(10.0)
+            .content_mode(components::ContentMode::Fill);
+        let mut renderer = testing::MockRenderer::new();
+        image.render(&mut renderer, &env, 0.0, 0.0);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(renderer.calls[0], testing::DrawCall::PushClip { x: 0.0, y: 0.0, w: 10.0, h: 10.0 });
+        assert!(matches!(renderer.calls[1], testing::DrawCall::Image { x: -5.0, y: 0.0, w: 20.0, h: 10.0, .. }));
+        assert_eq!(renderer.calls[2], testing::DrawCall::PopClip);
+    }
+
+    #[test]
+    fn test_icon_cache_key_includes_size() {
+        let key = texture::TextureKey::icon("icons/save.svg", 16, 16);
+        assert_eq!(key, texture::TextureKey::icon("icons/save.svg", 16, 16));
+        assert_ne!(key, texture::TextureKey::icon("icons/save.svg", 32, 32));
+        assert_ne!(key, texture::TextureKey::File("icons/save.svg".into()));
+        assert_eq!(key.path(), std::path::Path::new("icons/save.svg"));
+    }
+
+    #[test]
+    fn test_icon_sizes_are_cached_separately() {
+        let mut renderer = testing::MockRenderer::new();
+        let env = environment::Environment::default();
+        components::Icon::new("icons/save.svg", 16.0).render(&mut renderer, &env, 0.0, 0.0);
+        components::Icon::new("icons/save.svg", 16.0).render(&mut renderer, &env, 0.0, 0.0);
+        assert_eq!(renderer.images.len(), 1);
+        components::Icon::new("icons/save.svg", 32.0).render(&mut renderer, &env, 0.0, 0.0);
+        assert_eq!(renderer.images.len(), 2);
+        // Rasterized for the display, drawn at the logical size
+        let hidpi = environment::Environment::default().with_scale_factor(1.5);
+        components::Icon::new("icons/save.svg", 16.0).render(&mut renderer, &hidpi, 0.0, 0.0);
+        assert_eq!(renderer.images.len(), 3);
+        assert!(matches!(renderer.calls.last(), Some(testing::DrawCall::Image { w: 16.0, h: 16.0, .. })));
+    }
+
+    #[test]
+    fn test_icon_cache_evicts_least_recently_used() {
+        let mut cache = texture::TextureCache::new().with_max_icons(2);
+        let load = |cache: &mut texture::TextureCache<u32>, size| {
+            cache.load_key(texture::TextureKey::icon("save.svg", size, size), |_| Ok(size)).unwrap().unwrap()
+        };
+        let small = load(&mut cache, 16);
+        load(&mut cache, 32);
+        load(&mut cache, 16);
+        // Resizing again evicts 32, which hasn't been used since it loaded
+        let large = load(&mut cache, 48);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.drain_evicted(), vec![32]);
+        assert!(cache.drain_evicted().is_empty());
+        assert_eq!(cache.get(small), Some(&16));
+        assert_eq!(cache.get(large), Some(&48));
+        // Files aren't evicted
+        cache.load(std::path::Path::new("logo.png"), |_| Ok(0)).unwrap();
+        assert_eq!(cache.len(), 3);
+    }
+
+    #[test]
+    fn test_rasterize_svg() {
+        let path = std::env::temp_dir().join(format!("oblivion-icon-{}.svg", std::process::id()));
+        std::fs::write(
+            &path,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10"><rect width="10" height="10" fill="red"/></svg>"#,
+        )
+        .unwrap();
+        let pixels = texture::rasterize_svg(&path, 4, 4).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(pixels.dimensions(), (4, 4));
+        assert_eq!(pixels.get_pixel(2, 2).0, [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_device_rect_rounds_negative_and_fractional_coordinates() {
+        use rendering::device_rect;
+
+        // Rounds to nearest rather than truncating toward zero
+        assert_eq!(device_rect(-200.4, -0.6, 50.0, 10.0, 1.0), (-200, -1, 50, 10));
+        assert_eq!(device_rect(10.6, 0.4, 20.0, 20.0, 1.0), (11, 0, 20, 20));
+        // Moving by a fraction never changes the size by more than the rounding
+        assert_eq!(device_rect(-0.5, 0.0, 10.0, 10.0, 1.0).2, 10);
+        assert_eq!(device_rect(-1.5, 0.0, 10.0, 10.0, 1.0).2, 10);
+        // Negative sizes are empty
+        assert_eq!(device_rect(5.0, 5.0, -3.0, -8.0, 1.0), (5, 5, 0, 0));
+        assert_eq!(device_rect(0.25, 0.25, 0.5, 0.5, 2.0), (1, 1, 1, 1));
+    }
+
+    #[test]
+    fn test_drawable_rect_skips_empty_and_negative_sizes() {
+        use rendering::drawable_rect;
+
+        assert!(drawable_rect(10.0, 10.0, 0.0, 20.0, 1.0).is_none());
+        assert!(drawable_rect(10.0, 10.0, 20.0, 0.0, 1.0).is_none());
+        assert!(drawable_rect(10.0, 10.0, 0.3, 20.0, 1.0).is_none());
+        assert!(drawable_rect(10.0, 10.0, -5.0, -1e9, 1.0).is_none());
+        assert!(drawable_rect(-1e9, -1e9, -1e9, 5.0, 2.0).is_none());
+        let rect = drawable_rect(10.0, 10.0, 0.3, 20.0, 4.0).unwrap();
+        assert_eq!((rect.width(), rect.height()), (1, 80));
+    }
+
+    #[test]
+    fn test_flex_children_split_spare_room_by_weight() {
+        use components::ViewExt;
+        let env = environment::Environment::default();
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(false));
+        let label = |s: &str| components::Text::new(state::State::new(s.to_string(), redraw.clone()).binding());
+
+        // 300 wide, minus the 100-wide button, leaves 200 for two equal shares
+        let mut row = components::HStack::new(0.0).width(300.0);
+        row.add_child(Box::new(label("a").flex(1.0)));
+        row.add_child(Box::new(label("b").flex(1.0)));
+        row.add_child(Box::new(components::Button::new("Go".to_string())));
+        let mut renderer = testing::MockRenderer::new();
+        row.render(&mut renderer, &env, 0.0, 0.0);
+        let xs: Vec<f32> = renderer.texts().into_iter().map(|(_, x, _)| x).collect();
+        assert_eq!(xs, vec![0.0, 100.0, 205.0]);
+
+        let mut column = components::VStack::new(0.0).height(400.0);
+        column.add_child(Box::new(label("a").flex(1.0)));
+        column.add_child(Box::new(label("b").flex(3.0)));
+        column.add_child(Box::new(label("c")));
+        let mut renderer = testing::MockRenderer::new();
+        column.render(&mut renderer, &env, 0.0, 0.0);
+        let text_height = label("c").size(&env).1;
+        let share = (400.0 - text_height) / 4.0;
+        let ys: Vec<f32> = renderer.texts().into_iter().map(|(_, _, y)| y).collect();
+        assert_eq!(ys, vec![0.0, share, share * 4.0]);
+    }
+
+    #[test]
+    fn test_panel_sizes_border_to_padded_child() {
+        let env = environment::Environment::default();
+        let (child_width, child_height) = text_view("Hi").size(&env);
+        let panel = components::Panel::new(2.0, 8.0).child(text_view("Hi"));
+        assert_eq!(panel.size(&env), (child_width + 16.0, child_height + 16.0));
+
+        let mut renderer = testing::MockRenderer::new();
+        panel.render(&mut renderer, &env, 10.0, 20.0);
+        assert_eq!(renderer.texts()[0].1, 18.0);
+        assert_eq!(renderer.outlines(), vec![(10.0, 20.0, child_width + 16.0, child_height + 16.0, 2.0)]);
+        // Outline only: no filled block over the child
+        assert!(renderer.rects().is_empty());
+    }
+
+    #[test]
+    fn test_menu_bar_hit_tests_measured_items() {
+        let env = environment::Environment::default();
+        let selected = std::rc::Rc::new(std::cell::Cell::new(None));
+        let seen = selected.clone();
+        let items = vec!["File".to_string(), "Edit and more".to_string(), "Go".to_string()];
+        let mut menu = components::MenuBar::new(items.clone()).on_select(move |index| seen.set(Some(index)));
+        let mut renderer = testing::MockRenderer::new();
+        menu.render(&mut renderer, &env, 0.0, 0.0);
+
+        let width = |s: &str| components::measure_text(s, env.font_size).0 + 16.0;
+        let third_start = width("File") + width("Edit and more");
+        // Labels no longer overlap
+        let xs: Vec<f32> = renderer.texts().into_iter().map(|(_, x, _)| x).collect();
+        assert_eq!(xs, vec![8.0, width("File") + 8.0, third_start + 8.0]);
+
+        menu.handle_event(&components::Event::Click { x: third_start + 2.0, y: 5.0 });
+        assert_eq!(selected.get(), Some(2));
+        // Inside the long second item, well past the old 50px slots
+        menu.handle_event(&components::Event::Click { x: third_start - 2.0, y: 5.0 });
+        assert_eq!(selected.get(), Some(1));
+        assert_eq!(menu.item_at(third_start + width("Go") + 1.0), None);
+    }
+
+    #[test]
+    fn test_menu_item_tree_nests_submenus_and_separators() {
+        use components::MenuItem;
+        let menu = components::MenuBar::new(vec![
+            MenuItem::submenu("File", vec![MenuItem::new("Open"), MenuItem::separator(), MenuItem::submenu("Recent", vec![MenuItem::new("a.txt")])]),
+            MenuItem::new("Help"),
+        ]);
+        assert_eq!(menu.items.len(), 2);
+        assert!(menu.item(&[0]).unwrap().has_submenu());
+        assert!(menu.item(&[0, 1]).unwrap().is_separator);
+        assert_eq!(menu.item(&[0, 2, 0]).unwrap().title, "a.txt");
+        assert!(!menu.item(&[1]).unwrap().has_submenu());
+        assert!(menu.item(&[0, 3]).is_none());
+        assert!(menu.item(&[]).is_none());
+    }
+
+    #[test]
+    fn test_menu_bar_click_path_fires_leaf_action() {
+        use components::MenuItem;
+        let env = environment::Environment::default();
+        let fired = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let (open, recent) = (fired.clone(), fired.clone());
+        let mut menu = components::MenuBar::new(vec![MenuItem::submenu(
+            "File",
+            vec![
+                MenuItem::new("Open").action(move || open.borrow_mut().push("open")),
+                MenuItem::separator(),
+                MenuItem::submenu("Recent", vec![MenuItem::new("a.txt").action(move || recent.borrow_mut().push("a.txt"))]),
+            ],
+        )]);
+        let mut renderer = testing::MockRenderer::new();
+        menu.render(&mut renderer, &env, 0.0, 0.0);
+
+        // Parents toggle their submenus; separators do nothing
+        menu.activate(&[0]);
+        assert_eq!(menu.open_path(), &[0]);
+        menu.activate(&[0, 1]);
+        assert_eq!(menu.open_path(), &[0]);
+        menu.activate(&[0, 2]);
+        assert_eq!(menu.open_path(), &[0, 2]);
+        menu.activate(&[0, 2, 0]);
+        assert_eq!(*fired.borrow(), vec!["a.txt"]);
+        assert!(menu.open_path().is_empty());
+
+        // The same by clicking: open File, then hit the first dropdown row
+        let row_height = components::measure_text("", env.font_size).1;
+        menu.handle_event(&components::Event::Click { x: 5.0, y: 5.0 });
+        let dropdown = menu.overlay_bounds().unwrap();
+        assert_eq!((dropdown.x, dropdown.y), (0.0, row_height));
+        menu.render_overlay(&mut renderer, &env);
+        menu.handle_event(&components::Event::Click { x: 10.0, y: row_height + 2.0 });
+        assert_eq!(*fired.borrow(), vec!["a.txt", "open"]);
+        assert!(menu.overlay_bounds().is_none());
+
+        // An outside click closes an open menu without firing anything
+        menu.handle_event(&components::Event::Click { x: 5.0, y: 5.0 });
+        menu.handle_event(&components::Event::Click { x: 500.0, y: 500.0 });
+        assert!(menu.open_path().is_empty());
+        assert_eq!(fired.borrow().len(), 2);
+    }
+
+    #[test]
+    fn test_context_menu_opens_on_right_click_and_closes_outside() {
+        use components::{Canvas, MenuItem, ViewExt};
+        let env = environment::Environment::default();
+        let copied = std::rc::Rc::new(std::cell::Cell::new(0));
+        let count = copied.clone();
+        let mut view = Canvas::new(100.0, 50.0).context_menu(vec![
+            MenuItem::new("Copy").action(move || count.set(count.get() + 1)),
+            MenuItem::separator(),
+            MenuItem::new("Paste"),
+        ]);
+        let mut renderer = testing::MockRenderer::new();
+        view.render(&mut renderer, &env, 0.0, 0.0);
+
+        // Right-clicks outside the view don't open anything
+        view.handle_event(&components::Event::RightClick { x: 150.0, y: 10.0 });
+        assert!(view.overlay_bounds().is_none());
+
+        view.handle_event(&components::Event::RightClick { x: 20.0, y: 10.0 });
+        let menu = view.overlay_bounds().unwrap();
+        assert_eq!((menu.x, menu.y), (20.0, 10.0));
+        view.render_overlay(&mut renderer, &env);
+        assert!(renderer.texts().iter().any(|(text, x, y)| text == "Copy" && *x == 28.0 && *y == 10.0));
+
+        view.handle_event(&components::Event::Click { x: 300.0, y: 300.0 });
+        assert!(!view.modifier.is_open());
+        assert_eq!(copied.get(), 0);
+
+        // Picking an item runs it and closes the menu
+        view.handle_event(&components::Event::RightClick { x: 20.0, y: 10.0 });
+        view.handle_event(&components::Event::Click { x: 30.0, y: 12.0 });
+        assert_eq!(copied.get(), 1);
+        assert!(!view.modifier.is_open());
+    }
+
+    #[test]
+    fn test_set_if_changed_skips_redraw_for_equal_values() {
+        let redraw_trigger = std::rc::Rc::new(std::cell::RefCell::new(false));
+        let count = state::State::new(0, redraw_trigger.clone());
+        let writes = std::rc::Rc::new(std::cell::Cell::new(0));
+        let seen = writes.clone();
+        count.on_change(move |_| seen.set(seen.get() + 1));
+
+        assert!(count.set_if_changed(1));
+        assert!(*redraw_trigger.borrow());
+        *redraw_trigger.borrow_mut() = false;
+        assert!(!count.set_if_changed(1));
+        assert!(!*redraw_trigger.borrow());
+        assert_eq!(writes.get(), 1);
+
+        // Bindings compare too; plain `set` still always writes
+        let binding = count.binding();
+        assert!(!binding.set_if_changed(1));
+        assert!(!*redraw_trigger.borrow());
+        count.set(1);
+        assert!(*redraw_trigger.borrow());
+        assert_eq!(writes.get(), 2);
+    }
+
+    #[test]
+    fn test_dirty_region_merges_overlapping_rects() {
+        use components::Bounds;
+        let mut region = damage::DirtyRegion::new();
+        region.add(Bounds::new(0.0, 0.0, 10.0, 10.0));
+        region.add(Bounds::new(50.0, 50.0, 10.0, 10.0));
+        // Overlaps neither yet; the next rect bridges it to the first
+        region.add(Bounds::new(20.0, 0.0, 10.0, 10.0));
+        assert_eq!(region.rects().len(), 3);
+        region.add(Bounds::new(5.0, 5.0, 20.0, 2.0));
+        region.add(Bounds::new(0.0, 0.0, 0.0, 40.0));
+        assert_eq!(region.rects().len(), 2);
+        assert!(region.rects().contains(&Bounds::new(0.0, 0.0, 30.0, 10.0)));
+        assert!(region.rects().contains(&Bounds::new(50.0, 50.0, 10.0, 10.0)));
+        // Touching edges don't overlap
+        region.add(Bounds::new(60.0, 50.0, 5.0, 5.0));
+        assert_eq!(region.rects().len(), 3);
+        assert_eq!(region.bounds(), Some(Bounds::new(0.0, 0.0, 65.0, 60.0)));
+        region.clear();
+        assert_eq!(region.bounds(), None);
+    }
+
+    #[test]
+    fn test_damage_tracker_collects_view_damage() {
+        let env = environment::Environment::default();
+        let label = state::State::new("ab".to_string(), std::rc::Rc::new(std::cell::RefCell::new(false)));
+        let mut vstack = components::VStack::new(10.0);
+        vstack.add_child(Box::new(components::Button::new("Add".to_string())));
+        vstack.add_child(Box::new(components::Text::new(label.binding())));
+        let mut tracker = damage::DamageTracker::new();
+        let frame = |tracker: &mut damage::DamageTracker, env: &environment::Environment| {
+            let damage = tracker.take(&vstack, env);
+            vstack.render(&mut testing::MockRenderer::new(), env, 0.0, 0.0);
+            damage
+        };
+
+        assert_eq!(frame(&mut tracker, &env), damage::Damage::Full);
+        assert_eq!(frame(&mut tracker, &env), damage::Damage::None);
+        // Same width: only the label repaints, not the button
+        label.set("cd".to_string());
+        let label_frame = vstack.children[1].layout_bounds().unwrap();
+        assert!(label_frame.y > 0.0);
+        assert_eq!(frame(&mut tracker, &env), damage::Damage::Region(label_frame));
+        // A wider label moves what's around it
+        label.set("wider".to_string());
+        assert_eq!(frame(&mut tracker, &env), damage::Damage::Full);
+
+        tracker.add(components::Bounds::new(1.0, 2.0, 3.0, 4.0));
+        assert_eq!(frame(&mut tracker, &env), damage::Damage::Region(components::Bounds::new(1.0, 2.0, 3.0, 4.0)));
+        tracker.invalidate();
+        assert_eq!(frame(&mut tracker, &env), damage::Damage::Full);
+        let dark = environment::Environment::new(themes::Theme { is_dark: true, ..themes::Theme::default() });
+        assert_eq!(frame(&mut tracker, &dark), damage::Damage::Full);
+        assert_eq!(frame(&mut tracker, &dark), damage::Damage::None);
+    }
+
+    #[test]
+    fn test_keyed_for_each_keeps_row_animation_across_rebuilds() {
+        let env = environment::Environment::default();
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(false));
+        let items = state::State::new(vec![(1, "one".to_string())], redraw.clone());
+        let row_redraw = redraw.clone();
+        let mut list = components::ForEach::keyed(items.binding(), |item: &(u32, String)| item.0, move |item| {
+            let label = components::Text::new(state::State::new(item.1.clone(), row_redraw.clone()).binding());
+            Box::new(components::AnimatedView::new(Box::new(label), 0.0, 100.0, 1.0)) as Box<dyn View>
+        });
+        let mut renderer = testing::MockRenderer::new();
+        list.update(0.5);
+        list.render(&mut renderer, &env, 0.0, 0.0);
+        assert_eq!(renderer.texts(), vec![("one".to_string(), 50.0, 0.0)]);
+        let id = list.node_ids()[0];
+
+        // Rebuilt from new data: the new row starts over, the old one carries on
+        items.set(vec![(2, "two".to_string()), (1, "one".to_string())]);
+        list.update(0.0);
+        renderer.clear();
+        list.render(&mut renderer, &env, 0.0, 0.0);
+        assert_eq!(renderer.texts(), vec![("two".to_string(), 0.0, 0.0), ("one".to_string(), 50.0, 50.0)]);
+        assert_eq!(list.node_ids()[1], id);
+    }
+
+    #[test]
+    fn test_window_stacks_children_by_height_and_spacing() {
+        let env = environment::Environment::default();
+        let mut window = components::Window::new("Main".to_string(), 640, 480).spacing(12.0);
+        window.add_child(Box::new(components::Canvas::new(100.0, 30.0)));
+        window.add_child(Box::new(components::Canvas::new(100.0, 80.0)));
+        let mut renderer = testing::MockRenderer::new();
+        window.render(&mut renderer, &env, 0.0, 0.0);
+
+        let (_, title_height) = components::measure_text("Main", env.title_font_size);
+        // Skip the window background
+        let rects = &renderer.rects()[1..];
+        assert_eq!(rects[0], (0.0, title_height, 100.0, 30.0));
+        assert_eq!(rects[1], (0.0, title_height + 30.0 + 12.0, 100.0, 80.0));
+    }
+
+    #[test]
+    fn test_disabled_button_is_dimmed_and_ignores_clicks() {
+        use components::{Button, Event, ViewExt};
+        let env = environment::Environment::default();
+        let clicks = std::rc::Rc::new(std::cell::Cell::new(0));
+        let counter = clicks.clone();
+        let disabled = state::State::new(true, std::rc::Rc::new(std::cell::RefCell::new(false)));
+        let mut button = Button::new("Save".to_string())
+            .on_click(move || counter.set(counter.get() + 1))
+            .disabled(disabled.binding());
+        let mut renderer = testing::MockRenderer::new();
+        button.render(&mut renderer, &env, 0.0, 0.0);
+        assert!(renderer.text_colors().iter().all(|color| color.a == 102));
+
+        button.handle_event(&Event::Click { x: 5.0, y: 5.0 });
+        button.handle_event(&Event::KeyDown(Key::Enter));
+        assert_eq!(clicks.get(), 0);
+        // Disabled views can't take focus either
+        let mut focus = focus::FocusManager::new();
+        focus.collect_tab_order(&button);
+        focus.focus_next(&mut button);
+        assert_eq!(focus.focused(), None);
+
+        disabled.set(false);
+        assert!(matches!(button.damage(), damage::Damage::Region(_)));
+        renderer.clear();
+        button.render(&mut renderer, &env, 0.0, 0.0);
+        assert!(renderer.text_colors().iter().all(|color| color.a == 255));
+        button.handle_event(&Event::Click { x: 5.0, y: 5.0 });
+        assert_eq!(clicks.get(), 1);
+        assert!(!Button::new("Fixed".to_string()).disabled(true).is_enabled());
+    }
+
+    #[test]
+    fn test_on_change_of_fires_once_per_changed_value() {
+        use components::{Canvas, ViewExt};
+        let env = environment::Environment::default();
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(false));
+        let query = state::State::new("a".to_string(), redraw);
+        let seen = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let log = seen.clone();
+        let view = Canvas::new(10.0, 10.0).on_change_of(query.binding(), move |value: String| log.borrow_mut().push(value));
+        let mut renderer = testing::MockRenderer::new();
+
+        view.render(&mut renderer, &env, 0.0, 0.0);
+        assert!(seen.borrow().is_empty());
+        query.set("ab".to_string());
+        view.render(&mut renderer, &env, 0.0, 0.0);
+        view.render(&mut renderer, &env, 0.0, 0.0);
+        assert_eq!(*seen.borrow(), vec!["ab".to_string()]);
+        // Writing the same value again is not a change
+        query.set("ab".to_string());
+        view.render(&mut renderer, &env, 0.0, 0.0);
+        assert_eq!(seen.borrow().len(), 1);
+    }
+
+    #[test]
+    fn test_shortcuts_match_exact_modifiers() {
+        use shortcut::{Modifiers, ShortcutRegistry};
+        let saves = std::rc::Rc::new(std::cell::Cell::new(0));
+        let counter = saves.clone();
+        let mut shortcuts = ShortcutRegistry::new();
+        shortcuts.add(Modifiers::CONTROL, Key::S, move || counter.set(counter.get() + 1));
+        shortcuts.add(Modifiers::CONTROL | Modifiers::SHIFT, Key::S, || {});
+
+        assert!(shortcuts.matches(Modifiers::CONTROL, Key::S));
+        assert!(!shortcuts.matches(Modifiers::NONE, Key::S));
+        assert!(!shortcuts.matches(Modifiers::CONTROL | Modifiers::ALT, Key::S));
+        assert!(!shortcuts.matches(Modifiers::CONTROL, Key::D));
+        // Only the exact combination runs
+        assert!(shortcuts.trigger(Modifiers::CONTROL, Key::S));
+        assert!(shortcuts.trigger(Modifiers::CONTROL | Modifiers::SHIFT, Key::S));
+        assert!(!shortcuts.trigger(Modifiers::NONE, Key::S));
+        assert_eq!(saves.get(), 1);
+
+        let keymod = sdl2::keyboard::Mod::RCTRLMOD | sdl2::keyboard::Mod::NUMMOD;
+        assert_eq!(rendering::modifiers_from_sdl(keymod), Modifiers::CONTROL);
+    }
+
+    #[test]
+    fn test_tab_view_switches_on_header_click() {
+        let env = environment::Environment::default();
+        let selected = state::State::new(0, std::rc::Rc::new(std::cell::RefCell::new(false)));
+        let mut tabs = components::TabView::with_tabs(
+            vec![("General".to_string(), text_view("General settings")), ("Advanced".to_string(), text_view("Advanced settings"))],
+            selected.binding(),
+        );
+        let mut renderer = testing::MockRenderer::new();
+        tabs.render(&mut renderer, &env, 0.0, 0.0);
+        let texts: Vec<String> = renderer.texts().into_iter().map(|(text, _, _)| text).collect();
+        assert_eq!(texts, vec!["General", "Advanced", "General settings"]);
+
+        // Below the header row is content, not a tab
+        let general_width = components::measure_text("General", env.font_size).0 + 24.0;
+        tabs.handle_event(&components::Event::Click { x: general_width + 5.0, y: 40.0 });
+        assert_eq!(selected.get(), 0);
+        tabs.handle_event(&components::Event::Click { x: general_width + 5.0, y: 5.0 });
+        assert_eq!(selected.get(), 1);
+
+        renderer.clear();
+        tabs.render(&mut renderer, &env, 0.0, 0.0);
+        let texts: Vec<String> = renderer.texts().into_iter().map(|(text, _, _)| text).collect();
+        assert_eq!(texts, vec!["General", "Advanced", "Advanced settings"]);
+        // The selected header is highlighted
+        assert_eq!(renderer.rects()[0], (general_width, 0.0, components::measure_text("Advanced", env.font_size).0 + 24.0, 30.0));
+    }
+
+    #[test]
+    fn test_disclosure_group_shows_content_only_when_expanded() {
+        let env = environment::Environment::default();
+        let expanded = state::State::new(false, std::rc::Rc::new(std::cell::RefCell::new(false)));
+        let mut group = components::DisclosureGroup::new("Network".to_string(), expanded.binding(), Box::new(components::Canvas::new(200.0, 40.0)));
+        let (header_width, header_height) = components::measure_text("> Network", env.font_size);
+        assert_eq!(group.size(&env), (header_width, header_height));
+        let mut renderer = testing::MockRenderer::new();
+        group.render(&mut renderer, &env, 0.0, 0.0);
+        assert!(renderer.rects().is_empty());
+        assert!(group.children().is_empty());
+
+        // Clicking the header expands it
+        group.handle_event(&components::Event::Click { x: 2.0, y: 2.0 });
+        assert!(expanded.get());
+        assert_eq!(group.size(&env), (216.0, header_height + 40.0));
+        renderer.clear();
+        group.render(&mut renderer, &env, 0.0, 0.0);
+        assert_eq!(renderer.rects(), vec![(16.0, header_height, 200.0, 40.0)]);
+        assert_eq!(group.layout_bounds(), Some(components::Bounds::new(0.0, 0.0, 216.0, header_height + 40.0)));
+
+        expanded.set(false);
+        renderer.clear();
+        group.render(&mut renderer, &env, 0.0, 0.0);
+        assert!(renderer.rects().is_empty());
+        assert_eq!(group.layout_bounds(), Some(components::Bounds::new(0.0, 0.0, header_width, header_height)));
+    }
+
+    #[test]
+    fn test_window_offsets_children_by_safe_area() {
+        use components::{Canvas, EdgeInsets, ViewExt};
+        let env = environment::Environment::default();
+        let mut window = components::Window::new("Main".to_string(), 640, 480).safe_area_insets(EdgeInsets::new(20.0, 10.0, 30.0, 40.0));
+        window.add_child(Box::new(Canvas::new(100.0, 30.0)));
+        window.add_child(Box::new(Canvas::new(640.0, 480.0).ignores_safe_area()));
+        let mut renderer = testing::MockRenderer::new();
+        window.render(&mut renderer, &env, 0.0, 0.0);
+
+        let (_, title_height) = components::measure_text("Main", env.title_font_size);
+        assert_eq!(renderer.texts()[0], ("Main".to_string(), 10.0, 20.0));
+        let rects = &renderer.rects()[1..];
+        assert_eq!(rects[0], (10.0, 20.0 + title_height, 100.0, 30.0));
+        assert_eq!(rects[1], (0.0, 0.0, 640.0, 480.0));
+        // The bottom and trailing insets bound what the safe children can draw
+        let safe_clip = testing::DrawCall::PushClip { x: 10.0, y: 20.0, w: 590.0, h: 430.0 };
+        assert_eq!(renderer.calls.iter().filter(|call| **call == safe_clip).count(), 2);
+    }
+
+    #[test]
+    fn test_relative_width_resolves_against_container() {
+        use components::{Canvas, ViewExt};
+        let env = environment::Environment::default();
+        let half = Canvas::new(10.0, 20.0).relative_width(0.5);
+        // Without a container there is nothing to be relative to
+        assert_eq!(half.size(&env), (10.0, 20.0));
+        assert_eq!(half.size(&env.with_available_size(Some(400.0), None)), (200.0, 20.0));
+
+        let mut hstack = components::HStack::new(0.0).width(400.0);
+        hstack.add_child(Box::new(half));
+        hstack.add_child(Box::new(Canvas::new(30.0, 20.0)));
+        let mut renderer = testing::MockRenderer::new();
+        hstack.render(&mut renderer, &env, 0.0, 0.0);
+        assert_eq!(renderer.rects()[1], (200.0, 0.0, 30.0, 20.0));
+    }
+
+    #[test]
+    fn test_list_selection_moves_with_arrows_and_clamps() {
+        use components::Event;
+        let selected = state::State::new(0usize, std::rc::Rc::new(std::cell::RefCell::new(false)));
+        let mut list = components::List::new(vec![text_view("a"), text_view("b"), text_view("c")]).selection(selected.binding());
+        list.handle_event(&Event::KeyDown(Key::Up));
+        assert_eq!(selected.get(), 0);
+        list.handle_event(&Event::KeyDown(Key::Down));
+        list.handle_event(&Event::KeyDown(Key::Down));
+        list.handle_event(&Event::KeyDown(Key::Down));
+        assert_eq!(selected.get(), 2);
+        list.handle_event(&Event::KeyDown(Key::Up));
+        assert_eq!(selected.get(), 1);
+
+        let mut renderer = testing::MockRenderer::new();
+        list.render(&mut renderer, &environment::Environment::default(), 0.0, 0.0);
+        let width = list.size(&environment::Environment::default()).0;
+        assert_eq!(renderer.rects()[0], (0.0, 30.0, width, 30.0));
+    }
+
+    #[test]
+    fn test_grid_selection_moves_with_arrows_and_clamps() {
+        use components::Event;
+        let selected = state::State::new((0usize, 0usize), std::rc::Rc::new(std::cell::RefCell::new(false)));
+        let mut grid = components::Grid::new(2, 3, 0.0).selection(selected.binding());
+        grid.handle_event(&Event::KeyDown(Key::Left));
+        grid.handle_event(&Event::KeyDown(Key::Up));
+        assert_eq!(selected.get(), (0, 0));
+        for _ in 0..5 {
+            grid.handle_event(&Event::KeyDown(Key::Right));
+            grid.handle_event(&Event::KeyDown(Key::Down));
+        }
+        assert_eq!(selected.get(), (1, 2));
+        grid.handle_event(&Event::KeyDown(Key::Left));
+        assert_eq!(selected.get(), (1, 1));
+    }
+
+    #[test]
+    fn test_list_reorders_dragged_row() {
+        use components::Event;
+        let mut values = vec!["a", "b", "c"];
+        components::reorder(&mut values, 0, 2);
+        assert_eq!(values, ["b", "c", "a"]);
+        components::reorder(&mut values, 2, 0);
+        assert_eq!(values, ["a", "b", "c"]);
+
+        let names = state::State::new(vec!["a", "b", "c"], std::rc::Rc::new(std::cell::RefCell::new(false)));
+        let mut list = components::List::new(vec![text_view("a"), text_view("b"), text_view("c")]).reorderable(names.binding());
+        let env = environment::Environment::default();
+        list.render(&mut testing::MockRenderer::new(), &env, 0.0, 0.0);
+
+        // Press on the first row and drag past the third
+        list.handle_event(&Event::Click { x: 5.0, y: 10.0 });
+        list.handle_event(&Event::Drag { dx: 0.0, dy: 70.0 });
+        assert_eq!(list.drop_index(), Some(2));
+        list.handle_event(&Event::MouseUp { x: 5.0, y: 80.0 });
+        assert_eq!(names.get(), vec!["b", "c", "a"]);
+        assert_eq!(list.drop_index(), None);
+    }
+
+    #[test]
+    fn test_click_in_overlap_only_reaches_top_view() {
+        let fired = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let mut zstack = components::ZStack::new();
+        for name in ["bottom", "top"] {
+            let log = fired.clone();
+            zstack.add_child(Box::new(components::Button::new(name.to_string()).on_click(move || log.borrow_mut().push(name))));
+        }
+        let mut renderer = testing::MockRenderer::new();
+        zstack.render(&mut renderer, &environment::Environment::default(), 0.0, 0.0);
+        assert!(zstack.handle_event(&components::Event::Click { x: 5.0, y: 5.0 }));
+        assert_eq!(*fired.borrow(), vec!["top"]);
+        // Events nobody consumes still reach every view
+        assert!(!zstack.handle_event(&components::Event::Click { x: 500.0, y: 500.0 }));
+    }
+
+    #[test]
+    fn test_routed_click_hits_frontmost_view_first() {
+        let fired = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let mut zstack = components::ZStack::new();
+        for name in ["bottom", "top"] {
+            let log = fired.clone();
+            zstack.add_child(Box::new(components::Button::new(name.to_string()).on_click(move || log.borrow_mut().push(name))));
+        }
+        let mut renderer = testing::MockRenderer::new();
+        zstack.render(&mut renderer, &environment::Environment::default(), 0.0, 0.0);
+
+        assert_eq!(focus::hit_test(&zstack, 5.0, 5.0), vec![vec![1], vec![0]]);
+        let mut focus = focus::FocusManager::new();
+        focus.route(&mut zstack, &components::Event::Click { x: 5.0, y: 5.0 });
+        assert_eq!(*fired.borrow(), vec!["top"]);
+    }
+
+    #[test]
+    fn test_layout_snapshot_lists_bounds_of_each_view() {
+        use components::Canvas;
+        let env = environment::Environment::default();
+        let mut vstack = components::VStack::new(10.0);
+        vstack.add_child(Box::new(components::Button::new("OK".to_string())));
+        vstack.add_child(Box::new(Canvas::new(50.0, 20.0)));
+        vstack.render(&mut testing::MockRenderer::new(), &env, 5.0, 5.0);
+
+        let (button_width, button_height) = vstack.children[0].size(&env);
+        let snapshot = debug::layout_snapshot(&vstack, &env);
+        let expected = format!(
+            "VStack (5, 5, {}, {})\n  Button (5, 5, {}, {})\n  Canvas (5, {}, 50, 20)\n",
+            button_width.max(50.0),
+            button_height + 30.0,
+            button_width,
+            button_height,
+            5.0 + button_height + 10.0
+        );
+        assert_eq!(snapshot.to_string(), expected);
+
+        let mut renderer = testing::MockRenderer::new();
+        debug::draw_layout_outlines(&mut renderer, &vstack);
+        assert_eq!(renderer.calls.len(), 3);
+    }
+
+    #[test]
+    fn test_layout_snapshot_places_views_that_record_no_frame() {
+        use components::Renderer;
+        use environment::Environment;
+
+        struct Probe;
+        impl View for Probe {
+            fn render(&self, _renderer: &mut dyn Renderer, _env: &Environment, _x: f32, _y: f32) {}
+            fn handle_event(&mut self, _event: &components::Event) -> bool {
+                false
+            }
+            fn size(&self, _env: &Environment) -> (f32, f32) {
+                (10.0, 10.0)
+            }
+        }
+
+        let env = Environment::default();
+        let mut zstack = components::ZStack::new();
+        zstack.add_child(Box::new(Probe));
+        zstack.render(&mut testing::MockRenderer::new(), &env, 7.0, 9.0);
+        let snapshot = debug::layout_snapshot(&zstack, &env);
+        assert_eq!(snapshot.children[0].bounds, components::Bounds::new(7.0, 9.0, 10.0, 10.0));
+    }
+
+    #[test]
+    fn test_to_rso_round_trips_through_load_rso() {
+        let source = "VStack { Text('It\\'s') HStack(4) { Button('OK') Spacer Divider } ZStack }";
+        let view = rso::load_rso(source, &rso::RsoRegistry::new()).unwrap();
+        let written = rso::to_rso(view.as_ref());
+        assert_eq!(written, "VStack(10) {\n    Text('It\\'s')\n    HStack(4) {\n        Button('OK')\n        Spacer(10)\n        Divider\n    }\n    ZStack\n}\n");
+        let reloaded = rso::load_rso(&written, &rso::RsoRegistry::new()).unwrap();
+        assert_eq!(rso::to_rso(reloaded.as_ref()), written);
+        assert!(rso::load_rso("Text('a') { Button('b') }", &rso::RsoRegistry::new()).is_err());
+        assert!(rso::load_rso("Slider('loud')", &rso::RsoRegistry::new()).is_err());
+    }
+
+    #[test]
+    fn test_rso_resolves_bindings_and_actions_by_name() {
+        let counter = state::State::new("0".to_string(), std::rc::Rc::new(std::cell::RefCell::new(false)));
+        let count = counter.binding();
+        let registry = rso::RsoRegistry::new()
+            .binding("counter", counter.binding())
+            .action("increment", move || count.set((count.get().parse::<i32>().unwrap() + 1).to_string()));
+        let mut view = rso::load_rso(r#"VStack { Text(bind: "counter") Button("Inc", action: "increment") }"#, &registry).unwrap();
+        let texts = |view: &dyn View| {
+            let mut renderer = testing::MockRenderer::new();
+            view.render(&mut renderer, &environment::Environment::default(), 0.0, 0.0);
+            renderer.texts().into_iter().map(|(text, ..)| text).collect::<Vec<_>>()
+        };
+        assert_eq!(texts(view.as_ref()), vec!["0", "Inc"]);
+
+        counter.set("41".to_string());
+        assert_eq!(texts(view.as_ref()), vec!["41", "Inc"]);
+        let (x, y) = {
+            let button = view.children()[1].bounds().unwrap();
+            (button.x + 1.0, button.y + 1.0)
+        };
+        view.handle_event(&components::Event::Click { x, y });
+        assert_eq!(texts(view.as_ref()), vec!["42", "Inc"]);
+
+        for source in [r#"Text(bind: "missing")"#, r#"Button("Inc", action: "missing")"#, r#"Text("a", size: 3)"#] {
+            assert!(matches!(rso::load_rso(source, &registry), Err(error::UiError::ParseError(_))), "{}", source);
+        }
+    }
+
+    #[test]
+    fn test_cached_layout_measures_unchanged_subtree_once() {
+        use components::{Renderer, VStack, ViewExt};
+        use environment::Environment;
+
+        // Counts how often it is measured
+        struct SizeProbe(std::rc::Rc<std::cell::Cell<u32>>);
+        impl View for SizeProbe {
+            fn render(&self, _renderer: &mut dyn Renderer, _env: &Environment, _x: f32, _y: f32) {}
+            fn handle_event(&mut self, _event: &components::Event) -> bool {
+                false
+            }
+            fn size(&self, _env: &Environment) -> (f32, f32) {
+                self.0.set(self.0.get() + 1);
+                (40.0, 20.0)
+            }
+        }
+
+        let measured = std::rc::Rc::new(std::cell::Cell::new(0));
+        let label = state::State::new("a".to_string(), std::rc::Rc::new(std::cell::RefCell::new(false)));
+        let mut stack = VStack::new(0.0);
+        stack.add_child(Box::new(SizeProbe(measured.clone()).cached_layout(&[&label])));
+        let env = Environment::default();
+        let render = |stack: &VStack| stack.render(&mut testing::MockRenderer::new(), &env, 0.0, 0.0);
+
+        render(&stack);
+        render(&stack);
+        assert_eq!(measured.get(), 1);
+
+        label.set("b".to_string());
+        render(&stack);
+        render(&stack);
+        assert_eq!(measured.get(), 2);
+
+        // A different environment is a different key
+        stack.size(&Environment::default().with_scale_factor(2.0));
+        assert_eq!(measured.get(), 3);
+
+        let mut french = Environment::default().with_scale_factor(2.0);
+        french.set_localization(localization::Localization::new("fr", std::collections::HashMap::new()));
+        stack.size(&french);
+        stack.size(&french);
+        assert_eq!(measured.get(), 4);
+
+        let mut theme = themes::Theme::default();
+        theme.font_size += 2;
+        stack.size(&Environment::new(theme));
+        assert_eq!(measured.get(), 5);
+    }
+
+    #[test]
+    fn test_geometry_reader_builds_content_for_offered_size() {
+        use components::{GeometryReader, Size, ViewExt};
+
+        let built = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let log = built.clone();
+        let reader = GeometryReader::new(move |size: Size| {
+            log.borrow_mut().push(size);
+            text_view(&format!("{}x{}", size.width, size.height))
+        });
+        let framed = reader.frame(200.0, 100.0);
+        let env = environment::Environment::default();
+        let mut renderer = testing::MockRenderer::new();
+        framed.render(&mut renderer, &env, 0.0, 0.0);
+        framed.render(&mut renderer, &env, 0.0, 0.0);
+        let texts: Vec<String> = renderer.texts().into_iter().map(|(text, ..)| text).collect();
+        assert_eq!(texts, vec!["200x100", "200x100"]);
+        assert_eq!(*built.borrow(), vec![Size::new(200.0, 100.0)]);
+
+        // A new size rebuilds the content
+        framed.view.render(&mut renderer, &env.with_available_size(Some(300.0), Some(50.0)), 0.0, 0.0);
+        assert_eq!(built.borrow().last(), Some(&Size::new(300.0, 50.0)));
+        assert_eq!(framed.view.size(&env), (400.0, 300.0));
+    }
+
+    #[test]
+    fn test_geometry_reader_exposes_built_content_as_child() {
+        use components::{GeometryReader, Size};
+
+        let mut reader = GeometryReader::new(|_: Size| Box::new(components::Button::new("Go".to_string())) as Box<dyn View>);
+        assert!(reader.children().is_empty());
+        reader.render(&mut testing::MockRenderer::new(), &environment::Environment::default(), 0.0, 0.0);
+        // Content built while rendering is adopted on the next update
+        reader.update(0.0);
+        assert_eq!(reader.children().len(), 1);
+        assert!(reader.children()[0].id().is_some());
+        assert_eq!(reader.children_mut().len(), 1);
+    }
+
+    #[test]
+    fn test_stepper_swaps_reversed_bounds() {
+        let value = state::State::new(5, std::rc::Rc::new(std::cell::RefCell::new(false)));
+        let mut stepper = components::Stepper::new(value.binding(), 10, 0);
+        assert_eq!((stepper.min, stepper.max), (0, 10));
+        stepper.step_by(20);
+        assert_eq!(value.get(), 10);
+    }
+
+    #[test]
+    fn test_stepper_steps_by_step_and_clamps_at_bounds() {
+        let value = state::State::new(5, std::rc::Rc::new(std::cell::RefCell::new(false)));
+        let changes = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let log = changes.clone();
+        let mut stepper = components::Stepper::new(value.binding(), 0, 10).step(3).on_change(move |v| log.borrow_mut().push(v));
+        let mut renderer = testing::MockRenderer::new();
+        stepper.render(&mut renderer, &environment::Environment::default(), 0.0, 0.0);
+        assert!(renderer.texts().iter().any(|(text, ..)| text == "5"));
+
+        // "+" sits after "-" (100) and the value (50)
+        let plus = components::Event::Click { x: 160.0, y: 10.0 };
+        assert!(stepper.handle_event(&plus));
+        assert_eq!(value.get(), 8);
+        stepper.handle_event(&plus);
+        assert_eq!(value.get(), 10);
+        // Already at max: no change, no callback
+        stepper.handle_event(&plus);
+        assert_eq!(value.get(), 10);
+
+        stepper.handle_event(&components::Event::Click { x: 10.0, y: 10.0 });
+        assert_eq!(value.get(), 7);
+        // Clicking the value itself does nothing
+        assert!(!stepper.handle_event(&components::Event::Click { x: 120.0, y: 10.0 }));
+        assert_eq!(*changes.borrow(), vec![8, 10, 7]);
+    }
+
+    #[test]
+    fn test_date_step_rolls_over_and_clamps_february() {
+        use date::{days_in_month, Date, DateField};
+
+        let date = |y, m, d| Date::new(y, m, d).unwrap();
+        assert_eq!(date(2023, 12, 15).step(DateField::Month, 1), date(2024, 1, 15));
+        assert_eq!(date(2024, 1, 15).step(DateField::Month, -1), date(2023, 12, 15));
+        assert_eq!(date(2023, 12, 31).step(DateField::Day, 1), date(2024, 1, 1));
+        assert_eq!(date(2024, 3, 1).step(DateField::Day, -1), date(2024, 2, 29));
+
+        // Jan 31 clamps to the last day of February, leap years included
+        assert_eq!(date(2024, 1, 31).step(DateField::Month, 1), date(2024, 2, 29));
+        assert_eq!(date(2023, 1, 31).step(DateField::Month, 1), date(2023, 2, 28));
+        assert_eq!(date(2024, 2, 29).step(DateField::Year, 1), date(2025, 2, 28));
+        assert_eq!((days_in_month(1900, 2), days_in_month(2000, 2)), (28, 29));
+        assert_eq!(Date::new(2023, 2, 29), None);
+        assert_eq!(date(2024, 2, 9).to_string(), "2024-02-09");
+    }
+
+    #[test]
+    fn test_date_picker_buttons_step_each_field() {
+        let value = state::State::new(date::Date::new(2024, 1, 31).unwrap(), std::rc::Rc::new(std::cell::RefCell::new(false)));
+        let mut picker = components::DatePicker::new(value.binding());
+        let mut renderer = testing::MockRenderer::new();
+        picker.render(&mut renderer, &environment::Environment::default(), 0.0, 0.0);
+        assert!(renderer.texts().iter().any(|(text, ..)| text == "2024-01-31"));
+
+        // Rows of 30 below the date line: year, month, day; "+" starts at 150
+        let (_, line) = components::measure_text("0", environment::Environment::default().font_size);
+        let click = |row: f32, x: f32| components::Event::Click { x, y: line + row * 30.0 + 10.0 };
+        assert!(picker.handle_event(&click(1.0, 160.0)));
+        assert_eq!(value.get(), date::Date::new(2024, 2, 29).unwrap());
+        picker.handle_event(&click(0.0, 10.0));
+        assert_eq!(value.get(), date::Date::new(2023, 2, 28).unwrap());
+        picker.handle_event(&click(2.0, 160.0));
+        assert_eq!(value.get(), date::Date::new(2023, 3, 1).unwrap());
+    }
+
+    #[test]
+    fn test_color_picker_red_slider_updates_binding_and_swatch() {
+        use color::Color;
+
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(false));
+        let value = state::State::new(Color::rgb(10, 20, 30), redraw.clone());
+        let changes = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let log = changes.clone();
+        let mut picker = components::ColorPicker::new(value.binding()).on_change(move |c| log.borrow_mut().push(c));
+        let env = environment::Environment::default();
+        let swatch = |picker: &components::ColorPicker| {
+            let mut renderer = testing::MockRenderer::new();
+            picker.render(&mut renderer, &env, 0.0, 0.0);
+            *renderer.rect_colors().last().unwrap()
+        };
+        assert_eq!(swatch(&picker), Color::rgb(10, 20, 30));
+
+        // Grab the red slider and drag its knob past the right end
+        let slider_x = components::measure_text("G", env.font_size).0 + 5.0;
+        assert!(picker.handle_event(&components::Event::Click { x: slider_x + 50.0, y: 10.0 }));
+        picker.handle_event(&components::Event::Drag { dx: 200.0, dy: 0.0 });
+        picker.handle_event(&components::Event::MouseUp { x: 0.0, y: 0.0 });
+        assert_eq!(value.get(), Color::rgb(255, 20, 30));
+        assert_eq!(swatch(&picker), Color::rgb(255, 20, 30));
+        assert_eq!(changes.borrow().last(), Some(&Color::rgb(255, 20, 30)));
+
+        // Setting the binding elsewhere moves the sliders with it, and
+        // drawing that doesn't write any state
+        value.set(Color::rgb(0, 0, 0));
+        *redraw.borrow_mut() = false;
+        assert_eq!(swatch(&picker), Color::rgb(0, 0, 0));
+        assert!(!*redraw.borrow());
+        picker.handle_event(&components::Event::Click { x: slider_x, y: 40.0 });
+        assert_eq!(value.get(), Color::rgb(0, 0, 0));
+    }
+
+    #[test]
+    fn test_hover_delay_shows_after_resting() {
+        use components::HoverDelay;
+
+        let mut hover = HoverDelay::new(0.5);
+        assert!(!hover.tick(1.0), "not hovered yet");
+        hover.enter();
+        assert!(!hover.tick(0.3));
+        // Moving restarts the wait
+        hover.moved();
+        assert!(!hover.tick(0.3));
+        assert!(hover.is_pending());
+        assert!(hover.tick(0.2));
+        assert!(hover.is_visible());
+        // Only the tick that crosses the delay reports it; moving keeps it up
+        assert!(!hover.tick(0.1));
+        hover.moved();
+        assert!(hover.is_visible());
+        hover.leave();
+        assert!(!hover.is_visible() && !hover.is_pending());
+    }
+
+    #[test]
+    fn test_tooltip_overlay_follows_hover() {
+        use components::ViewExt;
+
+        let mut view = components::Button::new("Save".to_string()).tooltip("Save the file").delay(0.25);
+        let env = environment::Environment::default();
+        let overlay_texts = |view: &dyn View| {
+            let mut renderer = testing::MockRenderer::new();
+            view.render(&mut renderer, &env, 0.0, 0.0);
+            let mut renderer = testing::MockRenderer::new();
+            view.render_overlay(&mut renderer, &env);
+            renderer.texts()
+        };
+        assert!(overlay_texts(&view).is_empty());
+
+        view.handle_event(&components::Event::MouseEnter);
+        view.handle_event(&components::Event::MouseMove { x: 20.0, y: 10.0 });
+        view.update(0.1);
+        assert!(view.is_animating());
+        assert!(overlay_texts(&view).is_empty());
+        view.update(0.2);
+        assert!(!view.is_animating());
+        let texts = overlay_texts(&view);
+        assert_eq!(texts.len(), 1);
+        assert_eq!((texts[0].0.as_str(), texts[0].1), ("Save the file", 24.0));
+
+        view.handle_event(&components::Event::MouseLeave);
+        assert!(overlay_texts(&view).is_empty());
+    }
+
+    #[test]
+    fn test_localized_text_resolves_key_or_falls_back() {
+        use components::{Text, ViewExt};
+        use localization::{Localization, LOCALIZATION_KEY};
+
+        let french = Localization::from_toml_str("fr", "greeting = \"Bonjour\"\n[menu]\nopen = \"Ouvrir\"").unwrap();
+        assert_eq!(french.get("menu.open"), Some("Ouvrir"));
+        assert!(Localization::from_toml_str("fr", "count = 3").is_err());
+
+        let texts = |view: &dyn View, env: &environment::Environment| {
+            let mut renderer = testing::MockRenderer::new();
+            view.render(&mut renderer, env, 0.0, 0.0);
+            renderer.texts().into_iter().map(|(text, ..)| text).collect::<Vec<_>>()
+        };
+        let mut env = environment::Environment::default();
+        env.set_localization(french.clone());
+        assert_eq!(texts(&Text::localized("greeting"), &env), vec!["Bonjour"]);
+        assert_eq!(texts(&Text::localized("farewell"), &env), vec!["farewell"]);
+        assert_eq!(texts(&Text::localized("greeting"), &environment::Environment::default()), vec!["greeting"]);
+
+        // A subtree can carry its own locale
+        let scoped = Text::localized("menu.open").environment(LOCALIZATION_KEY, french);
+        assert_eq!(texts(&scoped, &environment::Environment::default()), vec!["Ouvrir"]);
+    }
+
+    #[test]
+    fn test_localized_text_reports_shown_string_and_writes_its_key() {
+        use components::Text;
+
+        let text = Text::localized("greeting");
+        let mut env = environment::Environment::default();
+        env.set_localization(localization::Localization::from_toml_str("fr", "greeting = \"Bonjour\"").unwrap());
+        text.render(&mut testing::MockRenderer::new(), &env, 0.0, 0.0);
+        assert_eq!(text.accessibility().and_then(|info| info.label).as_deref(), Some("Bonjour"));
+
+        let source = rso::to_rso(&text);
+        assert_eq!(source, "Text(localized: 'greeting')\n");
+        let loaded = rso::load_rso(&source, &rso::RsoRegistry::new()).unwrap();
+        assert_eq!(rso::to_rso(loaded.as_ref()), source);
+    }
+
+    #[test]
+    fn test_routed_click_reaches_each_view_once() {
+        let clicks = std::rc::Rc::new(std::cell::Cell::new(0));
+        let count = clicks.clone();
+        let canvas = components::Canvas::new(100.0, 100.0).on_event(move |event| {
+            if matches!(event, components::Event::Click { .. }) {
+                count.set(count.get() + 1);
+            }
+        });
+        let mut root = components::ScrollView::new(Box::new(canvas), 200.0, 200.0);
+        root.render(&mut testing::MockRenderer::new(), &environment::Environment::default(), 0.0, 0.0);
+
+        let mut focus = focus::FocusManager::new();
+        focus.route(&mut root, &components::Event::Click { x: 10.0, y: 10.0 });
+        assert_eq!(clicks.get(), 1);
+        // Nothing hit: broadcast, still once
+        focus.route(&mut root, &components::Event::Click { x: 500.0, y: 500.0 });
+        assert_eq!(clicks.get(), 2);
+    }
+
+    #[test]
+    fn test_click_on_sibling_closes_open_picker() {
+        let selected = state::State::new(0, std::rc::Rc::new(std::cell::RefCell::new(false)));
+        let clicked = std::rc::Rc::new(std::cell::Cell::new(false));
+        let flag = clicked.clone();
+        let mut row = components::HStack::new(0.0);
+        row.add_child(Box::new(components::Picker::new(vec!["A".to_string(), "B".to_string()], selected.binding())));
+        row.add_child(Box::new(components::Button::new("Go".to_string()).on_click(move || flag.set(true))));
+        row.render(&mut testing::MockRenderer::new(), &environment::Environment::default(), 0.0, 0.0);
+        let picker_open = |row: &components::HStack| row.children()[0].overlay_bounds().is_some();
+
+        let mut focus = focus::FocusManager::new();
+        focus.route(&mut row, &components::Event::Click { x: 10.0, y: 10.0 });
+        assert!(picker_open(&row));
+        // The button consumes the click, yet the picker still closes
+        focus.route(&mut row, &components::Event::Click { x: 160.0, y: 10.0 });
+        assert!(clicked.get());
+        assert!(!picker_open(&row));
+
+        // Clicking the header of an open picker still toggles it shut
+        focus.route(&mut row, &components::Event::Click { x: 10.0, y: 10.0 });
+        focus.route(&mut row, &components::Event::Click { x: 10.0, y: 10.0 });
+        assert!(!picker_open(&row));
+    }
+
+    #[test]
+    fn test_shared_state_binding_version_follows_writes() {
+        use state::Dependency;
+        use std::sync::atomic::AtomicBool;
+        use std::sync::Arc;
+
+        let status = state::SharedState::new(0, Arc::new(AtomicBool::new(false)));
+        let version = status.binding().version_counter();
+        let before = version.get();
+
+        let worker = status.clone();
+        std::thread::spawn(move || worker.set(1)).join().unwrap();
+        assert_ne!(version.get(), before);
+
+        let after_set = version.get();
+        status.update(|value| *value += 1);
+        assert_ne!(version.get(), after_set);
+    }
+
+    #[test]
+    fn test_event_loop_routes_input_to_its_window() {
+        use backend::BackendEvent;
+
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(true));
+        let clicks = std::rc::Rc::new(std::cell::Cell::new(0));
+        let counter = clicks.clone();
+        let second = components::Button::new("Second".to_string()).on_click(move || counter.set(counter.get() + 1));
+        let mut event_loop = backend::EventLoop::new(1, text_view("Main"), redraw.clone());
+        event_loop.add_window(2, Box::new(second), std::rc::Rc::new(std::cell::RefCell::new(true)));
+
+        let click = components::Event::Click { x: 5.0, y: 5.0 };
+        let mut backend = ScriptedBackend {
+            frames: vec![
+                vec![(1, BackendEvent::Input(components::Event::Click { x: 5.0, y: 5.0 }))],
+                vec![(2, BackendEvent::Input(click))],
+                vec![(2, BackendEvent::Closed)],
+            ]
+            .into(),
+            renderer: testing::MockRenderer::new(),
+        };
+        let now = std::time::Instant::now();
+        assert!(event_loop.step(&mut backend, now, 0.0));
+        assert_eq!(clicks.get(), 0);
+        assert!(event_loop.step(&mut backend, now, 0.0));
+        assert_eq!(clicks.get(), 1);
+        // Closing another window keeps the loop going; the scripted Quit ends it
+        assert!(event_loop.step(&mut backend, now, 0.0));
+        assert!(event_loop.window_mut(2).is_none());
+        assert!(!event_loop.step(&mut backend, now, 0.0));
+    }
+
+    #[test]
+    fn test_headless_redraws_after_shared_state_write() {
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(true));
+        let progress = std::rc::Rc::new(std::cell::RefCell::new(None));
+        let slot = progress.clone();
+        let mut engine = testing::HeadlessEngine::new(
+            Box::new(components::Canvas::new(100.0, 20.0).on_draw(move |renderer, x, y| {
+                if let Some(status) = slot.borrow().as_ref() {
+                    let status: &state::SharedState<String> = status;
+                    renderer.draw_text(&status.get(), x, y, 12, color::Color::rgb(0, 0, 0));
+                }
+            })),
+            redraw,
+        );
+        *progress.borrow_mut() = Some(state::SharedState::new("0%".to_string(), engine.shared_redraw_flag()));
+        assert!(engine.frame());
+        assert!(!engine.frame());
+
+        let worker = progress.borrow().as_ref().unwrap().clone();
+        std::thread::spawn(move || worker.set("50%".to_string())).join().unwrap();
+        assert!(engine.frame());
+        assert_eq!(engine.texts(), vec!["50%".to_string()]);
+    }
+
+    #[test]
+    fn test_computed_binding_version_follows_dependencies() {
+        use state::Dependency;
+
+        let count = state::State::new(1, std::rc::Rc::new(std::cell::RefCell::new(false)));
+        let reader = count.binding();
+        let doubled = state::Computed::new(move || reader.get() * 2, &[&count]);
+        let version = doubled.binding().version_counter();
+        let before = version.get();
+        count.set(2);
+        assert_ne!(version.get(), before);
+        assert_eq!(doubled.get(), 4);
+    }
+
+    #[test]
+    fn test_list_selection_follows_shifted_row() {
+        use components::Event;
+        assert_eq!(components::reordered_index(0, 0, 2), 2);
+        assert_eq!(components::reordered_index(1, 0, 2), 0);
+        assert_eq!(components::reordered_index(1, 2, 0), 2);
+        assert_eq!(components::reordered_index(2, 0, 1), 2);
+
+        let trigger = std::rc::Rc::new(std::cell::RefCell::new(false));
+        let names = state::State::new(vec!["a", "b", "c"], trigger.clone());
+        let selection = state::State::new(1, trigger);
+        let mut list = components::List::new(vec![text_view("a"), text_view("b"), text_view("c")])
+            .selection(selection.binding())
+            .reorderable(names.binding());
+        list.render(&mut testing::MockRenderer::new(), &environment::Environment::default(), 0.0, 0.0);
+
+        // Dragging "a" past "c" moves the selected "b" up a row
+        list.handle_event(&Event::Click { x: 5.0, y: 10.0 });
+        list.handle_event(&Event::Drag { dx: 0.0, dy: 70.0 });
+        list.handle_event(&Event::MouseUp { x: 5.0, y: 80.0 });
+        assert_eq!(names.get(), vec!["b", "c", "a"]);
+        assert_eq!(selection.get(), 0);
+    }
+
+    #[test]
+    fn test_rso_binds_typed_state_and_writes_names_back() {
+        let trigger = std::rc::Rc::new(std::cell::RefCell::new(false));
+        let label = state::State::new("Volume".to_string(), trigger.clone());
+        let muted = state::State::new(true, trigger.clone());
+        let volume = state::State::new(0.5f32, trigger);
+        let registry = rso::RsoRegistry::new()
+            .binding("label", label.binding())
+            .binding("muted", muted.binding())
+            .binding("volume", volume.binding())
+            .action("reset", || {});
+        let source = "VStack(10) {\n    Text(bind: 'label')\n    Toggle(bind: 'muted')\n    Slider(0, 2, bind: 'volume')\n    Button('Reset', action: 'reset')\n}\n";
+        let view = rso::load_rso(source, &registry).unwrap();
+        assert_eq!(rso::to_rso(view.as_ref()), source);
+
+        let mut renderer = testing::MockRenderer::new();
+        view.render(&mut renderer, &environment::Environment::default(), 0.0, 0.0);
+        let texts: Vec<String> = renderer.texts().into_iter().map(|(text, ..)| text).collect();
+        assert_eq!(texts, vec!["Volume", "ON", "Reset"]);
+
+        // A name bound to another type is an error rather than a silent miss
+        assert!(matches!(rso::load_rso("Toggle(bind: 'volume')", &registry), Err(error::UiError::ParseError(_))));
+        assert_eq!(rso::to_rso(rso::load_rso("Slider(1, 3)", &registry).unwrap().as_ref()), "Slider(1, 3)\n");
+    }
+
+    #[test]
+    fn test_containers_offer_children_their_own_room() {
+        use components::{Canvas, ViewExt};
+        let env = environment::Environment::default();
+        let outer = env.with_available_size(Some(400.0), Some(300.0));
+        let full = || Box::new(Canvas::new(0.0, 0.0).relative_width(1.0).relative_height(1.0));
+
+        let panel = components::Panel::new(1.0, 10.0).child(full());
+        assert_eq!(panel.size(&outer), (400.0, 300.0));
+        assert_eq!(full().padding(5.0).size(&outer), (400.0, 300.0));
+        let scroll = components::ScrollView::new(full(), 200.0, 100.0);
+        let mut renderer = testing::MockRenderer::new();
+        scroll.render(&mut renderer, &outer, 0.0, 0.0);
+        assert_eq!(scroll.max_offset(), 0.0);
+
+        let list = components::List::new(vec![full()]);
+        assert_eq!(list.size(&outer), (400.0, 30.0));
+
+        let mut grid = components::Grid::new(2, 2, 10.0).column_widths(vec![50.0]);
+        grid.set_child(0, 0, full());
+        grid.set_child(0, 1, full());
+        assert_eq!(grid.track_sizes(&outer), (vec![50.0, 195.0], vec![145.0, 0.0]));
+    }
+
+    #[test]
+    fn test_theme_change_redraws_every_window() {
+        let redraw = std::rc::Rc::new(std::cell::RefCell::new(true));
+        let mut event_loop = backend::EventLoop::new(1, text_view("Main"), redraw);
+        event_loop.add_window(2, text_view("Second"), std::rc::Rc::new(std::cell::RefCell::new(true)));
+        let mut backend = ScriptedBackend { frames: vec![vec![], vec![], vec![]].into(), renderer: testing::MockRenderer::new() };
+        let now = std::time::Instant::now();
+        assert!(event_loop.step(&mut backend, now, 0.0));
+        assert_eq!(event_loop.frame_count(), 2);
+        assert!(event_loop.step(&mut backend, now, 0.0));
+        assert_eq!(event_loop.frame_count(), 2);
+
+        event_loop.theme_handle().set_theme(themes::Theme::dark());
+        assert!(event_loop.step(&mut backend, now, 0.0));
+        assert_eq!(event_loop.frame_count(), 4);
+    }
+
+    #[test]
+    fn test_rounded_image_still_draws_its_texture() {
+        use components::ViewExt;
+        let mut image = components::Image::new(40.0, 30.0);
+        image.source = Some("logo.png".into());
+        let rounded = image.corner_radius(8.0);
+        let mut renderer = testing::MockRenderer::new();
+        rounded.render(&mut renderer, &environment::Environment::default(), 0.0, 0.0);
+        assert!(renderer.calls.iter().any(|call| matches!(call, testing::DrawCall::Image { w: 40.0, h: 30.0, .. })));
+        assert!(renderer.rects().is_empty());
+    }
+}
@@ -0,0 +1,68 @@
+// Appear/disappear tracking for views between frames
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::components::{View, ViewId};
+
+pub type LifecycleCallback = Rc<RefCell<dyn FnMut()>>;
+
+/// Callbacks a view wants run when it enters or leaves the visible tree.
+#[derive(Clone)]
+pub struct LifecycleHooks {
+    pub id: ViewId,
+    pub on_appear: Option<LifecycleCallback>,
+    pub on_disappear: Option<LifecycleCallback>,
+}
+
+/// Remembers which views were visible after the previous frame. A view is
+/// visible when it can be reached from the root through `View::children`.
+#[derive(Default)]
+pub struct LifecycleTracker {
+    visible: HashMap<ViewId, LifecycleHooks>,
+}
+
+impl LifecycleTracker {
+    pub fn new() -> Self {
+        LifecycleTracker {
+            visible: HashMap::new(),
+        }
+    }
+
+    /// Call once per rendered frame. Fires `on_appear` for views that are
+    /// new since the last call and `on_disappear` for views that are gone.
+    pub fn update(&mut self, root: &dyn View) {
+        let mut current = HashMap::new();
+        collect_hooks(root, &mut current);
+
+        for (id, hooks) in &current {
+            if !self.visible.contains_key(id) {
+                if let Some(callback) = &hooks.on_appear {
+                    (callback.borrow_mut())();
+                }
+            }
+        }
+        for (id, hooks) in &self.visible {
+            if !current.contains_key(id) {
+                if let Some(callback) = &hooks.on_disappear {
+                    (callback.borrow_mut())();
+                }
+            }
+        }
+        self.visible = current;
+    }
+
+    pub fn is_visible(&self, id: ViewId) -> bool {
+        self.visible.contains_key(&id)
+    }
+}
+
+fn collect_hooks(view: &dyn View, out: &mut HashMap<ViewId, LifecycleHooks>) {
+    if let Some(hooks) = view.lifecycle() {
+        out.insert(hooks.id, hooks);
+    }
+    for child in view.children() {
+        collect_hooks(child, out);
+    }
+}
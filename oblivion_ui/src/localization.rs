@@ -0,0 +1,64 @@
+// UI strings looked up by key, one table per locale
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::error::UiError;
+
+/// Environment key the active `Localization` is stored under, e.g. for
+/// `.environment(LOCALIZATION_KEY, localization)` on a subtree.
+pub const LOCALIZATION_KEY: &str = "localization";
+
+/// The strings of one locale, keyed by identifiers such as `"menu.open"`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Localization {
+    pub locale: String,
+    strings: HashMap<String, String>,
+}
+
+impl Localization {
+    pub fn new(locale: impl Into<String>, strings: HashMap<String, String>) -> Self {
+        Localization { locale: locale.into(), strings }
+    }
+
+    /// Loads a locale file such as `locales/fr.toml`; the file name is
+    /// the locale. See `from_toml_str` for the format.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, UiError> {
+        let path = path.as_ref();
+        let locale = path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+        let content = std::fs::read_to_string(path)?;
+        Self::from_toml_str(locale, &content).map_err(|e| UiError::ParseError(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Parses `key = "string"` pairs. Tables nest keys with dots, so
+    /// `[menu]` followed by `open = "Ouvrir"` defines `menu.open`.
+    pub fn from_toml_str(locale: impl Into<String>, content: &str) -> Result<Self, UiError> {
+        let table: toml::Table = content.parse().map_err(|e: toml::de::Error| UiError::ParseError(e.to_string()))?;
+        let mut strings = HashMap::new();
+        collect_strings("", &table, &mut strings)?;
+        Ok(Localization::new(locale, strings))
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.strings.get(key).map(String::as_str)
+    }
+
+    /// The string for `key`, or the key itself when this locale lacks it.
+    pub fn resolve<'a>(&'a self, key: &'a str) -> &'a str {
+        self.get(key).unwrap_or(key)
+    }
+}
+
+fn collect_strings(prefix: &str, table: &toml::Table, strings: &mut HashMap<String, String>) -> Result<(), UiError> {
+    for (key, value) in table {
+        let key = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+        match value {
+            toml::Value::String(text) => {
+                strings.insert(key, text.clone());
+            }
+            toml::Value::Table(nested) => collect_strings(&key, nested, strings)?,
+            _ => return Err(UiError::ParseError(format!("{} must be a string", key))),
+        }
+    }
+    Ok(())
+}
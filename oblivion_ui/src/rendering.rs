@@ -1,119 +1,1118 @@
-use sdl2::event::Event;
-use sdl2::keyboard::Keycode;
-use sdl2::pixels::Color;
+use sdl2::event::{Event, WindowEvent};
+use sdl2::keyboard::{Keycode, Mod};
+use sdl2::mouse::MouseButton;
+use sdl2::pixels::{Color as SdlColor, PixelFormatEnum};
 use sdl2::rect::Rect;
-use sdl2::render::Canvas;
+use sdl2::render::{BlendMode, Canvas, Texture};
 use sdl2::video::Window;
-use sdl2::Sdl;
+use sdl2::VideoSubsystem;
+use sdl2::{EventPump, Sdl};
+use sdl2::sys::SDL_WindowFlags;
 use std::rc::Rc;
 use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use crate::color::Color;
+use crate::dispatch::Dispatcher;
+use crate::environment::Environment;
 use crate::error::UiError;
+use crate::key::Key;
 
-use crate::components::{View, Renderer as UIRenderer};
-use crate::themes::Theme;
+use crate::components::{measure_text, Bounds, Clipboard, Spacer, View, Renderer as UIRenderer};
+use crate::backend::{draw_tree, Backend, BackendEvent, EventLoop, EventSource, WindowId};
+use crate::damage::Damage;
+use crate::focus::FocusManager;
+use crate::shortcut::{Modifiers, ShortcutRegistry};
+use crate::state::State;
+use crate::texture::{decode_image, rasterize_svg, TextureCache, TextureId, TextureKey};
+use crate::timer::TimerHandle;
+use crate::themes::{Theme, ThemeHandle};
+use crate::window_state::WindowGeometry;
 
+/// Runs view trees in SDL windows on the shared `backend::EventLoop`.
 pub struct SDLEngine {
-    sdl_context: Sdl,
+    backend: SdlBackend,
+    event_loop: EventLoop,
+    target_fps: u32,
+    // Where to save the window geometry when `run` returns
+    state_path: Option<PathBuf>,
+    error_handler: Option<Box<dyn FnMut(UiError)>>,
+}
+
+// SDL's side of the engine loop: the windows' canvases and their input
+struct SdlBackend {
+    // Keeps SDL initialised for as long as the engine lives
+    _sdl_context: Sdl,
+    video: VideoSubsystem,
+    event_pump: EventPump,
+    // The first window is the main one
+    surfaces: Vec<Surface>,
+    // Last pointer position, since SDL wheel events don't carry one
+    mouse_position: (f32, f32),
+    drag: DragTracker,
+    gestures: GestureRecognizer,
+    // Window that received the current press, for long-press delivery
+    press_window: u32,
+    // Copy/Cut events handed to the loop, written out on the next poll
+    pending_clipboard: Vec<Clipboard>,
+    shortcuts: ShortcutRegistry,
+    // Failures the loop carries on past, for the engine's error handler
+    errors: Vec<UiError>,
+}
+
+/// Identifies a window opened by the engine and carries its redraw flag.
+#[derive(Clone)]
+pub struct WindowHandle {
+    window_id: u32,
+    redraw_trigger: Rc<RefCell<bool>>,
+}
+
+impl WindowHandle {
+    pub fn new(window_id: u32) -> Self {
+        Self::with_trigger(window_id, Rc::new(RefCell::new(true)))
+    }
+
+    fn with_trigger(window_id: u32, redraw_trigger: Rc<RefCell<bool>>) -> Self {
+        WindowHandle { window_id, redraw_trigger }
+    }
+
+    pub fn window_id(&self) -> u32 {
+        self.window_id
+    }
+
+    /// Trigger to give the `State`s shown in this window.
+    pub fn redraw_trigger(&self) -> Rc<RefCell<bool>> {
+        self.redraw_trigger.clone()
+    }
+
+    pub fn request_redraw(&self) {
+        *self.redraw_trigger.borrow_mut() = true;
+    }
+
+    pub fn needs_redraw(&self) -> bool {
+        *self.redraw_trigger.borrow()
+    }
+
+    pub fn clear_redraw(&self) {
+        *self.redraw_trigger.borrow_mut() = false;
+    }
+}
+
+// One OS window's canvas; its view tree lives in the event loop
+struct Surface {
     canvas: Canvas<Window>,
+    textures: Textures,
+    // The last frame. Repaints draw what changed into it and copy all of it
+    // to the window, so the back buffer's contents never matter.
+    frame: Option<Texture>,
+}
+
+impl Surface {
+    fn new(canvas: Canvas<Window>) -> Self {
+        Surface { canvas, textures: Textures::new(), frame: None }
+    }
+
+    fn window_id(&self) -> u32 {
+        self.canvas.window().id()
+    }
+
+    // Repaints `damage` into the frame texture and presents the whole frame
+    fn present(&mut self, errors: &mut Vec<UiError>, damage: Damage, background: Color, draw: &mut dyn FnMut(&mut dyn UIRenderer)) {
+        let region = match damage {
+            Damage::None => return,
+            Damage::Full => None,
+            Damage::Region(region) => Some(region),
+        };
+        if !self.canvas.render_target_supported() {
+            paint(&mut self.canvas, &mut self.textures, errors, background, None, draw);
+            self.canvas.present();
+            return;
+        }
+        let size = match self.canvas.output_size() {
+            Ok(size) => size,
+            Err(e) => return report(errors, Err(e)),
+        };
+        let current = self.frame.as_ref().map(|frame| (frame.query().width, frame.query().height));
+        // A resized window needs a new texture, painted in full
+        let region = if current == Some(size) {
+            region
+        } else {
+            if let Some(old) = self.frame.take() {
+                destroy_texture(old);
+            }
+            match self.canvas.texture_creator().create_texture_target(None, size.0, size.1) {
+                Ok(frame) => self.frame = Some(frame),
+                Err(e) => return report(errors, Err(e.to_string())),
+            }
+            None
+        };
+        let Surface { canvas, textures, frame } = self;
+        let Some(frame) = frame.as_mut() else {
+            return;
+        };
+        let painted = canvas.with_texture_canvas(frame, |target| paint(target, textures, errors, background, region, draw));
+        report(errors, painted.map_err(|e| e.to_string()));
+        report(errors, canvas.copy(frame, None, None));
+        canvas.present();
+    }
+}
+
+// Textures are freed along with their window's renderer; this frees one
+// sooner. Only pass textures of a canvas that is still alive.
+fn destroy_texture(texture: Texture) {
+    // SAFETY: every caller holds the canvas that created the texture
+    unsafe { texture.destroy() }
+}
+
+// Images uploaded to one window's renderer, which frees them along with it
+type Textures = TextureCache<Texture>;
+
+// Uploads decoded pixels as a texture of `canvas`
+fn upload_texture(canvas: &Canvas<Window>, pixels: image::RgbaImage) -> Result<Texture, UiError> {
+    let (width, height) = pixels.dimensions();
+    let creator = canvas.texture_creator();
+    let mut texture = creator
+        .create_texture_static(PixelFormatEnum::RGBA32, width, height)
+        .map_err(|e| UiError::RenderError(e.to_string()))?;
+    if let Err(e) = texture.update(None, pixels.as_raw(), width as usize * 4) {
+        destroy_texture(texture);
+        return Err(UiError::RenderError(e.to_string()));
+    }
+    texture.set_blend_mode(BlendMode::Blend);
+    Ok(texture)
+}
+
+fn render_frame(canvas: &mut Canvas<Window>, textures: &mut Textures, errors: &mut Vec<UiError>, focus: &FocusManager, view: &dyn View, theme: &Theme) {
+    paint_view(canvas, textures, errors, focus, view, theme);
+    canvas.present();
+}
+
+// Draws a frame into the back buffer without presenting it
+fn paint_view(canvas: &mut Canvas<Window>, textures: &mut Textures, errors: &mut Vec<UiError>, focus: &FocusManager, view: &dyn View, theme: &Theme) {
+    let env = Environment::new(theme.clone()).with_scale_factor(window_scale_factor(canvas));
+    paint(canvas, textures, errors, env.background_color, None, &mut |renderer| draw_tree(renderer, focus, view, &env));
+}
+
+// Hands text copied by a view to the system clipboard
+fn write_clipboard(video: &VideoSubsystem, clipboard: &Clipboard, errors: &mut Vec<UiError>) {
+    if let Some(text) = clipboard.take() {
+        if let Err(e) = video.clipboard().set_clipboard_text(&text) {
+            errors.push(UiError::SdlError(format!("can't set the clipboard text: {}", e)));
+        }
+    }
+}
+
+// Clears `region` (or the whole window) and draws into it, clipped to it
+fn paint(
+    canvas: &mut Canvas<Window>,
+    textures: &mut Textures,
+    errors: &mut Vec<UiError>,
+    background: Color,
+    region: Option<Bounds>,
+    draw: &mut dyn FnMut(&mut dyn UIRenderer),
+) {
+    let scale = window_scale_factor(canvas);
+    canvas.set_draw_color(SdlColor::RGB(background.r, background.g, background.b));
+    match region {
+        // Clearing ignores the clip rect, so fill the region instead
+        Some(region) => {
+            canvas.set_clip_rect(physical_rect(region.x, region.y, region.width, region.height, scale));
+            report(errors, canvas.fill_rect(None));
+        }
+        None => canvas.clear(),
+    }
+    let clip_stack = region.into_iter().collect();
+    let mut renderer = SDLRenderer { canvas, textures, errors, scale, clip_stack };
+    draw(&mut renderer);
+    renderer.canvas.set_clip_rect(None);
+}
+
+/// Repacks rows of native-endian ARGB8888 pixels, `pitch` bytes apart,
+/// into tightly packed RGBA bytes.
+pub fn argb_to_rgba(pixels: &[u8], pitch: usize, width: u32, height: u32) -> Vec<u8> {
+    let mut rgba = Vec::with_capacity(width as usize * height as usize * 4);
+    for row in 0..height as usize {
+        for column in 0..width as usize {
+            let offset = row * pitch + column * 4;
+            let bytes = [pixels[offset], pixels[offset + 1], pixels[offset + 2], pixels[offset + 3]];
+            let [a, r, g, b] = u32::from_ne_bytes(bytes).to_be_bytes();
+            rgba.extend_from_slice(&[r, g, b, a]);
+        }
+    }
+    rgba
+}
+
+fn encode_png(rgba: Vec<u8>, width: u32, height: u32) -> Result<Vec<u8>, UiError> {
+    let image = image::RgbaImage::from_raw(width, height, rgba)
+        .ok_or_else(|| UiError::RenderError("pixel buffer doesn't match the frame size".to_string()))?;
+    let mut png = Vec::new();
+    image.write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)?;
+    Ok(png)
+}
+
+/// Reads which modifier keys SDL reports as held, either side counting.
+pub fn modifiers_from_sdl(keymod: Mod) -> Modifiers {
+    Modifiers {
+        control: keymod.intersects(Mod::LCTRLMOD | Mod::RCTRLMOD),
+        shift: keymod.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD),
+        alt: keymod.intersects(Mod::LALTMOD | Mod::RALTMOD),
+        meta: keymod.intersects(Mod::LGUIMOD | Mod::RGUIMOD),
+    }
+}
+
+/// Maps an SDL keycode to a [`Key`]; keys the framework doesn't know about map to `None`.
+pub fn key_from_sdl(keycode: Keycode) -> Option<Key> {
+    let key = match keycode {
+        Keycode::A => Key::A,
+        Keycode::B => Key::B,
+        Keycode::C => Key::C,
+        Keycode::D => Key::D,
+        Keycode::E => Key::E,
+        Keycode::F => Key::F,
+        Keycode::G => Key::G,
+        Keycode::H => Key::H,
+        Keycode::I => Key::I,
+        Keycode::J => Key::J,
+        Keycode::K => Key::K,
+        Keycode::L => Key::L,
+        Keycode::M => Key::M,
+        Keycode::N => Key::N,
+        Keycode::O => Key::O,
+        Keycode::P => Key::P,
+        Keycode::Q => Key::Q,
+        Keycode::R => Key::R,
+        Keycode::S => Key::S,
+        Keycode::T => Key::T,
+        Keycode::U => Key::U,
+        Keycode::V => Key::V,
+        Keycode::W => Key::W,
+        Keycode::X => Key::X,
+        Keycode::Y => Key::Y,
+        Keycode::Z => Key::Z,
+        Keycode::Num0 | Keycode::Kp0 => Key::Num0,
+        Keycode::Num1 | Keycode::Kp1 => Key::Num1,
+        Keycode::Num2 | Keycode::Kp2 => Key::Num2,
+        Keycode::Num3 | Keycode::Kp3 => Key::Num3,
+        Keycode::Num4 | Keycode::Kp4 => Key::Num4,
+        Keycode::Num5 | Keycode::Kp5 => Key::Num5,
+        Keycode::Num6 | Keycode::Kp6 => Key::Num6,
+        Keycode::Num7 | Keycode::Kp7 => Key::Num7,
+        Keycode::Num8 | Keycode::Kp8 => Key::Num8,
+        Keycode::Num9 | Keycode::Kp9 => Key::Num9,
+        Keycode::F1 => Key::F1,
+        Keycode::F2 => Key::F2,
+        Keycode::F3 => Key::F3,
+        Keycode::F4 => Key::F4,
+        Keycode::F5 => Key::F5,
+        Keycode::F6 => Key::F6,
+        Keycode::F7 => Key::F7,
+        Keycode::F8 => Key::F8,
+        Keycode::F9 => Key::F9,
+        Keycode::F10 => Key::F10,
+        Keycode::F11 => Key::F11,
+        Keycode::F12 => Key::F12,
+        Keycode::Left => Key::Left,
+        Keycode::Right => Key::Right,
+        Keycode::Up => Key::Up,
+        Keycode::Down => Key::Down,
+        Keycode::Home => Key::Home,
+        Keycode::End => Key::End,
+        Keycode::PageUp => Key::PageUp,
+        Keycode::PageDown => Key::PageDown,
+        Keycode::Return | Keycode::KpEnter => Key::Enter,
+        Keycode::Backspace => Key::Backspace,
+        Keycode::Delete => Key::Delete,
+        Keycode::Insert => Key::Insert,
+        Keycode::Tab => Key::Tab,
+        Keycode::Escape => Key::Escape,
+        Keycode::Space => Key::Space,
+        Keycode::LShift | Keycode::RShift => Key::Shift,
+        Keycode::LCtrl | Keycode::RCtrl => Key::Control,
+        Keycode::LAlt | Keycode::RAlt => Key::Alt,
+        Keycode::LGui | Keycode::RGui => Key::Meta,
+        _ => return None,
+    };
+    Some(key)
+}
+
+fn window_scale_factor(canvas: &Canvas<Window>) -> f32 {
+    let drawable = canvas.output_size().unwrap_or_else(|_| canvas.window().size());
+    scale_factor(canvas.window().size(), drawable)
+}
+
+/// Physical pixels per logical pixel, from the window's size in logical
+/// pixels and its drawable size. 2.0 on a typical retina display.
+pub fn scale_factor(window_size: (u32, u32), drawable_size: (u32, u32)) -> f32 {
+    if window_size.0 == 0 {
+        return 1.0;
+    }
+    drawable_size.0 as f32 / window_size.0 as f32
+}
+
+/// Converts a rectangle in logical pixels to device pixels as
+/// `(x, y, width, height)`. Edges are rounded separately so adjacent rects
+/// still meet without gaps, and negative sizes come out empty.
+pub fn device_rect(x: f32, y: f32, w: f32, h: f32, scale: f32) -> (i32, i32, u32, u32) {
+    // Round halves up rather than away from zero, so moving across the
+    // origin doesn't change the size
+    let round = |v: f32| (v + 0.5).floor();
+    let left = round(x * scale);
+    let top = round(y * scale);
+    let right = round((x + w) * scale);
+    let bottom = round((y + h) * scale);
+    (left as i32, top as i32, (right - left).max(0.0) as u32, (bottom - top).max(0.0) as u32)
+}
+
+/// `device_rect` as an SDL rect, or `None` when it rounds to nothing.
+/// SDL widens empty rects to one pixel, so those must not be drawn.
+pub fn drawable_rect(x: f32, y: f32, w: f32, h: f32, scale: f32) -> Option<Rect> {
+    let (x, y, w, h) = device_rect(x, y, w, h, scale);
+    (w > 0 && h > 0).then(|| Rect::new(x, y, w, h))
+}
+
+/// `device_rect` as an SDL rect; see `drawable_rect` for drawing.
+pub fn physical_rect(x: f32, y: f32, w: f32, h: f32, scale: f32) -> Rect {
+    let (x, y, w, h) = device_rect(x, y, w, h, scale);
+    Rect::new(x, y, w, h)
+}
+
+/// Time left in the current frame at `target_fps` after `elapsed` has been
+/// spent on it. `None` means the frame rate is uncapped.
+pub fn frame_budget(target_fps: u32, elapsed: Duration) -> Option<Duration> {
+    if target_fps == 0 {
+        return None;
+    }
+    let frame = Duration::from_secs_f64(1.0 / target_fps as f64);
+    Some(frame.saturating_sub(elapsed))
+}
+
+/// Measures the real time between frames.
+pub struct FrameClock {
+    last: Instant,
+}
+
+impl FrameClock {
+    pub fn new() -> Self {
+        Self::starting_at(Instant::now())
+    }
+
+    pub fn starting_at(start: Instant) -> Self {
+        FrameClock { last: start }
+    }
+
+    /// Seconds since the previous tick.
+    pub fn tick(&mut self) -> f64 {
+        self.tick_at(Instant::now())
+    }
+
+    pub fn tick_at(&mut self, now: Instant) -> f64 {
+        let dt = now.saturating_duration_since(self.last).as_secs_f64();
+        self.last = now;
+        dt
+    }
+}
+
+impl Default for FrameClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Turns press/motion/release into `Drag` events carrying per-motion deltas.
+#[derive(Default)]
+pub struct DragTracker {
+    last: Option<(f32, f32)>,
+}
+
+impl DragTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn press(&mut self, x: f32, y: f32) {
+        self.last = Some((x, y));
+    }
+
+    /// Returns a `Drag` event if the button is held.
+    pub fn motion(&mut self, x: f32, y: f32) -> Option<crate::components::Event> {
+        let (last_x, last_y) = self.last?;
+        self.last = Some((x, y));
+        Some(crate::components::Event::Drag { dx: x - last_x, dy: y - last_y })
+    }
+
+    pub fn release(&mut self) {
+        self.last = None;
+    }
+}
+
+/// Recognizes double-clicks and long presses from raw button input.
+pub struct GestureRecognizer {
+    pub double_click_interval: Duration,
+    /// Max distance in pixels between the two clicks of a double-click.
+    pub double_click_distance: f32,
+    pub long_press_duration: Duration,
+    /// Movement beyond this many pixels cancels a pending long press.
+    pub long_press_tolerance: f32,
+    last_click: Option<(f32, f32, Instant)>,
+    pressed: Option<(f32, f32, Instant)>,
+}
+
+impl Default for GestureRecognizer {
+    fn default() -> Self {
+        GestureRecognizer {
+            double_click_interval: Duration::from_millis(400),
+            double_click_distance: 5.0,
+            long_press_duration: Duration::from_millis(600),
+            long_press_tolerance: 5.0,
+            last_click: None,
+            pressed: None,
+        }
+    }
+}
+
+impl GestureRecognizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a `DoubleClick` if this press completes one.
+    pub fn press(&mut self, x: f32, y: f32, at: Instant) -> Option<crate::components::Event> {
+        self.pressed = Some((x, y, at));
+        match self.last_click.take() {
+            Some((last_x, last_y, last_at))
+                if at.saturating_duration_since(last_at) <= self.double_click_interval
+                    && distance((x, y), (last_x, last_y)) <= self.double_click_distance =>
+            {
+                // A third click starts a new pair rather than double-clicking again
+                Some(crate::components::Event::DoubleClick { x, y })
+            }
+            _ => {
+                self.last_click = Some((x, y, at));
+                None
+            }
+        }
+    }
+
+    pub fn motion(&mut self, x: f32, y: f32) {
+        if let Some((press_x, press_y, _)) = self.pressed {
+            if distance((x, y), (press_x, press_y)) > self.long_press_tolerance {
+                self.pressed = None;
+            }
+        }
+    }
+
+    pub fn release(&mut self) {
+        self.pressed = None;
+    }
+
+    /// Returns a `LongPress` once the button has been held long enough.
+    pub fn poll(&mut self, at: Instant) -> Option<crate::components::Event> {
+        let (x, y, pressed_at) = self.pressed?;
+        if at.saturating_duration_since(pressed_at) < self.long_press_duration {
+            return None;
+        }
+        self.pressed = None;
+        Some(crate::components::Event::LongPress { x, y })
+    }
+}
+
+fn distance(a: (f32, f32), b: (f32, f32)) -> f32 {
+    ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
 }
 
 impl SDLEngine {
     pub fn new(title: &str, width: u32, height: u32) -> Result<(Self, Rc<RefCell<bool>>), UiError> {
+        Self::with_target_fps(title, width, height, 60)
+    }
+
+    pub fn with_target_fps(title: &str, width: u32, height: u32, target_fps: u32) -> Result<(Self, Rc<RefCell<bool>>), UiError> {
+        Self::create(title, width, height, None, target_fps, None)
+    }
+
+    /// Like `new`, but restores the window geometry saved in `state_path`
+    /// (falling back to the defaults) and saves it there when `run` returns.
+    pub fn new_with_state(title: &str, default_width: u32, default_height: u32, state_path: impl AsRef<Path>) -> Result<(Self, Rc<RefCell<bool>>), UiError> {
+        let state_path = state_path.as_ref().to_path_buf();
+        let saved = WindowGeometry::load(&state_path);
+        let (width, height) = saved.map_or((default_width, default_height), |g| (g.width, g.height));
+        Self::create(title, width, height, saved.map(|g| (g.x, g.y)), 60, Some(state_path))
+    }
+
+    fn create(
+        title: &str,
+        width: u32,
+        height: u32,
+        position: Option<(i32, i32)>,
+        target_fps: u32,
+        state_path: Option<PathBuf>,
+    ) -> Result<(Self, Rc<RefCell<bool>>), UiError> {
         let sdl_context = sdl2::init().map_err(|e| UiError::SdlError(e.to_string()))?;
-        let video_subsystem = sdl_context.video()?;
+        let video_subsystem = sdl_context.video().map_err(UiError::SdlError)?;
+        let event_pump = sdl_context.event_pump().map_err(UiError::SdlError)?;
 
-        let window = video_subsystem
-            .window(title, width, height)
-            .position_centered()
+        let mut builder = video_subsystem.window(title, width, height);
+        match position {
+            Some((x, y)) => builder.position(x, y),
+            None => builder.position_centered(),
+        };
+        let window = builder
+            .resizable()
+            .allow_highdpi()
             .build()
             .map_err(|e| UiError::SdlError(e.to_string()))?;
 
         let canvas = window.into_canvas().build().map_err(|e| UiError::SdlError(e.to_string()))?;
+        let main_window = canvas.window().id();
 
         let redraw_trigger = Rc::new(RefCell::new(true));
+        // Replaced by the root view `run` is given
+        let event_loop = EventLoop::new(main_window, Box::new(Spacer::new()), redraw_trigger.clone());
+        let mut shortcuts = ShortcutRegistry::new();
+        let debug_layout = event_loop.debug_layout_flag();
+        shortcuts.add(Modifiers::NONE, Key::F12, move || debug_layout.set(!debug_layout.get()));
 
         Ok((SDLEngine {
-            sdl_context,
-            canvas,
+            backend: SdlBackend {
+                _sdl_context: sdl_context,
+                video: video_subsystem,
+                event_pump,
+                surfaces: vec![Surface::new(canvas)],
+                mouse_position: (0.0, 0.0),
+                drag: DragTracker::new(),
+                gestures: GestureRecognizer::new(),
+                press_window: main_window,
+                pending_clipboard: Vec::new(),
+                shortcuts,
+                errors: Vec::new(),
+            },
+            event_loop,
+            target_fps,
+            state_path,
+            error_handler: None,
         }, redraw_trigger))
     }
 
-    pub fn run(&mut self, mut root_view: Box<dyn View>, theme: &Theme, redraw_trigger: Rc<RefCell<bool>>) -> Result<(), UiError> {
-        let mut event_pump = self.sdl_context.event_pump()?;
-        let mut last_time = std::time::Instant::now();
+    /// Timing and distance thresholds for double-click and long-press.
+    pub fn gestures_mut(&mut self) -> &mut GestureRecognizer {
+        &mut self.backend.gestures
+    }
 
-        'running: loop {
-            let now = std::time::Instant::now();
-            let dt = now.duration_since(last_time).as_secs_f64();
-            last_time = now;
+    /// Flag to pass to `SharedState::new`; the loop redraws every window
+    /// on the frame after it's raised.
+    pub fn shared_redraw_flag(&self) -> Arc<AtomicBool> {
+        self.event_loop.shared_redraw_flag()
+    }
 
-            root_view.update(dt);
+    /// Applies messages sent to `dispatcher` to `model` with `update` at the
+    /// start of each frame, redrawing afterwards.
+    pub fn add_dispatcher<Model: 'static, Msg: 'static>(
+        &mut self,
+        dispatcher: Dispatcher<Msg>,
+        model: State<Model>,
+        mut update: impl FnMut(&mut Model, Msg) + 'static,
+    ) {
+        self.event_loop.add_dispatcher(move || {
+            let messages = dispatcher.pending();
+            if messages.is_empty() {
+                return false;
+            }
+            // One write for the whole batch, so observers see the final model
+            model.update(|model| messages.into_iter().for_each(|msg| update(model, msg)));
+            true
+        });
+    }
 
-            for event in event_pump.poll_iter() {
-                match event {
-                    Event::Quit { .. }
-                    | Event::KeyDown {
-                        keycode: Some(Keycode::Escape),
-                        ..
-                    } => break 'running,
-                    _ => {
-                        // Convert SDL event to our Event
-                        let ui_event = self.convert_event(&event);
-                        root_view.handle_event(&ui_event);
-                    }
-                }
+    /// Runs `callback` when `key` is pressed with `modifiers`, whichever
+    /// view has focus. The key press is consumed and never reaches a view.
+    pub fn add_shortcut(&mut self, modifiers: Modifiers, key: Key, callback: impl FnMut() + 'static) {
+        self.backend.shortcuts.add(modifiers, key, callback);
+    }
+
+    /// Outlines every view's bounds over the normal frame, and prints the
+    /// view tree with positions and sizes after the next frame. F12 toggles it.
+    pub fn set_debug_layout(&mut self, enabled: bool) {
+        self.event_loop.debug_layout_flag().set(enabled);
+    }
+
+    /// Runs `callback` once, `after` from now.
+    pub fn schedule(&mut self, after: Duration, callback: impl FnMut() + 'static) -> TimerHandle {
+        self.event_loop.schedule_at(Instant::now() + after, None, callback)
+    }
+
+    /// Runs `callback` every `interval` until the handle is cancelled.
+    /// Intervals under `timer::MIN_INTERVAL` are raised to it.
+    pub fn schedule_repeating(&mut self, interval: Duration, callback: impl FnMut() + 'static) -> TimerHandle {
+        self.event_loop.schedule_at(Instant::now() + interval, Some(interval), callback)
+    }
+
+    /// Device pixels per logical pixel for the main window. Layout and
+    /// events use logical pixels; rendering scales by this factor.
+    pub fn scale_factor(&self) -> f32 {
+        window_scale_factor(&self.backend.surfaces[0].canvas)
+    }
+
+    /// Receives the errors the engine carries on past, such as a failed draw
+    /// call, an image that won't load or a failed clipboard write. Without a
+    /// handler they're dropped.
+    pub fn on_error(&mut self, handler: impl FnMut(UiError) + 'static) {
+        self.error_handler = Some(Box::new(handler));
+    }
+
+    // Hands the errors collected since the last call to the handler
+    fn report_errors(&mut self) {
+        for error in self.backend.errors.drain(..) {
+            if let Some(handler) = &mut self.error_handler {
+                handler(error);
+            }
+        }
+    }
+
+    /// Upper bound on frames per second; 0 disables the cap.
+    pub fn set_target_fps(&mut self, fps: u32) {
+        self.target_fps = fps;
+    }
+
+    /// Opens another top-level window showing `root_view`. Its views should
+    /// use the handle's redraw trigger; see `open_window_with`.
+    pub fn open_window(&mut self, title: &str, width: u32, height: u32, root_view: Box<dyn View>) -> Result<WindowHandle, UiError> {
+        self.open_window_with(title, width, height, |_| root_view)
+    }
+
+    /// Opens a window whose view tree is built with its own redraw trigger.
+    pub fn open_window_with<F>(&mut self, title: &str, width: u32, height: u32, build: F) -> Result<WindowHandle, UiError>
+    where
+        F: FnOnce(Rc<RefCell<bool>>) -> Box<dyn View>,
+    {
+        let window = self
+            .backend
+            .video
+            .window(title, width, height)
+            .resizable()
+            .allow_highdpi()
+            .build()
+            .map_err(|e| UiError::SdlError(e.to_string()))?;
+        let canvas = window.into_canvas().build().map_err(|e| UiError::SdlError(e.to_string()))?;
+        let window_id = canvas.window().id();
+        let redraw_trigger = Rc::new(RefCell::new(true));
+        self.event_loop.add_window(window_id, build(redraw_trigger.clone()), redraw_trigger.clone());
+        self.backend.surfaces.push(Surface::new(canvas));
+        Ok(WindowHandle::with_trigger(window_id, redraw_trigger))
+    }
+
+    /// Handle for switching the theme while the app is running.
+    pub fn theme_handle(&self) -> ThemeHandle {
+        self.event_loop.theme_handle()
+    }
+
+    pub fn run(&mut self, root_view: Box<dyn View>, theme: &Theme, redraw_trigger: Rc<RefCell<bool>>) -> Result<(), UiError> {
+        self.event_loop.theme_handle().set_default(theme.clone());
+        self.event_loop.main_window_mut().set_root(root_view, redraw_trigger);
+        let mut clock = FrameClock::new();
+
+        loop {
+            let frame_start = Instant::now();
+            let dt = clock.tick_at(frame_start);
+            let running = self.event_loop.step(&mut self.backend, frame_start, dt);
+            self.report_errors();
+            if !running {
+                break;
+            }
+            if let Some(remaining) = frame_budget(self.target_fps, frame_start.elapsed()) {
+                std::thread::sleep(remaining);
             }
+        }
 
-            if *redraw_trigger.borrow() {
-                self.render_view(&*root_view, theme)?;
-                *redraw_trigger.borrow_mut() = false;
+        if let Some(path) = &self.state_path {
+            let window = self.backend.surfaces[0].canvas.window();
+            let (x, y) = window.position();
+            let (width, height) = window.size();
+            // Losing the saved geometry isn't worth failing the app over
+            if let Err(e) = (WindowGeometry { x, y, width, height }).save(path) {
+                self.backend.errors.push(UiError::IoError(e));
+                self.report_errors();
             }
         }
 
         Ok(())
     }
 
+    /// Draws `view` into the main window.
     pub fn render_view(&mut self, view: &dyn View, theme: &Theme) -> Result<(), UiError> {
-        self.canvas.set_draw_color(Color::RGB(255, 255, 255));
-        self.canvas.clear();
+        let main = &mut self.backend.surfaces[0];
+        render_frame(&mut main.canvas, &mut main.textures, &mut self.backend.errors, self.event_loop.main_window().focus(), view, theme);
+        self.report_errors();
+        // The screen no longer shows the tracked frame
+        self.event_loop.main_window_mut().invalidate();
+        Ok(())
+    }
 
-        view.render(&mut SDLRenderer { canvas: &mut self.canvas, theme }, theme, 0.0, 0.0);
+    /// Redraws the main window and returns the frame encoded as PNG.
+    pub fn capture_frame(&mut self) -> Result<Vec<u8>, UiError> {
+        let theme = self.event_loop.theme_handle().get();
+        let main = &mut self.backend.surfaces[0];
+        let host = self.event_loop.main_window();
+        paint_view(&mut main.canvas, &mut main.textures, &mut self.backend.errors, host.focus(), host.root(), &theme);
+        self.report_errors();
+        let main = &mut self.backend.surfaces[0];
+        let (width, height) = main.canvas.output_size().map_err(UiError::SdlError)?;
+        let pixels = main.canvas.read_pixels(None, PixelFormatEnum::ARGB8888).map_err(UiError::SdlError)?;
+        main.canvas.present();
+        encode_png(argb_to_rgba(&pixels, width as usize * 4, width, height), width, height)
+    }
 
-        self.canvas.present();
+    /// Writes `capture_frame`'s PNG to `path`.
+    pub fn save_screenshot(&mut self, path: impl AsRef<Path>) -> Result<(), UiError> {
+        std::fs::write(path, self.capture_frame()?)?;
         Ok(())
     }
 
     pub fn handle_event(&mut self, sdl_event: &sdl2::event::Event, view: &mut dyn View) {
-        let ui_event = self.convert_event(sdl_event);
-        view.handle_event(&ui_event);
+        if let Some(ui_event) = self.backend.convert_event(sdl_event) {
+            self.event_loop.main_window_mut().focus_mut().route(view, &ui_event);
+        }
     }
 
-    fn convert_event(&self, event: &Event) -> crate::components::Event {
+    /// Focus state of the main window.
+    pub fn focus(&self) -> &FocusManager {
+        self.event_loop.main_window().focus()
+    }
+}
+
+impl SdlBackend {
+
+    // Runs the shortcut a key press matches; true if it was consumed
+    fn run_shortcut(&mut self, event: &Event) -> bool {
+        let Event::KeyDown { keycode: Some(keycode), keymod, .. } = event else {
+            return false;
+        };
+        key_from_sdl(*keycode).is_some_and(|key| self.shortcuts.trigger(modifiers_from_sdl(*keymod), key))
+    }
+
+    // Feeds the gesture recognizer; returns the gesture a press completes
+    fn track_gesture(&mut self, event: &Event) -> Option<crate::components::Event> {
         match event {
-            Event::MouseButtonDown { x, y, .. } => crate::components::Event::Click { x: *x as f32, y: *y as f32 },
-            Event::MouseMotion { x, y, .. } => crate::components::Event::MouseMove { x: *x as f32, y: *y as f32 },
-            Event::KeyDown { keycode: Some(key), .. } => crate::components::Event::KeyDown(*key),
-            _ => crate::components::Event::Click { x: 0.0, y: 0.0 }, // Default
+            Event::MouseButtonDown { window_id, mouse_btn: MouseButton::Left, x, y, .. } => {
+                self.press_window = *window_id;
+                self.gestures.press(*x as f32, *y as f32, Instant::now())
+            }
+            Event::MouseMotion { x, y, .. } => {
+                self.gestures.motion(*x as f32, *y as f32);
+                None
+            }
+            Event::MouseButtonUp { mouse_btn: MouseButton::Left, .. } => {
+                self.gestures.release();
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn convert_event(&mut self, event: &Event) -> Option<crate::components::Event> {
+        if let Event::MouseMotion { x, y, .. } = event {
+            self.mouse_position = (*x as f32, *y as f32);
+        }
+        match event {
+            // The right button opens context menus; it never starts a drag
+            Event::MouseButtonDown { mouse_btn: MouseButton::Right, x, y, .. } => {
+                Some(crate::components::Event::RightClick { x: *x as f32, y: *y as f32 })
+            }
+            Event::MouseButtonUp { mouse_btn: MouseButton::Right, .. } => None,
+            Event::MouseButtonDown { mouse_btn: MouseButton::Left, x, y, .. } => {
+                self.drag.press(*x as f32, *y as f32);
+                Some(crate::components::Event::Click { x: *x as f32, y: *y as f32 })
+            }
+            Event::MouseButtonUp { mouse_btn: MouseButton::Left, x, y, .. } => {
+                self.drag.release();
+                Some(crate::components::Event::MouseUp { x: *x as f32, y: *y as f32 })
+            }
+            // Middle and extra buttons have no meaning to views yet
+            Event::MouseButtonDown { .. } | Event::MouseButtonUp { .. } => None,
+            // While the button is held, motion becomes a drag
+            Event::MouseMotion { x, y, .. } => self
+                .drag
+                .motion(*x as f32, *y as f32)
+                .or(Some(crate::components::Event::MouseMove { x: *x as f32, y: *y as f32 })),
+            Event::KeyDown { keycode: Some(key), keymod, .. }
+                if keymod.intersects(Mod::LCTRLMOD | Mod::RCTRLMOD) && matches!(key, Keycode::C | Keycode::X | Keycode::V) =>
+            {
+                match key {
+                    Keycode::C => Some(crate::components::Event::Copy(Clipboard::default())),
+                    Keycode::X => Some(crate::components::Event::Cut(Clipboard::default())),
+                    _ => {
+                        let text = self.video.clipboard().clipboard_text().unwrap_or_default();
+                        (!text.is_empty()).then_some(crate::components::Event::Paste(text))
+                    }
+                }
+            }
+            Event::KeyDown { keycode: Some(key), keymod, .. }
+                if keymod.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD)
+                    && matches!(key, Keycode::Left | Keycode::Right | Keycode::Home | Keycode::End) =>
+            {
+                key_from_sdl(*key).map(crate::components::Event::ShiftKeyDown)
+            }
+            Event::KeyDown { keycode: Some(key), .. } => key_from_sdl(*key).map(crate::components::Event::KeyDown),
+            Event::MouseWheel { x, y, direction, .. } => {
+                // Normalise "natural scrolling" so positive dy always means up
+                let sign = if *direction == sdl2::mouse::MouseWheelDirection::Flipped { -1.0 } else { 1.0 };
+                let (mouse_x, mouse_y) = self.mouse_position;
+                Some(crate::components::Event::MouseWheel {
+                    x: mouse_x,
+                    y: mouse_y,
+                    dx: *x as f32 * sign,
+                    dy: *y as f32 * sign,
+                })
+            }
+            Event::TextInput { text, .. } => Some(crate::components::Event::TextInput(text.clone())),
+            Event::Window { win_event: WindowEvent::Resized(width, height), .. } => Some(crate::components::Event::Resize {
+                width: (*width).max(0) as u32,
+                height: (*height).max(0) as u32,
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl EventSource for SdlBackend {
+    fn poll_events(&mut self, timeout: Duration) -> Vec<(WindowId, BackendEvent)> {
+        for clipboard in std::mem::take(&mut self.pending_clipboard) {
+            write_clipboard(&self.video, &clipboard, &mut self.errors);
         }
+        let mut sdl_events: Vec<Event> = self.event_pump.wait_event_timeout(timeout.as_millis() as u32).into_iter().collect();
+        sdl_events.extend(self.event_pump.poll_iter());
+
+        let mut events = Vec::new();
+        for event in &sdl_events {
+            let window = event.get_window_id().unwrap_or(self.surfaces[0].window_id());
+            match event {
+                Event::Quit { .. } | Event::KeyDown { keycode: Some(Keycode::Escape), .. } => events.push((window, BackendEvent::Quit)),
+                Event::Window { win_event: WindowEvent::Close, .. } => {
+                    // The main window's canvas stays until the engine is dropped
+                    if window != self.surfaces[0].window_id() {
+                        self.surfaces.retain(|surface| surface.window_id() != window);
+                    }
+                    events.push((window, BackendEvent::Closed));
+                }
+                Event::KeyDown { keycode: Some(Keycode::Tab), keymod, .. } if keymod.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD) => {
+                    events.push((window, BackendEvent::FocusPrevious))
+                }
+                Event::Window { win_event: WindowEvent::Leave, .. } => events.push((window, BackendEvent::PointerLeft)),
+                Event::Window { win_event: WindowEvent::Exposed, .. } => events.push((window, BackendEvent::Exposed)),
+                _ if self.run_shortcut(event) => {}
+                _ => {
+                    let gesture = self.track_gesture(event);
+                    if let Some(ui_event) = self.convert_event(event) {
+                        if let crate::components::Event::Copy(clipboard) | crate::components::Event::Cut(clipboard) = &ui_event {
+                            self.pending_clipboard.push(clipboard.clone());
+                        }
+                        events.push((window, BackendEvent::Input(ui_event)));
+                    }
+                    // The click itself is delivered first, then the gesture it completes
+                    events.extend(gesture.map(|gesture| (window, BackendEvent::Input(gesture))));
+                }
+            }
+        }
+        if let Some(long_press) = self.gestures.poll(Instant::now()) {
+            events.push((self.press_window, BackendEvent::Input(long_press)));
+        }
+        events
     }
 }
 
+impl Backend for SdlBackend {
+    fn draw_frame(&mut self, window: WindowId, background: Color, draw: &mut dyn FnMut(&mut dyn UIRenderer)) {
+        let SdlBackend { surfaces, errors, .. } = self;
+        if let Some(surface) = surfaces.iter_mut().find(|surface| surface.window_id() == window) {
+            surface.present(errors, Damage::Full, background, draw);
+        }
+    }
+
+    fn draw_region(&mut self, window: WindowId, background: Color, region: Bounds, draw: &mut dyn FnMut(&mut dyn UIRenderer)) {
+        let SdlBackend { surfaces, errors, .. } = self;
+        if let Some(surface) = surfaces.iter_mut().find(|surface| surface.window_id() == window) {
+            surface.present(errors, Damage::Region(region), background, draw);
+        }
+    }
+
+    fn scale_factor(&self, window: WindowId) -> f32 {
+        let surface = self.surfaces.iter().find(|surface| surface.window_id() == window);
+        surface.map_or(1.0, |surface| window_scale_factor(&surface.canvas))
+    }
+
+    fn window_size(&self, window: WindowId) -> Option<(f32, f32)> {
+        let surface = self.surfaces.iter().find(|surface| surface.window_id() == window)?;
+        let (width, height) = surface.canvas.window().size();
+        Some((width as f32, height as f32))
+    }
+
+    fn focused_window(&self) -> Option<WindowId> {
+        let has_focus = |surface: &&Surface| surface.canvas.window().window_flags() & SDL_WindowFlags::SDL_WINDOW_INPUT_FOCUS as u32 != 0;
+        self.surfaces.iter().find(has_focus).map(Surface::window_id)
+    }
+}
+
+// A failed draw call loses part of one frame; pass it on rather than stop
+fn report(errors: &mut Vec<UiError>, result: Result<(), String>) {
+    if let Err(e) = result {
+        errors.push(UiError::RenderError(e));
+    }
+}
+
+// Views draw in logical pixels; the renderer scales to device pixels
 struct SDLRenderer<'a> {
     canvas: &'a mut Canvas<Window>,
-    theme: &'a crate::themes::Theme,
+    textures: &'a mut Textures,
+    errors: &'a mut Vec<UiError>,
+    scale: f32,
+    clip_stack: Vec<Bounds>,
+}
+
+impl<'a> SDLRenderer<'a> {
+    // Runs `draw` in `color`, blending when it's translucent and putting
+    // the canvas's blend mode back afterwards
+    fn with_color(&mut self, color: Color, draw: impl FnOnce(&mut Self)) {
+        self.canvas.set_draw_color(SdlColor::RGBA(color.r, color.g, color.b, color.a));
+        if color.a == 255 {
+            draw(self);
+            return;
+        }
+        let previous = self.canvas.blend_mode();
+        self.canvas.set_blend_mode(BlendMode::Blend);
+        draw(self);
+        self.canvas.set_blend_mode(previous);
+    }
+
+    fn apply_clip(&mut self) {
+        // SDL would widen an empty clip to a pixel; drawing stops instead
+        if self.clipped_out() {
+            return;
+        }
+        let scale = self.scale;
+        let rect = self.clip_stack.last().map(|clip| physical_rect(clip.x, clip.y, clip.width, clip.height, scale));
+        self.canvas.set_clip_rect(rect);
+    }
+
+    // Whether the innermost clip leaves no pixel to draw in
+    fn clipped_out(&self) -> bool {
+        let scale = self.scale;
+        self.clip_stack.last().is_some_and(|clip| drawable_rect(clip.x, clip.y, clip.width, clip.height, scale).is_none())
+    }
+
+    // Fills a logical rect, skipping ones that round to nothing
+    fn fill(&mut self, x: f32, y: f32, w: f32, h: f32) {
+        if self.clipped_out() {
+            return;
+        }
+        if let Some(rect) = drawable_rect(x, y, w, h, self.scale) {
+            report(self.errors, self.canvas.fill_rect(rect));
+        }
+    }
 }
 
 impl<'a> UIRenderer for SDLRenderer<'a> {
-    fn draw_text(&mut self, text: &str, x: f32, y: f32) {
+    fn draw_text(&mut self, text: &str, x: f32, y: f32, font_size: u32, color: Color) {
         // Placeholder: draw a colored rectangle representing text
-        self.canvas.set_draw_color(Color::RGB(self.theme.text_color.0, self.theme.text_color.1, self.theme.text_color.2));
-        let rect = Rect::new(x as i32, y as i32, (text.len() * 10) as u32, 20);
-        self.canvas.fill_rect(rect).unwrap();
+        let (w, h) = measure_text(text, font_size);
+        self.with_color(color, |renderer| renderer.fill(x, y, w, h));
+    }
+
+    fn draw_rect(&mut self, x: f32, y: f32, w: f32, h: f32, color: Color) {
+        self.with_color(color, |renderer| renderer.fill(x, y, w, h));
+    }
+
+    fn draw_round_rect(&mut self, x: f32, y: f32, w: f32, h: f32, radius: f32, color: Color) {
+        if self.clipped_out() {
+            return;
+        }
+        self.with_color(color, |renderer| {
+            // Work in device pixels so the corners stay smooth when scaled
+            let (x, y, w, h, radius) = (x * renderer.scale, y * renderer.scale, w * renderer.scale, h * renderer.scale, radius * renderer.scale);
+            let radius = radius.max(0.0).min(w / 2.0).min(h / 2.0);
+            // Fill row by row, insetting each row near the top and bottom edges by the corner arc
+            let rows = h.round() as i32;
+            for row in 0..rows {
+                let row_center = row as f32 + 0.5;
+                let dy = if row_center < radius {
+                    radius - row_center
+                } else if row_center > h - radius {
+                    row_center - (h - radius)
+                } else {
+                    0.0
+                };
+                let inset = if dy > 0.0 { radius - (radius * radius - dy * dy).max(0.0).sqrt() } else { 0.0 };
+                let width = (w - inset * 2.0).round();
+                if width > 0.0 {
+                    let rect = Rect::new((x + inset + 0.5).floor() as i32, (y + 0.5).floor() as i32 + row, width as u32, 1);
+                    report(renderer.errors, renderer.canvas.fill_rect(rect));
+                }
+            }
+        });
+    }
+
+    fn draw_rect_outline(&mut self, x: f32, y: f32, w: f32, h: f32, thickness: f32, color: Color) {
+        let t = thickness.min(w / 2.0).min(h / 2.0);
+        if t <= 0.0 {
+            return;
+        }
+        self.draw_rect(x, y, w, t, color); // Top
+        self.draw_rect(x, y + h - t, w, t, color); // Bottom
+        self.draw_rect(x, y + t, t, h - t * 2.0, color); // Left
+        self.draw_rect(x + w - t, y + t, t, h - t * 2.0, color); // Right
     }
 
-    fn draw_rect(&mut self, x: f32, y: f32, w: f32, h: f32) {
-        self.canvas.set_draw_color(Color::RGB(self.theme.secondary_color.0, self.theme.secondary_color.1, self.theme.secondary_color.2));
-        let rect = Rect::new(x as i32, y as i32, w as u32, h as u32);
-        self.canvas.fill_rect(rect).unwrap();
+    fn push_clip(&mut self, x: f32, y: f32, w: f32, h: f32) {
+        let requested = Bounds::new(x, y, w, h);
+        let clip = match self.clip_stack.last() {
+            Some(outer) => outer.intersect(&requested),
+            None => requested,
+        };
+        self.clip_stack.push(clip);
+        self.apply_clip();
     }
-}
\ No newline at end of file
+
+    fn pop_clip(&mut self) {
+        self.clip_stack.pop();
+        self.apply_clip();
+    }
+
+    fn load_image(&mut self, path: &Path) -> Option<TextureId> {
+        let canvas = &*self.canvas;
+        match self.textures.load(path, |path| upload_texture(canvas, decode_image(path)?)) {
+            Ok(id) => id,
+            Err(e) => {
+                self.errors.push(UiError::RenderError(format!("can't load image {}: {}", path.display(), e)));
+                None
+            }
+        }
+    }
+
+    fn load_icon(&mut self, path: &Path, width: u32, height: u32) -> Option<TextureId> {
+        let key = TextureKey::icon(path, width, height);
+        let canvas = &*self.canvas;
+        let loaded = self.textures.load_key(key, |_| upload_texture(canvas, rasterize_svg(path, width, height)?));
+        for texture in self.textures.drain_evicted() {
+            destroy_texture(texture);
+        }
+        match loaded {
+            Ok(id) => id,
+            Err(e) => {
+                self.errors.push(UiError::RenderError(format!("can't load icon {}: {}", path.display(), e)));
+                None
+            }
+        }
+    }
+
+    fn draw_image(&mut self, texture: TextureId, x: f32, y: f32, w: f32, h: f32, alpha: f32) {
+        if self.clipped_out() {
+            return;
+        }
+        let Some(texture) = self.textures.get_mut(texture) else {
+            return;
+        };
+        texture.set_alpha_mod((alpha.clamp(0.0, 1.0) * 255.0).round() as u8);
+        if let Some(rect) = drawable_rect(x, y, w, h, self.scale) {
+            report(self.errors, self.canvas.copy(texture, None, rect));
+        }
+    }
+}
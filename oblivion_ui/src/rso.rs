@@ -1,17 +1,551 @@
 // Module for .rso file support - declarative UI definitions
 
-use crate::components::{View, VStack, HStack, ZStack, Text, Button, Spacer, Divider, Image, List};
-use crate::state::State;
+use crate::accessibility::AccessibilityInfo;
+use crate::components::{Bounds, Button, Divider, Event, HStack, Renderer, Slider, Spacer, Text, Toggle, VStack, View, ViewId, ZStack};
+use crate::damage::Damage;
+use crate::environment::Environment;
+use crate::error::UiError;
+use crate::lifecycle::LifecycleHooks;
+use crate::state::{Binding, State};
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt::Write;
 use std::rc::Rc;
 use std::cell::RefCell;
+use std::sync::mpsc::Receiver;
 
-// Simple parser for .rso files (JSON-like for now)
-pub fn load_rso(content: &str) -> Result<Box<dyn View>, String> {
-    // Placeholder: parse JSON or simple format
-    // For example, assume content is "VStack { Text('Hello') Button('Click') }"
-    // But for simplicity, return a hardcoded view
-    let mut vstack = VStack::new(10.0);
-    vstack.add_child(Box::new(Text::new(State::new("Hello from .rso".to_string(), Rc::new(RefCell::new(false))).binding())));
-    vstack.add_child(Box::new(Button::new("Click".to_string())));
-    Ok(Box::new(vstack))
-}
\ No newline at end of file
+/// How a view is written in `.rso` source: a component name and its
+/// arguments, positional ones first. Children come from `View::children`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RsoElement {
+    pub name: &'static str,
+    pub args: Vec<RsoValue>,
+    /// Written as `label: value`.
+    pub labelled: Vec<(&'static str, RsoValue)>,
+}
+
+impl RsoElement {
+    pub fn new(name: &'static str) -> Self {
+        RsoElement { name, args: Vec::new(), labelled: Vec::new() }
+    }
+
+    pub fn arg(mut self, value: RsoValue) -> Self {
+        self.args.push(value);
+        self
+    }
+
+    pub fn labelled(mut self, label: &'static str, value: RsoValue) -> Self {
+        self.labelled.push((label, value));
+        self
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RsoValue {
+    /// Quoted with `'` or `"`, with `\'`, `\"` and `\\` escapes.
+    Text(String),
+    Number(f32),
+}
+
+type Action = Rc<RefCell<dyn FnMut()>>;
+
+/// Bindings and actions a `.rso` document can refer to by name, as in
+/// `Text(bind: "counter")` and `Button("Inc", action: "increment")`.
+/// Each binding keeps its value type; `Text` binds a `String`, `Toggle` a
+/// `bool` and `Slider` an `f32`.
+#[derive(Clone, Default)]
+pub struct RsoRegistry {
+    bindings: HashMap<String, Rc<dyn Any>>,
+    actions: HashMap<String, Action>,
+}
+
+impl RsoRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn binding<T: 'static>(mut self, name: impl Into<String>, binding: Binding<T>) -> Self {
+        self.bindings.insert(name.into(), Rc::new(binding));
+        self
+    }
+
+    // The binding registered as `name`, if it holds a `T`
+    fn bound<T: 'static>(&self, name: &str) -> Result<Binding<T>, UiError> {
+        let binding = self.bindings.get(name).ok_or_else(|| UiError::ParseError(format!("unknown binding '{}'", name)))?;
+        binding
+            .downcast_ref::<Binding<T>>()
+            .cloned()
+            .ok_or_else(|| UiError::ParseError(format!("binding '{}' isn't a {}", name, std::any::type_name::<T>())))
+    }
+
+    /// Registers `action`; every button naming it shares the one closure.
+    pub fn action(mut self, name: impl Into<String>, action: impl FnMut() + 'static) -> Self {
+        self.actions.insert(name.into(), Rc::new(RefCell::new(action)));
+        self
+    }
+}
+
+// Spacing of stacks written without one
+const DEFAULT_STACK_SPACING: f32 = 10.0;
+
+/// Parses a `.rso` document: one component, written as its name, optional
+/// arguments in parentheses and optional children in braces, e.g.
+/// `VStack(8) { Text('Hello') Button('Click') }`. Names in `bind:` and
+/// `action:` arguments are looked up in `registry`.
+pub fn load_rso(content: &str, registry: &RsoRegistry) -> Result<Box<dyn View>, UiError> {
+    if content.trim().is_empty() {
+        return Err(UiError::ParseError("empty .rso document".to_string()));
+    }
+    let mut parser = RsoParser { chars: content.chars().collect(), pos: 0, registry };
+    let view = parser.component()?;
+    parser.skip_whitespace();
+    if parser.pos < parser.chars.len() {
+        return Err(parser.error("end of document"));
+    }
+    Ok(view)
+}
+
+struct RsoParser<'a> {
+    chars: Vec<char>,
+    pos: usize,
+    registry: &'a RsoRegistry,
+}
+
+// An argument with its label, if it was written as `label: value`
+type Argument = (Option<String>, RsoValue);
+
+impl RsoParser<'_> {
+    fn error(&self, expected: &str) -> UiError {
+        UiError::ParseError(format!("expected {} at offset {}", expected, self.pos))
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.get(self.pos).is_some_and(|c| c.is_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    // Skips whitespace, then consumes `c` if it comes next
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        if self.chars.get(self.pos) == Some(&c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn take_while(&mut self, accept: impl Fn(char) -> bool) -> String {
+        let start = self.pos;
+        while self.chars.get(self.pos).is_some_and(|&c| accept(c)) {
+            self.pos += 1;
+        }
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    fn component(&mut self) -> Result<Box<dyn View>, UiError> {
+        self.skip_whitespace();
+        let name = self.take_while(|c| c.is_alphanumeric() || c == '_');
+        if name.is_empty() {
+            return Err(self.error("a component name"));
+        }
+        let mut args = Vec::new();
+        if self.eat('(') && !self.eat(')') {
+            loop {
+                args.push(self.argument()?);
+                if self.eat(')') {
+                    break;
+                }
+                if !self.eat(',') {
+                    return Err(self.error("',' or ')'"));
+                }
+            }
+        }
+        let mut children = Vec::new();
+        if self.eat('{') {
+            while !self.eat('}') {
+                if self.pos >= self.chars.len() {
+                    return Err(self.error("'}'"));
+                }
+                children.push(self.component()?);
+            }
+        }
+        build(&name, args, children, self.registry)
+    }
+
+    fn argument(&mut self) -> Result<Argument, UiError> {
+        self.skip_whitespace();
+        let mut label = None;
+        if self.chars.get(self.pos).is_some_and(|c| c.is_alphabetic()) {
+            label = Some(self.take_while(|c| c.is_alphanumeric() || c == '_'));
+            if !self.eat(':') {
+                return Err(self.error("':'"));
+            }
+        }
+        Ok((label, self.value()?))
+    }
+
+    fn value(&mut self) -> Result<RsoValue, UiError> {
+        self.skip_whitespace();
+        let quote = match self.chars.get(self.pos) {
+            Some(&quote @ ('\'' | '"')) => quote,
+            _ => {
+                let number = self.take_while(|c| c.is_ascii_digit() || c == '.' || c == '-');
+                return number.parse().map(RsoValue::Number).map_err(|_| self.error("a string or number"));
+            }
+        };
+        self.pos += 1;
+        let mut text = String::new();
+        loop {
+            match self.chars.get(self.pos) {
+                Some(&c) if c == quote => break,
+                Some('\\') if self.pos + 1 < self.chars.len() => {
+                    text.push(self.chars[self.pos + 1]);
+                    self.pos += 2;
+                }
+                Some(&c) => {
+                    text.push(c);
+                    self.pos += 1;
+                }
+                None => return Err(self.error("closing quote")),
+            }
+        }
+        self.pos += 1;
+        Ok(RsoValue::Text(text))
+    }
+}
+
+// Makes the view a parsed component describes
+fn build(name: &str, args: Vec<Argument>, children: Vec<Box<dyn View>>, registry: &RsoRegistry) -> Result<Box<dyn View>, UiError> {
+    let (labelled, args): (Vec<Argument>, Vec<Argument>) = args.into_iter().partition(|(label, _)| label.is_some());
+    let args: Vec<RsoValue> = args.into_iter().map(|(_, value)| value).collect();
+    let mut labelled: HashMap<String, RsoValue> = labelled.into_iter().filter_map(|(label, value)| Some((label?, value))).collect();
+    let allowed: &[&str] = match name {
+        "Text" => &["bind", "localized"],
+        "Button" => &["action"],
+        "Toggle" | "Slider" => &["bind"],
+        _ => &[],
+    };
+    if let Some(label) = labelled.keys().find(|label| !allowed.contains(&label.as_str())) {
+        return Err(UiError::ParseError(format!("{} has no '{}' argument", name, label)));
+    }
+    // Registry names the view was built from, written back by `to_rso`
+    let mut names = Vec::new();
+    // The registry entry a `label: "name"` argument refers to
+    let mut lookup = |label: &'static str| match labelled.remove(label) {
+        Some(RsoValue::Text(key)) => {
+            names.push((label, RsoValue::Text(key.clone())));
+            Ok(Some(key))
+        }
+        Some(RsoValue::Number(_)) => Err(UiError::ParseError(format!("{}: needs a name", label))),
+        None => Ok(None),
+    };
+    let text = |index: usize| match args.get(index) {
+        Some(RsoValue::Text(text)) => Ok(text.clone()),
+        _ => Err(UiError::ParseError(format!("{} needs a string argument", name))),
+    };
+    let number = |index: usize, default: f32| match args.get(index) {
+        Some(RsoValue::Number(n)) => Ok(*n),
+        None => Ok(default),
+        Some(RsoValue::Text(_)) => Err(UiError::ParseError(format!("{} needs a number argument", name))),
+    };
+    let is_container = matches!(name, "VStack" | "HStack" | "ZStack");
+    if !is_container && !children.is_empty() {
+        return Err(UiError::ParseError(format!("{} can't have children", name)));
+    }
+    let view: Box<dyn View> = match name {
+        "VStack" => {
+            let mut stack = VStack::new(number(0, DEFAULT_STACK_SPACING)?);
+            children.into_iter().for_each(|child| stack.add_child(child));
+            Box::new(stack)
+        }
+        "HStack" => {
+            let mut stack = HStack::new(number(0, DEFAULT_STACK_SPACING)?);
+            children.into_iter().for_each(|child| stack.add_child(child));
+            Box::new(stack)
+        }
+        "ZStack" => {
+            let mut stack = ZStack::new();
+            children.into_iter().for_each(|child| stack.add_child(child));
+            Box::new(stack)
+        }
+        "Text" => match (lookup("bind")?, lookup("localized")?) {
+            (Some(_), Some(_)) => return Err(UiError::ParseError("Text takes 'bind' or 'localized', not both".to_string())),
+            (Some(key), None) => Box::new(Text::new(registry.bound(&key)?)),
+            (None, Some(key)) => Box::new(Text::localized(key)),
+            (None, None) => Box::new(Text::new(State::new(text(0)?, Rc::new(RefCell::new(false))).binding())),
+        },
+        "Button" => {
+            let button = Button::new(text(0)?);
+            match lookup("action")? {
+                Some(key) => match registry.actions.get(&key) {
+                    Some(action) => {
+                        let action = action.clone();
+                        Box::new(button.on_click(move || (action.borrow_mut())()))
+                    }
+                    None => return Err(UiError::ParseError(format!("unknown action '{}'", key))),
+                },
+                None => Box::new(button),
+            }
+        }
+        "Toggle" => {
+            let is_on = match lookup("bind")? {
+                Some(key) => registry.bound(&key)?,
+                None => State::new(false, Rc::new(RefCell::new(false))).binding(),
+            };
+            Box::new(Toggle::new(is_on))
+        }
+        "Slider" => {
+            let (min, max) = (number(0, 0.0)?, number(1, 1.0)?);
+            let value = match lookup("bind")? {
+                Some(key) => registry.bound(&key)?,
+                None => State::new(min, Rc::new(RefCell::new(false))).binding(),
+            };
+            Box::new(Slider::new(value, min, max))
+        }
+        "Spacer" => Box::new(Spacer::new().min_length(number(0, Spacer::new().min_length)?)),
+        "Divider" => Box::new(Divider::new()),
+        _ => return Err(UiError::ParseError(format!("unknown component '{}'", name))),
+    };
+    match view.rso_element() {
+        Some(element) if !names.is_empty() => Ok(Box::new(Named { view, element: RsoElement { args, labelled: names, ..element } })),
+        _ => Ok(view),
+    }
+}
+
+// A view built from registry names, written back with those names rather
+// than the values they held. Otherwise it passes everything to the view.
+struct Named {
+    view: Box<dyn View>,
+    element: RsoElement,
+}
+
+impl View for Named {
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        self.view.render(renderer, env, x, y);
+    }
+
+    fn render_overlay(&self, renderer: &mut dyn Renderer, env: &Environment) {
+        self.view.render_overlay(renderer, env);
+    }
+
+    fn overlay_bounds(&self) -> Option<Bounds> {
+        self.view.overlay_bounds()
+    }
+
+    fn dismiss_overlay(&mut self) {
+        self.view.dismiss_overlay();
+    }
+
+    fn is_modal(&self) -> bool {
+        self.view.is_modal()
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        self.view.handle_event(event)
+    }
+
+    fn update(&mut self, dt: f64) {
+        self.view.update(dt);
+    }
+
+    fn is_animating(&self) -> bool {
+        self.view.is_animating()
+    }
+
+    fn damage(&self) -> Damage {
+        self.view.damage()
+    }
+
+    fn size(&self, env: &Environment) -> (f32, f32) {
+        self.view.size(env)
+    }
+
+    fn bounds(&self) -> Option<Bounds> {
+        self.view.bounds()
+    }
+
+    fn type_name(&self) -> &'static str {
+        self.view.type_name()
+    }
+
+    fn layout_bounds(&self) -> Option<Bounds> {
+        self.view.layout_bounds()
+    }
+
+    fn id(&self) -> Option<ViewId> {
+        self.view.id()
+    }
+
+    fn can_focus(&self) -> bool {
+        self.view.can_focus()
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.view.is_enabled()
+    }
+
+    fn extends_to_edges(&self) -> bool {
+        self.view.extends_to_edges()
+    }
+
+    fn flex(&self) -> f32 {
+        self.view.flex()
+    }
+
+    fn is_flexible(&self) -> bool {
+        self.view.is_flexible()
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.view.set_focused(focused);
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        self.view.children()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        self.view.children_mut()
+    }
+
+    fn lifecycle(&self) -> Option<LifecycleHooks> {
+        self.view.lifecycle()
+    }
+
+    fn accessibility(&self) -> Option<AccessibilityInfo> {
+        self.view.accessibility()
+    }
+
+    fn accessibility_children(&self) -> Vec<&dyn View> {
+        self.view.accessibility_children()
+    }
+
+    fn rso_element(&self) -> Option<RsoElement> {
+        Some(self.element.clone())
+    }
+}
+
+/// Writes the tree under `view` as `.rso` source that `load_rso` reads
+/// back. Views without an `rso_element` (such as modifiers) are left out
+/// and their children take their place. Views `load_rso` bound to registry
+/// names are written with those names; other state and callbacks aren't kept.
+pub fn to_rso(view: &dyn View) -> String {
+    let mut out = String::new();
+    write_view(view, 0, &mut out);
+    out
+}
+
+fn write_view(view: &dyn View, depth: usize, out: &mut String) {
+    let Some(element) = view.rso_element() else {
+        for child in view.children() {
+            write_view(child, depth, out);
+        }
+        return;
+    };
+    let _ = write!(out, "{:indent$}{}", "", element.name, indent = depth * 4);
+    if !element.args.is_empty() || !element.labelled.is_empty() {
+        let value = |arg: &RsoValue| match arg {
+            RsoValue::Text(text) => format!("'{}'", text.replace('\\', "\\\\").replace('\'', "\\'")),
+            RsoValue::Number(n) => n.to_string(),
+        };
+        let positional = element.args.iter().map(value);
+        let labelled = element.labelled.iter().map(|(label, arg)| format!("{}: {}", label, value(arg)));
+        let args: Vec<String> = positional.chain(labelled).collect();
+        let _ = write!(out, "({})", args.join(", "));
+    }
+    let children = view.children();
+    if children.is_empty() {
+        out.push('\n');
+        return;
+    }
+    out.push_str(" {\n");
+    for child in children {
+        write_view(child, depth + 1, out);
+    }
+    let _ = writeln!(out, "{:indent$}}}", "", indent = depth * 4);
+}
+
+/// Root view for live previews. Shows the last `.rso` content that parsed,
+/// swapping in new content as it arrives.
+pub struct RsoReloader {
+    current: Box<dyn View>,
+    registry: RsoRegistry,
+    updates: Option<Receiver<String>>,
+    redraw_trigger: Option<Rc<RefCell<bool>>>,
+    error_handler: Option<Box<dyn FnMut(UiError)>>,
+}
+
+impl RsoReloader {
+    /// Shows `content`; names in it and in later content are looked up in
+    /// `registry`.
+    pub fn new(content: &str, registry: RsoRegistry) -> Result<Self, UiError> {
+        Ok(RsoReloader {
+            current: load_rso(content, &registry)?,
+            registry,
+            updates: None,
+            redraw_trigger: None,
+            error_handler: None,
+        })
+    }
+
+    /// Reloads from new content each frame one arrives on `updates`, then
+    /// marks `redraw_trigger`.
+    pub fn watch(mut self, updates: Receiver<String>, redraw_trigger: Rc<RefCell<bool>>) -> Self {
+        self.updates = Some(updates);
+        self.redraw_trigger = Some(redraw_trigger);
+        self
+    }
+
+    /// Receives the parse errors of watched content that was rejected, while
+    /// the previous view stays up.
+    pub fn on_error(mut self, handler: impl FnMut(UiError) + 'static) -> Self {
+        self.error_handler = Some(Box::new(handler));
+        self
+    }
+
+    /// Replaces the view with `content`; on a parse error the current view stays.
+    pub fn reload(&mut self, content: &str) -> Result<(), UiError> {
+        self.current = load_rso(content, &self.registry)?;
+        if let Some(trigger) = &self.redraw_trigger {
+            *trigger.borrow_mut() = true;
+        }
+        Ok(())
+    }
+}
+
+impl View for RsoReloader {
+    fn render(&self, renderer: &mut dyn Renderer, env: &Environment, x: f32, y: f32) {
+        self.current.render(renderer, env, x, y);
+    }
+
+    fn handle_event(&mut self, event: &Event) -> bool {
+        self.current.handle_event(event)
+    }
+
+    fn update(&mut self, dt: f64) {
+        // Only the newest content matters if several saves queued up
+        let latest = self.updates.as_ref().and_then(|updates| updates.try_iter().last());
+        if let Some(content) = latest {
+            if let Err(e) = self.reload(&content) {
+                if let Some(handler) = &mut self.error_handler {
+                    handler(e);
+                }
+            }
+        }
+        self.current.update(dt);
+    }
+
+    fn size(&self, env: &Environment) -> (f32, f32) {
+        self.current.size(env)
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        vec![self.current.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        vec![self.current.as_mut()]
+    }
+}
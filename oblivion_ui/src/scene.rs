@@ -0,0 +1,33 @@
+// Stable identity for views rebuilt from data, so rows keep their state
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+/// Identity of a retained node across rebuilds, derived from a key such
+/// as a database id.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NodeId(u64);
+
+impl NodeId {
+    pub fn from_key<K: Hash + ?Sized>(key: &K) -> Self {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        NodeId(hasher.finish())
+    }
+}
+
+/// Diffs the previous nodes against the ids wanted now: nodes whose id is
+/// still wanted are kept (in the new order), the rest are dropped, and
+/// `build` makes a node for each new id from its index in `ids`.
+pub fn reconcile<V>(previous: Vec<(NodeId, V)>, ids: &[NodeId], mut build: impl FnMut(usize) -> V) -> Vec<(NodeId, V)> {
+    let mut retained: HashMap<NodeId, V> = previous.into_iter().collect();
+    ids.iter()
+        .enumerate()
+        .map(|(index, &id)| {
+            // A repeated id only reuses the old node once
+            let node = retained.remove(&id).unwrap_or_else(|| build(index));
+            (id, node)
+        })
+        .collect()
+}
@@ -0,0 +1,79 @@
+// Application-wide keyboard shortcuts, matched before views see the key
+
+use std::ops::BitOr;
+
+use crate::key::Key;
+
+/// Modifier keys held down with a key. Combine them with `|`:
+/// `Modifiers::CONTROL | Modifiers::SHIFT`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Modifiers {
+    pub control: bool,
+    pub shift: bool,
+    pub alt: bool,
+    /// Command on macOS, the Windows key elsewhere.
+    pub meta: bool,
+}
+
+impl Modifiers {
+    pub const NONE: Modifiers = Modifiers { control: false, shift: false, alt: false, meta: false };
+    pub const CONTROL: Modifiers = Modifiers { control: true, ..Modifiers::NONE };
+    pub const SHIFT: Modifiers = Modifiers { shift: true, ..Modifiers::NONE };
+    pub const ALT: Modifiers = Modifiers { alt: true, ..Modifiers::NONE };
+    pub const META: Modifiers = Modifiers { meta: true, ..Modifiers::NONE };
+}
+
+impl BitOr for Modifiers {
+    type Output = Modifiers;
+
+    fn bitor(self, other: Modifiers) -> Modifiers {
+        Modifiers {
+            control: self.control || other.control,
+            shift: self.shift || other.shift,
+            alt: self.alt || other.alt,
+            meta: self.meta || other.meta,
+        }
+    }
+}
+
+struct Shortcut {
+    modifiers: Modifiers,
+    key: Key,
+    action: Box<dyn FnMut()>,
+}
+
+/// Shortcuts registered with the engine, checked against every key press.
+#[derive(Default)]
+pub struct ShortcutRegistry {
+    shortcuts: Vec<Shortcut>,
+}
+
+impl ShortcutRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `action` for `key` pressed with exactly `modifiers` held.
+    /// A later registration of the same combination replaces the earlier one.
+    pub fn add(&mut self, modifiers: Modifiers, key: Key, action: impl FnMut() + 'static) {
+        self.shortcuts.retain(|shortcut| (shortcut.modifiers, shortcut.key) != (modifiers, key));
+        self.shortcuts.push(Shortcut { modifiers, key, action: Box::new(action) });
+    }
+
+    /// Whether a shortcut is registered for this combination.
+    pub fn matches(&self, modifiers: Modifiers, key: Key) -> bool {
+        self.shortcuts.iter().any(|shortcut| shortcut.modifiers == modifiers && shortcut.key == key)
+    }
+
+    /// Runs the shortcut for this combination, if any. Returns true when
+    /// one ran, meaning the key press is consumed.
+    pub fn trigger(&mut self, modifiers: Modifiers, key: Key) -> bool {
+        match self.shortcuts.iter_mut().find(|shortcut| shortcut.modifiers == modifiers && shortcut.key == key) {
+            Some(shortcut) => {
+                (shortcut.action)();
+                true
+            }
+            None => false,
+        }
+    }
+}
@@ -1,16 +1,76 @@
 use std::rc::Rc;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Count of writes to a state, shared with its bindings. Caches compare it
+/// to tell whether the value changed without reading it.
+#[derive(Clone)]
+pub struct Version(VersionCounter);
+
+#[derive(Clone)]
+enum VersionCounter {
+    Local(Rc<Cell<u64>>),
+    // Bumped by `SharedState` writes from any thread
+    Shared(Arc<AtomicU64>),
+    // Moves whenever one of the versions a computed value reads does
+    Derived(Rc<[Version]>),
+}
+
+impl Version {
+    pub fn new() -> Self {
+        Version(VersionCounter::Local(Rc::new(Cell::new(0))))
+    }
+
+    pub fn get(&self) -> u64 {
+        match &self.0 {
+            VersionCounter::Local(count) => count.get(),
+            VersionCounter::Shared(count) => count.load(Ordering::Acquire),
+            VersionCounter::Derived(deps) => deps.iter().fold(0, |sum, dep| sum.wrapping_add(dep.get())),
+        }
+    }
+
+    fn bump(&self) {
+        match &self.0 {
+            VersionCounter::Local(count) => count.set(count.get() + 1),
+            VersionCounter::Shared(count) => {
+                count.fetch_add(1, Ordering::AcqRel);
+            }
+            VersionCounter::Derived(_) => {}
+        }
+    }
+}
+
+impl Default for Version {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+type Observers<T> = RefCell<Vec<Box<dyn Fn(&T)>>>;
 
 pub struct State<T> {
     value: Rc<RefCell<T>>,
+    version: Version,
     redraw_trigger: Rc<RefCell<bool>>,
+    observers: Rc<Observers<T>>,
+}
+
+// Runs each observer with the value just written
+fn notify<T>(value: &RefCell<T>, observers: &Observers<T>) {
+    let value = value.borrow();
+    for observer in observers.borrow().iter() {
+        observer(&value);
+    }
 }
 
 impl<T> State<T> {
     pub fn new(initial: T, redraw_trigger: Rc<RefCell<bool>>) -> Self {
         State {
             value: Rc::new(RefCell::new(initial)),
+            version: Version::new(),
             redraw_trigger,
+            observers: Rc::new(RefCell::new(Vec::new())),
         }
     }
 
@@ -23,18 +83,159 @@ impl<T> State<T> {
 
     pub fn set(&self, new_value: T) {
         *self.value.borrow_mut() = new_value;
-        // Trigger redraw
+        self.version.bump();
+        *self.redraw_trigger.borrow_mut() = true;
+        notify(&self.value, &self.observers);
+    }
+
+    /// Like `set`, but a value equal to the current one is dropped without
+    /// a redraw or observer calls. Returns whether the value changed.
+    pub fn set_if_changed(&self, new_value: T) -> bool
+    where
+        T: PartialEq,
+    {
+        if *self.value.borrow() == new_value {
+            return false;
+        }
+        self.set(new_value);
+        true
+    }
+
+    /// Modifies the value in place, as one write.
+    pub fn update(&self, f: impl FnOnce(&mut T)) {
+        f(&mut self.value.borrow_mut());
+        self.version.bump();
+        *self.redraw_trigger.borrow_mut() = true;
+        notify(&self.value, &self.observers);
+    }
+
+    /// Calls `observer` with the new value after every write, whether made
+    /// through the state or one of its bindings. Observers must not write
+    /// the state themselves.
+    pub fn on_change(&self, observer: impl Fn(&T) + 'static) {
+        self.observers.borrow_mut().push(Box::new(observer));
     }
 
     pub fn binding(&self) -> Binding<T> {
         Binding {
-            value: Rc::clone(&self.value),
+            source: Source::Value(Rc::clone(&self.value), Rc::clone(&self.observers)),
+            version: self.version.clone(),
+            redraw_trigger: Rc::clone(&self.redraw_trigger),
+        }
+    }
+}
+
+/// Thread-safe counterpart to `State`, for values written by background
+/// threads. Writes raise a shared flag that the engine checks every frame;
+/// get the flag from `SDLEngine::shared_redraw_flag`.
+pub struct SharedState<T> {
+    value: Arc<Mutex<T>>,
+    version: Arc<AtomicU64>,
+    redraw_flag: Arc<AtomicBool>,
+}
+
+impl<T> SharedState<T> {
+    pub fn new(initial: T, redraw_flag: Arc<AtomicBool>) -> Self {
+        SharedState {
+            value: Arc::new(Mutex::new(initial)),
+            version: Arc::new(AtomicU64::new(0)),
+            redraw_flag,
+        }
+    }
+
+    pub fn get(&self) -> T
+    where
+        T: Clone,
+    {
+        lock(&self.value).clone()
+    }
+
+    pub fn set(&self, new_value: T) {
+        *lock(&self.value) = new_value;
+        self.version.fetch_add(1, Ordering::AcqRel);
+        self.redraw_flag.store(true, Ordering::Release);
+    }
+
+    /// Modifies the value in place while holding the lock.
+    pub fn update(&self, f: impl FnOnce(&mut T)) {
+        f(&mut lock(&self.value));
+        self.version.fetch_add(1, Ordering::AcqRel);
+        self.redraw_flag.store(true, Ordering::Release);
+    }
+
+    /// Binding for views on the UI thread; reads take the lock each time.
+    pub fn binding(&self) -> Binding<T>
+    where
+        T: Clone + 'static,
+    {
+        let reader = self.clone();
+        let writer = self.clone();
+        Binding {
+            source: Source::Projected {
+                get: Rc::new(move || reader.get()),
+                set: Rc::new(move |value| writer.set(value)),
+            },
+            version: Version(VersionCounter::Shared(Arc::clone(&self.version))),
+            redraw_trigger: Rc::new(RefCell::new(false)),
+        }
+    }
+}
+
+impl<T> Clone for SharedState<T> {
+    fn clone(&self) -> Self {
+        SharedState {
+            value: Arc::clone(&self.value),
+            version: Arc::clone(&self.version),
+            redraw_flag: Arc::clone(&self.redraw_flag),
+        }
+    }
+}
+
+// A panicked writer can't leave a plain value half-written, so keep going
+fn lock<T>(value: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    value.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+enum Source<T> {
+    Value(Rc<RefCell<T>>, Rc<Observers<T>>),
+    // Read-only value produced on demand, e.g. by a `Computed`
+    Derived(Rc<dyn Fn() -> T>),
+    // Part of another binding's value, see `Binding::map`
+    Projected { get: Rc<dyn Fn() -> T>, set: Rc<dyn Fn(T)> },
+}
+
+impl<T> Clone for Source<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Source::Value(value, observers) => Source::Value(Rc::clone(value), Rc::clone(observers)),
+            Source::Derived(get) => Source::Derived(Rc::clone(get)),
+            Source::Projected { get, set } => Source::Projected { get: Rc::clone(get), set: Rc::clone(set) },
         }
     }
 }
 
 pub struct Binding<T> {
-    value: Rc<RefCell<T>>,
+    source: Source<T>,
+    version: Version,
+    redraw_trigger: Rc<RefCell<bool>>,
+}
+
+impl<T: Clone + 'static> Binding<T> {
+    /// A read-only binding that always holds `value`, for parameters that
+    /// take either a fixed value or state.
+    pub fn constant(value: T) -> Self {
+        Binding {
+            source: Source::Derived(Rc::new(move || value.clone())),
+            version: Version::new(),
+            redraw_trigger: Rc::new(RefCell::new(false)),
+        }
+    }
+}
+
+impl<T: Clone + 'static> From<T> for Binding<T> {
+    fn from(value: T) -> Self {
+        Binding::constant(value)
+    }
 }
 
 impl<T> Binding<T> {
@@ -42,19 +243,185 @@ impl<T> Binding<T> {
     where
         T: Clone,
     {
-        self.value.borrow().clone()
+        match &self.source {
+            Source::Value(value, _) => value.borrow().clone(),
+            Source::Derived(get) | Source::Projected { get, .. } => get(),
+        }
     }
 
+    /// Writes through to the underlying state. Derived bindings are
+    /// read-only and ignore writes.
     pub fn set(&self, new_value: T) {
-        *self.value.borrow_mut() = new_value;
-        // Trigger redraw
+        match &self.source {
+            Source::Value(value, observers) => {
+                *value.borrow_mut() = new_value;
+                self.version.bump();
+                *self.redraw_trigger.borrow_mut() = true;
+                notify(value, observers);
+            }
+            Source::Projected { set, .. } => set(new_value),
+            Source::Derived(_) => {}
+        }
+    }
+
+    /// Writes only when `new_value` differs from the current value, so equal
+    /// writes don't trigger a redraw. Returns whether it wrote.
+    pub fn set_if_changed(&self, new_value: T) -> bool
+    where
+        T: PartialEq,
+    {
+        if matches!(self.source, Source::Derived(_)) || self.with(|current| *current == new_value) {
+            return false;
+        }
+        self.set(new_value);
+        true
+    }
+
+    /// Binding to one part of this value, e.g. a struct field:
+    /// `person.map(|p| p.name.clone(), |p, name| p.name = name)`.
+    /// Writes update the part in place and go back through this binding.
+    pub fn map<U: 'static>(&self, get: impl Fn(&T) -> U + 'static, set: impl Fn(&mut T, U) + 'static) -> Binding<U>
+    where
+        T: 'static,
+    {
+        let reader = self.clone();
+        let writer = self.clone();
+        Binding {
+            source: Source::Projected {
+                get: Rc::new(move || reader.with(&get)),
+                set: Rc::new(move |part| writer.modify(|whole| set(whole, part))),
+            },
+            version: self.version.clone(),
+            redraw_trigger: Rc::clone(&self.redraw_trigger),
+        }
+    }
+
+    fn with<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        match &self.source {
+            Source::Value(value, _) => f(&value.borrow()),
+            Source::Derived(get) | Source::Projected { get, .. } => f(&get()),
+        }
+    }
+
+    fn modify(&self, f: impl FnOnce(&mut T)) {
+        match &self.source {
+            Source::Value(value, observers) => {
+                f(&mut value.borrow_mut());
+                self.version.bump();
+                *self.redraw_trigger.borrow_mut() = true;
+                notify(value, observers);
+            }
+            Source::Projected { get, set } => {
+                let mut whole = get();
+                f(&mut whole);
+                set(whole);
+            }
+            Source::Derived(_) => {}
+        }
     }
 }
 
 impl<T> Clone for Binding<T> {
     fn clone(&self) -> Self {
         Binding {
-            value: Rc::clone(&self.value),
+            source: self.source.clone(),
+            version: self.version.clone(),
+            redraw_trigger: Rc::clone(&self.redraw_trigger),
+        }
+    }
+}
+
+/// Something a `Computed` can depend on. The version counter is bumped
+/// every time the value is written.
+pub trait Dependency {
+    fn version_counter(&self) -> Version;
+    fn redraw_trigger(&self) -> Rc<RefCell<bool>>;
+}
+
+impl<T> Dependency for State<T> {
+    fn version_counter(&self) -> Version {
+        self.version.clone()
+    }
+
+    fn redraw_trigger(&self) -> Rc<RefCell<bool>> {
+        Rc::clone(&self.redraw_trigger)
+    }
+}
+
+impl<T> Dependency for Binding<T> {
+    fn version_counter(&self) -> Version {
+        self.version.clone()
+    }
+
+    fn redraw_trigger(&self) -> Rc<RefCell<bool>> {
+        Rc::clone(&self.redraw_trigger)
+    }
+}
+
+struct ComputedInner<T> {
+    compute: Box<dyn Fn() -> T>,
+    deps: Vec<Version>,
+    cache: RefCell<Option<(Vec<u64>, T)>>,
+    // Follows the dependencies, so a change shows before the value is recomputed
+    version: Version,
+    redraw_trigger: Rc<RefCell<bool>>,
+}
+
+/// Read-only value derived from other states. The closure only re-runs
+/// when one of the dependencies has been written since the last `get`.
+pub struct Computed<T> {
+    inner: Rc<ComputedInner<T>>,
+}
+
+impl<T: Clone + 'static> Computed<T> {
+    pub fn new<F: Fn() -> T + 'static>(compute: F, deps: &[&dyn Dependency]) -> Self {
+        // Dependencies normally share the engine's trigger, which their
+        // own `set` already marks when they change
+        let redraw_trigger = deps
+            .first()
+            .map(|dep| dep.redraw_trigger())
+            .unwrap_or_else(|| Rc::new(RefCell::new(false)));
+        Computed {
+            inner: Rc::new(ComputedInner {
+                compute: Box::new(compute),
+                deps: deps.iter().map(|dep| dep.version_counter()).collect(),
+                cache: RefCell::new(None),
+                version: Version(VersionCounter::Derived(deps.iter().map(|dep| dep.version_counter()).collect())),
+                redraw_trigger,
+            }),
+        }
+    }
+
+    pub fn get(&self) -> T {
+        Self::get_inner(&self.inner)
+    }
+
+    pub fn binding(&self) -> Binding<T> {
+        let inner = Rc::clone(&self.inner);
+        Binding {
+            source: Source::Derived(Rc::new(move || Self::get_inner(&inner))),
+            version: self.inner.version.clone(),
+            redraw_trigger: Rc::clone(&self.inner.redraw_trigger),
+        }
+    }
+
+    fn get_inner(inner: &ComputedInner<T>) -> T {
+        let versions: Vec<u64> = inner.deps.iter().map(|v| v.get()).collect();
+        if let Some((seen, value)) = inner.cache.borrow().as_ref() {
+            if *seen == versions {
+                return value.clone();
+            }
+        }
+        let value = (inner.compute)();
+        *inner.cache.borrow_mut() = Some((versions, value.clone()));
+        value
+    }
+}
+
+impl<T> Clone for Computed<T> {
+    fn clone(&self) -> Self {
+        Computed {
+            inner: Rc::clone(&self.inner),
         }
     }
-}
\ No newline at end of file
+}
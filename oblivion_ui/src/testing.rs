@@ -0,0 +1,262 @@
+// Helpers for testing views without a window
+
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::backend::{Backend, BackendEvent, EventLoop, EventSource, WindowId};
+use crate::color::Color;
+use crate::components::{Bounds, Event, Renderer, View};
+use crate::texture::{TextureCache, TextureId, TextureKey};
+use crate::themes::Theme;
+use crate::timer::TimerHandle;
+
+/// A single call made against a `Renderer`, with its arguments.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DrawCall {
+    Text { text: String, x: f32, y: f32, font_size: u32, color: Color },
+    Rect { x: f32, y: f32, w: f32, h: f32, color: Color },
+    RoundRect { x: f32, y: f32, w: f32, h: f32, radius: f32, color: Color },
+    RectOutline { x: f32, y: f32, w: f32, h: f32, thickness: f32, color: Color },
+    PushClip { x: f32, y: f32, w: f32, h: f32 },
+    PopClip,
+    Image { texture: TextureId, x: f32, y: f32, w: f32, h: f32, alpha: f32 },
+}
+
+/// Renderer that records every draw call instead of drawing.
+#[derive(Default)]
+pub struct MockRenderer {
+    pub calls: Vec<DrawCall>,
+    clip_stack: Vec<Bounds>,
+    /// Images and icons requested so far; nothing is read from disk.
+    pub images: TextureCache<TextureKey>,
+}
+
+impl MockRenderer {
+    pub fn new() -> Self {
+        MockRenderer {
+            calls: Vec::new(),
+            clip_stack: Vec::new(),
+            images: TextureCache::new(),
+        }
+    }
+
+    /// The effective clip rectangle: the intersection of all pushed clips.
+    pub fn current_clip(&self) -> Option<Bounds> {
+        self.clip_stack.last().copied()
+    }
+
+    /// Text draws in call order, as `(text, x, y)`.
+    pub fn texts(&self) -> Vec<(String, f32, f32)> {
+        self.calls
+            .iter()
+            .filter_map(|call| match call {
+                DrawCall::Text { text, x, y, .. } => Some((text.clone(), *x, *y)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Colors of text draws, in call order.
+    pub fn text_colors(&self) -> Vec<Color> {
+        self.calls
+            .iter()
+            .filter_map(|call| match call {
+                DrawCall::Text { color, .. } => Some(*color),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Rect draws in call order, as `(x, y, w, h)`.
+    pub fn rects(&self) -> Vec<(f32, f32, f32, f32)> {
+        self.calls
+            .iter()
+            .filter_map(|call| match call {
+                DrawCall::Rect { x, y, w, h, .. } => Some((*x, *y, *w, *h)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Fill colors of rect draws, in call order.
+    pub fn rect_colors(&self) -> Vec<Color> {
+        self.calls
+            .iter()
+            .filter_map(|call| match call {
+                DrawCall::Rect { color, .. } => Some(*color),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Outline draws in call order, as `(x, y, w, h, thickness)`.
+    pub fn outlines(&self) -> Vec<(f32, f32, f32, f32, f32)> {
+        self.calls
+            .iter()
+            .filter_map(|call| match call {
+                DrawCall::RectOutline { x, y, w, h, thickness, .. } => Some((*x, *y, *w, *h, *thickness)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.calls.clear();
+        self.clip_stack.clear();
+    }
+}
+
+impl Renderer for MockRenderer {
+    fn draw_text(&mut self, text: &str, x: f32, y: f32, font_size: u32, color: Color) {
+        self.calls.push(DrawCall::Text { text: text.to_string(), x, y, font_size, color });
+    }
+
+    fn draw_rect(&mut self, x: f32, y: f32, w: f32, h: f32, color: Color) {
+        self.calls.push(DrawCall::Rect { x, y, w, h, color });
+    }
+
+    fn draw_round_rect(&mut self, x: f32, y: f32, w: f32, h: f32, radius: f32, color: Color) {
+        self.calls.push(DrawCall::RoundRect { x, y, w, h, radius, color });
+    }
+
+    fn draw_rect_outline(&mut self, x: f32, y: f32, w: f32, h: f32, thickness: f32, color: Color) {
+        self.calls.push(DrawCall::RectOutline { x, y, w, h, thickness, color });
+    }
+
+    fn push_clip(&mut self, x: f32, y: f32, w: f32, h: f32) {
+        self.calls.push(DrawCall::PushClip { x, y, w, h });
+        let requested = Bounds::new(x, y, w, h);
+        let clip = match self.clip_stack.last() {
+            Some(outer) => outer.intersect(&requested),
+            None => requested,
+        };
+        self.clip_stack.push(clip);
+    }
+
+    fn pop_clip(&mut self) {
+        self.calls.push(DrawCall::PopClip);
+        self.clip_stack.pop();
+    }
+
+    fn load_image(&mut self, path: &Path) -> Option<TextureId> {
+        self.images.load_key(TextureKey::File(path.to_path_buf()), |key| Ok(key.clone())).ok().flatten()
+    }
+
+    fn load_icon(&mut self, path: &Path, width: u32, height: u32) -> Option<TextureId> {
+        self.images.load_key(TextureKey::icon(path, width, height), |key| Ok(key.clone())).ok().flatten()
+    }
+
+    fn draw_image(&mut self, texture: TextureId, x: f32, y: f32, w: f32, h: f32, alpha: f32) {
+        self.calls.push(DrawCall::Image { texture, x, y, w, h, alpha });
+    }
+}
+
+// Backend whose frames land in a MockRenderer and whose input is injected
+#[derive(Default)]
+struct RecordingSurface {
+    renderer: MockRenderer,
+    pending: Vec<BackendEvent>,
+}
+
+impl EventSource for RecordingSurface {
+    fn poll_events(&mut self, _timeout: Duration) -> Vec<(WindowId, BackendEvent)> {
+        self.pending.drain(..).map(|event| (0, event)).collect()
+    }
+}
+
+impl Backend for RecordingSurface {
+    fn draw_frame(&mut self, _window: WindowId, _background: Color, draw: &mut dyn FnMut(&mut dyn Renderer)) {
+        self.renderer.clear();
+        draw(&mut self.renderer);
+    }
+}
+
+/// Runs a view tree on the same `EventLoop` as `SDLEngine::run`, with a
+/// backend that records frames instead of showing them. Tests inject
+/// events, advance a virtual clock and inspect the draw calls of the last
+/// rendered frame.
+pub struct HeadlessEngine {
+    event_loop: EventLoop,
+    surface: RecordingSurface,
+    // Virtual time; only moves in `advance`
+    now: Instant,
+}
+
+impl HeadlessEngine {
+    pub fn new(root_view: Box<dyn View>, redraw_trigger: Rc<RefCell<bool>>) -> Self {
+        HeadlessEngine {
+            event_loop: EventLoop::new(0, root_view, redraw_trigger),
+            surface: RecordingSurface::default(),
+            now: Instant::now(),
+        }
+    }
+
+    pub fn with_theme(self, theme: Theme) -> Self {
+        self.event_loop.theme_handle().set_theme(theme);
+        self
+    }
+
+    /// Flag to pass to `SharedState::new`, as with `SDLEngine`.
+    pub fn shared_redraw_flag(&self) -> Arc<AtomicBool> {
+        self.event_loop.shared_redraw_flag()
+    }
+
+    /// Queues `event` for the next frame.
+    pub fn push_event(&mut self, event: Event) {
+        self.surface.pending.push(BackendEvent::Input(event));
+    }
+
+    /// Queues a press and release at `(x, y)`.
+    pub fn click(&mut self, x: f32, y: f32) {
+        self.push_event(Event::Click { x, y });
+        self.push_event(Event::MouseUp { x, y });
+    }
+
+    /// Runs `callback` once the virtual clock has moved on by `after`.
+    pub fn schedule(&mut self, after: Duration, callback: impl FnMut() + 'static) -> TimerHandle {
+        self.event_loop.schedule_at(self.now + after, None, callback)
+    }
+
+    pub fn schedule_repeating(&mut self, interval: Duration, callback: impl FnMut() + 'static) -> TimerHandle {
+        self.event_loop.schedule_at(self.now + interval, Some(interval), callback)
+    }
+
+    /// Runs one frame without moving the clock. Returns whether it redrew.
+    pub fn frame(&mut self) -> bool {
+        self.step(Duration::ZERO)
+    }
+
+    /// Moves the clock forward by `dt`, then runs one frame.
+    pub fn advance(&mut self, dt: Duration) -> bool {
+        self.now += dt;
+        self.step(dt)
+    }
+
+    fn step(&mut self, dt: Duration) -> bool {
+        let frames = self.event_loop.frame_count();
+        self.event_loop.step(&mut self.surface, self.now, dt.as_secs_f64());
+        self.event_loop.frame_count() > frames
+    }
+
+    /// Draw calls of the most recently rendered frame.
+    pub fn renderer(&self) -> &MockRenderer {
+        &self.surface.renderer
+    }
+
+    /// Text drawn in the most recent frame, in call order.
+    pub fn texts(&self) -> Vec<String> {
+        self.surface.renderer.texts().into_iter().map(|(text, _, _)| text).collect()
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.event_loop.frame_count()
+    }
+
+    pub fn root(&self) -> &dyn View {
+        self.event_loop.main_window().root()
+    }
+}
@@ -0,0 +1,195 @@
+// Images loaded by path, shared across frames
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::error::UiError;
+
+/// Handle to an image a renderer has loaded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TextureId(u32);
+
+/// What a cached texture was made from.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TextureKey {
+    /// A bitmap file at its own resolution.
+    File(PathBuf),
+    /// A vector file rasterized to a size in device pixels.
+    Icon { path: PathBuf, width: u32, height: u32 },
+}
+
+impl TextureKey {
+    pub fn icon(path: impl Into<PathBuf>, width: u32, height: u32) -> Self {
+        TextureKey::Icon { path: path.into(), width, height }
+    }
+
+    pub fn path(&self) -> &Path {
+        match self {
+            TextureKey::File(path) | TextureKey::Icon { path, .. } => path,
+        }
+    }
+}
+
+/// Most icon rasterizations a cache keeps. Icons are cached per size, so
+/// resizing one keeps adding entries; past this the least recently used
+/// is evicted.
+pub const MAX_ICONS: usize = 64;
+
+struct Entry<T> {
+    key: TextureKey,
+    texture: T,
+    last_used: u64,
+}
+
+/// Loads each key once and hands out the same id afterwards. Icons are
+/// evicted once there are more than `max_icons`; the id of an evicted icon
+/// may be handed out again, so look icons up each frame rather than
+/// keeping their ids.
+pub struct TextureCache<T> {
+    ids: HashMap<TextureKey, TextureId>,
+    entries: Vec<Option<Entry<T>>>,
+    // Keys whose decode failed, so a missing file isn't read every frame
+    failed: HashSet<TextureKey>,
+    // Textures dropped from the cache that the owner still has to free
+    evicted: Vec<T>,
+    max_icons: usize,
+    clock: u64,
+}
+
+impl<T> TextureCache<T> {
+    pub fn new() -> Self {
+        TextureCache {
+            ids: HashMap::new(),
+            entries: Vec::new(),
+            failed: HashSet::new(),
+            evicted: Vec::new(),
+            max_icons: MAX_ICONS,
+            clock: 0,
+        }
+    }
+
+    pub fn with_max_icons(mut self, max_icons: usize) -> Self {
+        self.max_icons = max_icons.max(1);
+        self
+    }
+
+    /// Id of the texture for `path`, calling `decode` only on the first
+    /// request. A failed load is remembered: its error is returned once, and
+    /// later requests give `Ok(None)` without decoding again.
+    pub fn load(&mut self, path: &Path, decode: impl FnOnce(&Path) -> Result<T, UiError>) -> Result<Option<TextureId>, UiError> {
+        self.load_key(TextureKey::File(path.to_path_buf()), |key| decode(key.path()))
+    }
+
+    /// Like `load`, for any kind of key.
+    pub fn load_key(&mut self, key: TextureKey, decode: impl FnOnce(&TextureKey) -> Result<T, UiError>) -> Result<Option<TextureId>, UiError> {
+        self.clock += 1;
+        if let Some(&id) = self.ids.get(&key) {
+            if let Some(entry) = self.entries[id.0 as usize].as_mut() {
+                entry.last_used = self.clock;
+            }
+            return Ok(Some(id));
+        }
+        if self.failed.contains(&key) {
+            return Ok(None);
+        }
+        let texture = match decode(&key) {
+            Ok(texture) => texture,
+            Err(e) => {
+                self.failed.insert(key);
+                return Err(e);
+            }
+        };
+        if matches!(key, TextureKey::Icon { .. }) && self.icon_count() >= self.max_icons {
+            self.evict_least_used_icon();
+        }
+        let entry = Entry { key: key.clone(), texture, last_used: self.clock };
+        let id = match self.entries.iter().position(Option::is_none) {
+            Some(free) => {
+                self.entries[free] = Some(entry);
+                TextureId(free as u32)
+            }
+            None => {
+                self.entries.push(Some(entry));
+                TextureId(self.entries.len() as u32 - 1)
+            }
+        };
+        self.ids.insert(key, id);
+        Ok(Some(id))
+    }
+
+    fn icon_count(&self) -> usize {
+        self.ids.keys().filter(|key| matches!(key, TextureKey::Icon { .. })).count()
+    }
+
+    fn evict_least_used_icon(&mut self) {
+        let oldest = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| entry.as_ref().map(|entry| (index, entry)))
+            .filter(|(_, entry)| matches!(entry.key, TextureKey::Icon { .. }))
+            .min_by_key(|(_, entry)| entry.last_used)
+            .map(|(index, _)| index);
+        if let Some(entry) = oldest.and_then(|index| self.entries[index].take()) {
+            self.ids.remove(&entry.key);
+            self.evicted.push(entry.texture);
+        }
+    }
+
+    /// Textures evicted since the last call, for the owner to free.
+    pub fn drain_evicted(&mut self) -> Vec<T> {
+        std::mem::take(&mut self.evicted)
+    }
+
+    pub fn get(&self, id: TextureId) -> Option<&T> {
+        self.entries.get(id.0 as usize)?.as_ref().map(|entry| &entry.texture)
+    }
+
+    pub fn get_mut(&mut self, id: TextureId) -> Option<&mut T> {
+        self.entries.get_mut(id.0 as usize)?.as_mut().map(|entry| &mut entry.texture)
+    }
+
+    /// Number of textures currently cached.
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+}
+
+impl<T> Default for TextureCache<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Decodes a PNG or JPEG file into RGBA pixels.
+pub fn decode_image(path: &Path) -> Result<image::RgbaImage, UiError> {
+    Ok(image::open(path)?.to_rgba8())
+}
+
+/// Renders an SVG file to `width` x `height` pixels, stretched to fit.
+pub fn rasterize_svg(path: &Path, width: u32, height: u32) -> Result<image::RgbaImage, UiError> {
+    use resvg::{tiny_skia, usvg};
+
+    let data = std::fs::read(path)?;
+    let tree = usvg::Tree::from_data(&data, &usvg::Options::default()).map_err(|e| UiError::ParseError(e.to_string()))?;
+    let mut pixmap = tiny_skia::Pixmap::new(width, height)
+        .ok_or_else(|| UiError::RenderError(format!("can't rasterize an icon at {}x{}", width, height)))?;
+    let size = tree.size();
+    let transform = tiny_skia::Transform::from_scale(width as f32 / size.width(), height as f32 / size.height());
+    resvg::render(&tree, transform, &mut pixmap.as_mut());
+
+    // tiny-skia stores premultiplied alpha; textures expect straight alpha
+    let rgba = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|pixel| {
+            let color = pixel.demultiply();
+            [color.red(), color.green(), color.blue(), color.alpha()]
+        })
+        .collect();
+    image::RgbaImage::from_raw(width, height, rgba).ok_or_else(|| UiError::RenderError("icon size mismatch".to_string()))
+}
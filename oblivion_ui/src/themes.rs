@@ -1,35 +1,191 @@
-#[derive(Clone)]
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use crate::color::Color;
+use crate::error::UiError;
+
+#[derive(Clone, PartialEq)]
 pub struct Theme {
-    pub primary_color: (u8, u8, u8),
-    pub secondary_color: (u8, u8, u8),
-    pub background_color: (u8, u8, u8),
-    pub text_color: (u8, u8, u8),
+    pub primary_color: Color,
+    pub secondary_color: Color,
+    pub background_color: Color,
+    pub text_color: Color,
+    /// Path to a TTF font; `None` uses the built-in placeholder glyphs.
+    pub font_path: Option<String>,
     pub font_size: u32,
+    pub title_font_size: u32,
+    pub caption_font_size: u32,
     pub is_dark: bool,
 }
 
+/// Typographic role of a piece of text, selecting its font size.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TextRole {
+    #[default]
+    Body,
+    Title,
+    Caption,
+}
+
 impl Default for Theme {
     fn default() -> Self {
         Theme {
-            primary_color: (0, 122, 255),
-            secondary_color: (142, 142, 147),
-            background_color: (255, 255, 255),
-            text_color: (0, 0, 0),
+            primary_color: Color::rgb(0, 122, 255),
+            secondary_color: Color::rgb(142, 142, 147),
+            background_color: Color::rgb(255, 255, 255),
+            text_color: Color::rgb(0, 0, 0),
+            font_path: None,
             font_size: 14,
+            title_font_size: 22,
+            caption_font_size: 11,
             is_dark: false,
         }
     }
 }
 
 impl Theme {
+    /// Parses `#rgb`, `#rrggbb` or `#rrggbbaa` into an opaque color (alpha
+    /// is dropped). The leading `#` is optional. Use `Color::from_hex` to
+    /// keep the alpha channel.
+    pub fn parse_hex(s: &str) -> Result<Color, UiError> {
+        Color::from_hex(s).map(|c| Color::rgb(c.r, c.g, c.b))
+    }
+
+    pub fn to_hex(color: impl Into<Color>) -> String {
+        let color = color.into();
+        Color::rgb(color.r, color.g, color.b).to_hex()
+    }
+
+    /// Loads a theme from a TOML file. See `from_toml_str` for the format.
+    pub fn from_file(path: &str) -> Result<Theme, UiError> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| UiError::ThemeError(format!("{}: {}", path, e)))?;
+        Theme::from_toml_str(&content)
+    }
+
+    /// Parses a theme from TOML. Colors may be `[r, g, b]` arrays or
+    /// `"#rrggbb"` strings; keys that are left out keep the value from
+    /// `Theme::default()` (or `Theme::dark()` when `is_dark = true`).
+    pub fn from_toml_str(content: &str) -> Result<Theme, UiError> {
+        let table: toml::Table = content
+            .parse()
+            .map_err(|e: toml::de::Error| UiError::ThemeError(e.to_string()))?;
+
+        let is_dark = match table.get("is_dark") {
+            Some(value) => value
+                .as_bool()
+                .ok_or_else(|| UiError::ThemeError("is_dark must be a boolean".to_string()))?,
+            None => false,
+        };
+        let mut theme = if is_dark { Theme::dark() } else { Theme::default() };
+
+        for (key, value) in &table {
+            match key.as_str() {
+                "primary_color" => theme.primary_color = color_value(key, value)?,
+                "secondary_color" => theme.secondary_color = color_value(key, value)?,
+                "background_color" => theme.background_color = color_value(key, value)?,
+                "text_color" => theme.text_color = color_value(key, value)?,
+                "font_size" => theme.font_size = size_value(key, value)?,
+                "title_font_size" => theme.title_font_size = size_value(key, value)?,
+                "caption_font_size" => theme.caption_font_size = size_value(key, value)?,
+                "font_path" => {
+                    let path = value
+                        .as_str()
+                        .ok_or_else(|| UiError::ThemeError("font_path must be a string".to_string()))?;
+                    theme.font_path = Some(path.to_string());
+                }
+                "is_dark" => {}
+                _ => return Err(UiError::ThemeError(format!("unknown key '{}'", key))),
+            }
+        }
+        Ok(theme)
+    }
+
+    pub fn font_size_for(&self, role: TextRole) -> u32 {
+        match role {
+            TextRole::Body => self.font_size,
+            TextRole::Title => self.title_font_size,
+            TextRole::Caption => self.caption_font_size,
+        }
+    }
+
     pub fn dark() -> Self {
         Theme {
-            primary_color: (10, 132, 255),
-            secondary_color: (142, 142, 147),
-            background_color: (28, 28, 30),
-            text_color: (255, 255, 255),
+            primary_color: Color::rgb(10, 132, 255),
+            secondary_color: Color::rgb(142, 142, 147),
+            background_color: Color::rgb(28, 28, 30),
+            text_color: Color::rgb(255, 255, 255),
+            font_path: None,
             font_size: 14,
+            title_font_size: 22,
+            caption_font_size: 11,
             is_dark: true,
         }
     }
-}
\ No newline at end of file
+}
+
+/// Shared handle to the engine's active theme. Setting a theme marks the
+/// redraw trigger so the next frame picks it up.
+#[derive(Clone)]
+pub struct ThemeHandle {
+    theme: Rc<RefCell<Theme>>,
+    // Whether the app has picked a theme through `set_theme`
+    chosen: Rc<Cell<bool>>,
+    redraw_trigger: Rc<RefCell<bool>>,
+}
+
+impl ThemeHandle {
+    pub fn new(theme: Theme, redraw_trigger: Rc<RefCell<bool>>) -> Self {
+        ThemeHandle {
+            theme: Rc::new(RefCell::new(theme)),
+            chosen: Rc::new(Cell::new(false)),
+            redraw_trigger,
+        }
+    }
+
+    pub fn get(&self) -> Theme {
+        self.theme.borrow().clone()
+    }
+
+    pub fn set_theme(&self, theme: Theme) {
+        self.chosen.set(true);
+        *self.theme.borrow_mut() = theme;
+        *self.redraw_trigger.borrow_mut() = true;
+    }
+
+    /// Sets `theme` unless one was already chosen with `set_theme`.
+    pub fn set_default(&self, theme: Theme) {
+        if !self.chosen.get() {
+            *self.theme.borrow_mut() = theme;
+            *self.redraw_trigger.borrow_mut() = true;
+        }
+    }
+}
+
+fn color_value(key: &str, value: &toml::Value) -> Result<Color, UiError> {
+    match value {
+        // Names the key in the message, which already says it's a theme error
+        toml::Value::String(hex) => Color::from_hex(hex).map_err(|e| match e {
+            UiError::ThemeError(message) => UiError::ThemeError(format!("{}: {}", key, message)),
+            other => other,
+        }),
+        toml::Value::Array(items) if items.len() == 3 => {
+            let mut channels = [0u8; 3];
+            for (channel, item) in channels.iter_mut().zip(items) {
+                *channel = item
+                    .as_integer()
+                    .and_then(|v| u8::try_from(v).ok())
+                    .ok_or_else(|| UiError::ThemeError(format!("{}: channels must be 0-255", key)))?;
+            }
+            Ok(Color::rgb(channels[0], channels[1], channels[2]))
+        }
+        _ => Err(UiError::ThemeError(format!("{}: expected [r, g, b] or \"#rrggbb\"", key))),
+    }
+}
+
+fn size_value(key: &str, value: &toml::Value) -> Result<u32, UiError> {
+    value
+        .as_integer()
+        .and_then(|v| u32::try_from(v).ok())
+        .filter(|&size| size > 0)
+        .ok_or_else(|| UiError::ThemeError(format!("{} must be a positive integer", key)))
+}
@@ -0,0 +1,86 @@
+// One-shot and repeating callbacks run by the engine loop
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// Cancels a scheduled callback. Dropping the handle leaves it scheduled.
+#[derive(Clone)]
+pub struct TimerHandle {
+    cancelled: Rc<Cell<bool>>,
+}
+
+impl TimerHandle {
+    pub fn cancel(&self) {
+        self.cancelled.set(true);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.get()
+    }
+}
+
+struct Timer {
+    due: Instant,
+    // Set for repeating timers
+    interval: Option<Duration>,
+    callback: Box<dyn FnMut()>,
+    cancelled: Rc<Cell<bool>>,
+}
+
+/// Shortest gap between runs of a repeating timer; shorter intervals,
+/// including zero, are raised to it.
+pub const MIN_INTERVAL: Duration = Duration::from_millis(1);
+
+#[derive(Default)]
+pub struct TimerQueue {
+    timers: Vec<Timer>,
+}
+
+impl TimerQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn schedule_at(&mut self, due: Instant, interval: Option<Duration>, callback: impl FnMut() + 'static) -> TimerHandle {
+        let cancelled = Rc::new(Cell::new(false));
+        let interval = interval.map(|interval| interval.max(MIN_INTERVAL));
+        self.timers.push(Timer { due, interval, callback: Box::new(callback), cancelled: cancelled.clone() });
+        TimerHandle { cancelled }
+    }
+
+    /// Runs every callback due at `now`, rescheduling repeating ones and
+    /// dropping the rest. Returns whether anything ran.
+    pub fn fire_due(&mut self, now: Instant) -> bool {
+        self.timers.retain(|timer| !timer.cancelled.get());
+        let mut fired = false;
+        for index in due_timers(self.timers.iter().map(|timer| timer.due), now) {
+            let timer = &mut self.timers[index];
+            (timer.callback)();
+            fired = true;
+            match timer.interval {
+                // Skip missed ticks rather than firing them all at once
+                Some(interval) => {
+                    while timer.due <= now {
+                        timer.due += interval;
+                    }
+                }
+                None => timer.cancelled.set(true),
+            }
+        }
+        self.timers.retain(|timer| !timer.cancelled.get());
+        fired
+    }
+
+    /// When the earliest pending timer is due.
+    pub fn next_due(&self) -> Option<Instant> {
+        self.timers.iter().filter(|timer| !timer.cancelled.get()).map(|timer| timer.due).min()
+    }
+}
+
+/// Indices of the times at or before `now`, earliest first.
+pub fn due_timers(due: impl IntoIterator<Item = Instant>, now: Instant) -> Vec<usize> {
+    let mut ready: Vec<(usize, Instant)> = due.into_iter().enumerate().filter(|&(_, at)| at <= now).collect();
+    ready.sort_by_key(|&(_, at)| at);
+    ready.into_iter().map(|(index, _)| index).collect()
+}
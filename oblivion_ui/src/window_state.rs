@@ -0,0 +1,39 @@
+// Remembering window placement between runs
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Last-known window position and size, stored as JSON.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl WindowGeometry {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("geometry always serializes")
+    }
+
+    pub fn from_json(json: &str) -> Option<WindowGeometry> {
+        serde_json::from_str(json).ok()
+    }
+
+    /// Reads a saved geometry. A missing or unreadable file yields `None`
+    /// so callers fall back to their defaults.
+    pub fn load(path: &Path) -> Option<WindowGeometry> {
+        let json = std::fs::read_to_string(path).ok()?;
+        let geometry = Self::from_json(&json)?;
+        // A zero-sized window can't be shown; treat it as corrupt
+        if geometry.width == 0 || geometry.height == 0 {
+            return None;
+        }
+        Some(geometry)
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, self.to_json())
+    }
+}